@@ -1,17 +1,39 @@
 #![allow(dead_code)]
 
-use crate::config::{AppConfig, ClusterConfig};
+use crate::audit::AuditEntry;
+use crate::config::{AppConfig, ClusterConfig, Environment};
+use crate::filter::{self, Filter, FilterError};
+use crate::import::ImportedCluster;
+use crate::kafka::client::KafkaCommand;
+use crate::kafka::compacted::TableSummary;
+use crate::kafka::page::{Page, PageAnchor};
+use crate::kafka::reassign::{MoveProgress, MoveStatus, ReassignmentPlan};
+use crate::kafka::search::{PartitionScan, SearchBound, SearchRange};
+use crate::kafka::analysis::{self, Analysis};
+use crate::kafka::timeline::{BucketSize, Timeline};
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
 
 /// Navigation route
 #[derive(Debug, Clone, PartialEq)]
 pub enum Route {
     ClusterSelect,
     Dashboard,
+    Brokers,
+    BrokerDetail(i32),
     Topics,
     TopicDetail(String),
     Messages(String),
+    TopicSearch(String),
+    TopicTable(String),
+    Timeline(String),
+    TopicAnalysis(String),
     ConsumerGroups,
     ConsumerGroupDetail(String),
+    Reassignment,
+    Acls,
+    ClientStats,
+    AuditLog,
 }
 
 /// Which panel has focus
@@ -32,6 +54,16 @@ pub enum Dialog {
     ResetOffset(ResetOffsetDialog),
     EditCluster(EditClusterDialog),
     ConnectionTest(ConnectionTestDialog),
+    ElectLeaders(ElectLeadersDialog),
+    ReplicationFactor(ReplicationFactorDialog),
+    Confirm(ConfirmDialog),
+    CreateAcl(CreateAclDialog),
+    PasswordPrompt(PasswordPromptDialog),
+    ImportClusters(ImportClustersDialog),
+    ProdConfirm(ProdConfirmDialog),
+    Search(SearchDialog),
+    GoToOffset(GoToOffsetDialog),
+    Timeline(TimelineDialog),
 }
 
 #[derive(Debug, Clone)]
@@ -79,7 +111,11 @@ pub struct ProduceMessageDialog {
     pub topic: String,
     pub key: String,
     pub value: String,
+    /// `k1:v1,k2:v2`; a name without `:` is a header with a null value
     pub headers: String,
+    /// Send a null key or value instead of the text, toggled with Ctrl+N
+    pub null_key: bool,
+    pub null_value: bool,
     pub focused_field: usize,
     pub result_message: Option<String>,
 }
@@ -91,10 +127,173 @@ impl ProduceMessageDialog {
             key: String::new(),
             value: String::new(),
             headers: String::new(),
+            null_key: true,
+            null_value: false,
             focused_field: 0,
             result_message: None,
         }
     }
+
+    pub fn parse_headers(&self) -> Vec<(String, Option<String>)> {
+        self.headers
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| match pair.split_once(':') {
+                Some((k, v)) => (k.trim().to_string(), Some(v.trim().to_string())),
+                None => (pair.to_string(), None),
+            })
+            .collect()
+    }
+}
+
+/// Search a range of a topic in the backend
+#[derive(Debug, Clone)]
+pub struct SearchDialog {
+    pub topic: String,
+    pub query: String,
+    /// Empty for the start of the log, an offset, or a time
+    pub from: String,
+    /// Empty for the current end of the log, an offset (inclusive), or a time
+    pub to: String,
+    pub focused_field: usize,
+    pub error: Option<String>,
+}
+
+impl SearchDialog {
+    pub fn new(topic: &str, query: &str) -> Self {
+        Self {
+            topic: topic.to_string(),
+            query: query.to_string(),
+            from: String::new(),
+            to: String::new(),
+            focused_field: 0,
+            error: None,
+        }
+    }
+
+    pub fn field_mut(&mut self) -> &mut String {
+        match self.focused_field {
+            0 => &mut self.query,
+            1 => &mut self.from,
+            _ => &mut self.to,
+        }
+    }
+
+    pub fn parse(&self) -> Result<(Filter, SearchRange), String> {
+        let filter = filter::parse(&self.query)
+            .map_err(|e| format!("Filter: {}", e))?
+            .ok_or_else(|| "Enter a filter to search for".to_string())?;
+        let bound = |text: &str, label: &str| -> Result<SearchBound, String> {
+            let text = text.trim();
+            if text.is_empty() {
+                Ok(SearchBound::Edge)
+            } else if let Ok(offset) = text.parse::<i64>() {
+                Ok(SearchBound::Offset(offset))
+            } else {
+                filter::parse_time(text)
+                    .map(SearchBound::Timestamp)
+                    .ok_or_else(|| format!("{}: expected an offset, a time like 2024-05-01T12:00:00, or -1d", label))
+            }
+        };
+        let range = SearchRange {
+            from: bound(&self.from, "From")?,
+            to: bound(&self.to, "To")?,
+        };
+        Ok((filter, range))
+    }
+}
+
+/// Window and bucket size of a message timeline
+#[derive(Debug, Clone)]
+pub struct TimelineDialog {
+    pub topic: String,
+    /// A time, e.g. `-1h`
+    pub from: String,
+    /// A time; empty for now
+    pub to: String,
+    pub bucket: BucketSize,
+    pub focused_field: usize,
+    pub error: Option<String>,
+}
+
+impl TimelineDialog {
+    pub fn new(topic: &str) -> Self {
+        Self {
+            topic: topic.to_string(),
+            from: "-1h".to_string(),
+            to: String::new(),
+            bucket: BucketSize::Minute,
+            focused_field: 0,
+            error: None,
+        }
+    }
+
+    pub fn field_mut(&mut self) -> &mut String {
+        match self.focused_field {
+            0 => &mut self.from,
+            _ => &mut self.to,
+        }
+    }
+
+    /// The window in milliseconds since the epoch
+    pub fn parse(&self) -> Result<(i64, i64), String> {
+        let time = |text: &str, label: &str| {
+            filter::parse_time(text.trim())
+                .ok_or_else(|| format!("{}: expected a time like 2024-05-01T12:00:00 or -1h", label))
+        };
+        let from = time(&self.from, "From")?;
+        let to = if self.to.trim().is_empty() {
+            chrono::Utc::now().timestamp_millis()
+        } else {
+            time(&self.to, "To")?
+        };
+        if from >= to {
+            return Err("From must be before To".to_string());
+        }
+        Ok((from, to))
+    }
+}
+
+/// Jump to one record by partition and offset
+#[derive(Debug, Clone)]
+pub struct GoToOffsetDialog {
+    pub topic: String,
+    pub partition: String,
+    pub offset: String,
+    /// Records to show on either side
+    pub context: String,
+    pub focused_field: usize,
+    pub error: Option<String>,
+}
+
+impl GoToOffsetDialog {
+    pub fn new(topic: &str) -> Self {
+        Self {
+            topic: topic.to_string(),
+            partition: String::new(),
+            offset: String::new(),
+            context: "5".to_string(),
+            focused_field: 0,
+            error: None,
+        }
+    }
+
+    pub fn field_mut(&mut self) -> &mut String {
+        match self.focused_field {
+            0 => &mut self.partition,
+            1 => &mut self.offset,
+            _ => &mut self.context,
+        }
+    }
+
+    /// Partition, offset and context
+    pub fn parse(&self) -> Result<(i32, i64, i64), String> {
+        let partition = self.partition.trim().parse().map_err(|_| "Partition must be a number".to_string())?;
+        let offset = self.offset.trim().parse().map_err(|_| "Offset must be a number".to_string())?;
+        let context = self.context.trim().parse::<i64>().map_err(|_| "Context must be a number".to_string())?;
+        Ok((partition, offset, context.clamp(0, 500)))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -126,14 +325,20 @@ impl ResetOffsetDialog {
 pub struct EditClusterDialog {
     pub name: String,
     pub brokers: String,
-    pub auth_type: usize, // 0=None, 1=SASL/PLAIN, 2=SCRAM-256, 3=SCRAM-512, 4=SSL
-    pub username: String,
-    pub password: String,
+    pub auth_type: usize, // 0=None, 1=SASL/PLAIN, 2=SCRAM-256, 3=SCRAM-512, 4=SSL, 8..=10=OAUTHBEARER
+    pub username: String, // also the OAuth client id / unsecured principal
+    pub password: String, // also the OAuth client secret
     pub ca_cert: String,
     pub client_cert: String,
     pub client_key: String,
+    pub token_endpoint: String,
+    pub scope: String,
+    pub properties: String, // key=value; key=value
+    pub environment: Option<Environment>,
+    pub read_only: bool,
     pub focused_field: usize,
     pub editing_index: Option<usize>, // None = new, Some(i) = editing
+    pub error: Option<String>,
 }
 
 impl Default for EditClusterDialog {
@@ -147,15 +352,81 @@ impl Default for EditClusterDialog {
             ca_cert: String::new(),
             client_cert: String::new(),
             client_key: String::new(),
+            token_endpoint: String::new(),
+            scope: String::new(),
+            properties: String::new(),
+            environment: None,
+            read_only: false,
             focused_field: 0,
             editing_index: None,
+            error: None,
         }
     }
 }
 
 impl EditClusterDialog {
+    pub const AUTH_TYPE_COUNT: usize = 11;
+
+    /// Index of the properties field, which always comes after the auth fields.
+    pub fn properties_field(&self) -> usize {
+        match self.auth_type {
+            1 | 2 | 3 => 5, // name, brokers, auth, user, pass
+            4 => 6,         // name, brokers, auth, ca, cert, key
+            5 | 6 | 7 => 6, // name, brokers, auth, user, pass, ca_cert(opt)
+            8 => 7,         // name, brokers, auth, endpoint, client id, secret, scope
+            9 => 8,         // ... plus ca_cert(opt)
+            10 => 4,        // name, brokers, auth, principal
+            _ => 3,         // name, brokers, auth
+        }
+    }
+
+    /// Environment and read-only toggle follow the properties field.
+    pub fn environment_field(&self) -> usize {
+        self.properties_field() + 1
+    }
+
+    pub fn read_only_field(&self) -> usize {
+        self.properties_field() + 2
+    }
+
+    pub fn field_count(&self) -> usize {
+        self.properties_field() + 3
+    }
+
+    /// Cycles none -> dev -> staging -> prod.
+    pub fn cycle_environment(&mut self, delta: isize) {
+        let options = Environment::ALL.len() as isize + 1;
+        let current = match self.environment {
+            None => 0,
+            Some(env) => Environment::ALL.iter().position(|e| *e == env).unwrap_or(0) as isize + 1,
+        };
+        let next = (current + delta).rem_euclid(options);
+        self.environment = if next == 0 { None } else { Some(Environment::ALL[next as usize - 1]) };
+    }
+
+    /// The text field behind `focused_field` for the current auth type, if any.
+    pub fn field_mut(&mut self, field: usize) -> Option<&mut String> {
+        if field == self.properties_field() {
+            return Some(&mut self.properties);
+        }
+        match (field, self.auth_type) {
+            (0, _) => Some(&mut self.name),
+            (1, _) => Some(&mut self.brokers),
+            (3, 1..=3 | 5..=7 | 10) | (4, 8 | 9) => Some(&mut self.username),
+            (4, 1..=3 | 5..=7) | (5, 8 | 9) => Some(&mut self.password),
+            (3, 4) | (5, 5..=7) | (7, 9) => Some(&mut self.ca_cert),
+            (4, 4) => Some(&mut self.client_cert),
+            (5, 4) => Some(&mut self.client_key),
+            (3, 8 | 9) => Some(&mut self.token_endpoint),
+            (6, 8 | 9) => Some(&mut self.scope),
+            _ => None,
+        }
+    }
+
     pub fn from_config(config: &ClusterConfig, index: usize) -> Self {
         use crate::config::AuthConfig;
+        let mut token_endpoint = String::new();
+        let mut scope = String::new();
         let (auth_type, username, password, ca_cert, client_cert, client_key) = match &config.auth {
             AuthConfig::None => (0, String::new(), String::new(), String::new(), String::new(), String::new()),
             AuthConfig::SaslPlain { username, password } => (1, username.clone(), password.clone(), String::new(), String::new(), String::new()),
@@ -172,6 +443,17 @@ impl EditClusterDialog {
             AuthConfig::SaslSslPlain { username, password, ca_cert } => (5, username.clone(), password.clone(), ca_cert.clone().unwrap_or_default(), String::new(), String::new()),
             AuthConfig::SaslSslScram256 { username, password, ca_cert } => (6, username.clone(), password.clone(), ca_cert.clone().unwrap_or_default(), String::new(), String::new()),
             AuthConfig::SaslSslScram512 { username, password, ca_cert } => (7, username.clone(), password.clone(), ca_cert.clone().unwrap_or_default(), String::new(), String::new()),
+            AuthConfig::SaslOAuthBearer { token_endpoint: endpoint, client_id, client_secret, scope: s } => {
+                token_endpoint = endpoint.clone();
+                scope = s.clone().unwrap_or_default();
+                (8, client_id.clone(), client_secret.clone(), String::new(), String::new(), String::new())
+            }
+            AuthConfig::SaslSslOAuthBearer { token_endpoint: endpoint, client_id, client_secret, scope: s, ca_cert } => {
+                token_endpoint = endpoint.clone();
+                scope = s.clone().unwrap_or_default();
+                (9, client_id.clone(), client_secret.clone(), ca_cert.clone().unwrap_or_default(), String::new(), String::new())
+            }
+            AuthConfig::SaslOAuthBearerUnsecured { principal } => (10, principal.clone(), String::new(), String::new(), String::new(), String::new()),
         };
         Self {
             name: config.name.clone(),
@@ -182,14 +464,22 @@ impl EditClusterDialog {
             ca_cert,
             client_cert,
             client_key,
+            token_endpoint,
+            scope,
+            properties: crate::config::format_properties(&config.properties),
+            environment: config.environment,
+            read_only: config.read_only,
             focused_field: 0,
             editing_index: Some(index),
+            error: None,
         }
     }
 
-    pub fn to_config(&self) -> ClusterConfig {
+    /// Fails when the properties don't parse
+    pub fn to_config(&self) -> Result<ClusterConfig, String> {
         use crate::config::AuthConfig;
         let ca = if self.ca_cert.is_empty() { None } else { Some(self.ca_cert.clone()) };
+        let scope = if self.scope.is_empty() { None } else { Some(self.scope.clone()) };
         let auth = match self.auth_type {
             1 => AuthConfig::SaslPlain {
                 username: self.username.clone(),
@@ -223,13 +513,33 @@ impl EditClusterDialog {
                 password: self.password.clone(),
                 ca_cert: ca,
             },
+            8 => AuthConfig::SaslOAuthBearer {
+                token_endpoint: self.token_endpoint.clone(),
+                client_id: self.username.clone(),
+                client_secret: self.password.clone(),
+                scope,
+            },
+            9 => AuthConfig::SaslSslOAuthBearer {
+                token_endpoint: self.token_endpoint.clone(),
+                client_id: self.username.clone(),
+                client_secret: self.password.clone(),
+                scope,
+                ca_cert: ca,
+            },
+            10 => AuthConfig::SaslOAuthBearerUnsecured {
+                principal: self.username.clone(),
+            },
             _ => AuthConfig::None,
         };
-        ClusterConfig {
+        let properties = crate::config::parse_properties(&self.properties).map_err(|e| format!("Properties: {}", e))?;
+        Ok(ClusterConfig {
             name: self.name.clone(),
             brokers: self.brokers.clone(),
             auth,
-        }
+            properties,
+            environment: self.environment,
+            read_only: self.read_only,
+        })
     }
 }
 
@@ -246,6 +556,192 @@ pub enum ConnectionTestStatus {
     Failed(String),
 }
 
+#[derive(Debug, Clone)]
+pub struct ElectLeadersDialog {
+    pub topic: String,
+    pub partitions: Vec<i32>,
+    pub running: bool,
+    pub results: Option<Vec<ElectionResult>>,
+}
+
+impl ElectLeadersDialog {
+    pub fn new(topic: &str, partitions: Vec<i32>) -> Self {
+        Self {
+            topic: topic.to_string(),
+            partitions,
+            running: false,
+            results: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplicationFactorDialog {
+    pub topic: String,
+    pub replication_factor: String,
+}
+
+impl ReplicationFactorDialog {
+    pub fn new(topic: &str, current: usize) -> Self {
+        Self {
+            topic: topic.to_string(),
+            replication_factor: current.to_string(),
+        }
+    }
+}
+
+/// Asks for the password of a cluster whose secret is `prompt:`. The
+/// password is only handed to the backend and never stored in the config.
+#[derive(Debug, Clone)]
+pub struct PasswordPromptDialog {
+    pub cluster_index: usize,
+    pub cluster_name: String,
+    pub password: String,
+    /// Run a connection test instead of connecting
+    pub test_only: bool,
+}
+
+/// Typed confirmation for a change on a prod cluster. The command is only
+/// sent once the cluster name has been typed.
+#[derive(Debug, Clone)]
+pub struct ProdConfirmDialog {
+    pub cluster_name: String,
+    pub mutation: String,
+    pub command: KafkaCommand,
+    pub confirm_input: String,
+    /// The dialog the change came from, shown again after sending
+    pub resume: Option<Box<Dialog>>,
+}
+
+/// Import from client.properties / kcat.conf files. The paths are typed
+/// first; Enter parses them into a preview and a second Enter adds them.
+#[derive(Debug, Clone)]
+pub struct ImportClustersDialog {
+    pub paths: String, // comma separated
+    pub preview: Option<Vec<ImportedCluster>>,
+    pub errors: Vec<String>,
+}
+
+impl Default for ImportClustersDialog {
+    fn default() -> Self {
+        let paths = crate::import::default_paths()
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        Self {
+            paths,
+            preview: None,
+            errors: Vec::new(),
+        }
+    }
+}
+
+/// Fields: 0=resource type, 1=resource name, 2=pattern type, 3=principal,
+/// 4=host, 5=operation, 6=permission. Enum fields cycle with ←/→.
+#[derive(Debug, Clone)]
+pub struct CreateAclDialog {
+    pub resource_type: usize,
+    pub resource_name: String,
+    pub pattern_type: usize,
+    pub principal: String,
+    pub host: String,
+    pub operation: usize,
+    pub permission: usize,
+    pub focused_field: usize,
+}
+
+impl Default for CreateAclDialog {
+    fn default() -> Self {
+        Self {
+            resource_type: 0,
+            resource_name: String::new(),
+            pattern_type: 0,
+            principal: "User:".to_string(),
+            host: "*".to_string(),
+            operation: 1,
+            permission: 0,
+            focused_field: 0,
+        }
+    }
+}
+
+impl CreateAclDialog {
+    pub const FIELD_COUNT: usize = 7;
+
+    pub fn to_binding(&self) -> AclBinding {
+        let resource_type = AclResourceType::ALL[self.resource_type % AclResourceType::ALL.len()];
+        // The cluster resource is always named "kafka-cluster"
+        let resource_name = if resource_type == AclResourceType::Cluster {
+            "kafka-cluster".to_string()
+        } else {
+            self.resource_name.clone()
+        };
+        AclBinding {
+            resource_type,
+            resource_name,
+            pattern_type: AclPatternType::ALL[self.pattern_type % AclPatternType::ALL.len()],
+            principal: self.principal.clone(),
+            host: self.host.clone(),
+            operation: AclOperation::ALL[self.operation % AclOperation::ALL.len()],
+            permission: AclPermission::ALL[self.permission % AclPermission::ALL.len()],
+        }
+    }
+
+    /// Cycles the enum field under focus by `delta` (+1 or -1).
+    pub fn cycle(&mut self, delta: isize) {
+        let (value, len) = match self.focused_field {
+            0 => (&mut self.resource_type, AclResourceType::ALL.len()),
+            2 => (&mut self.pattern_type, AclPatternType::ALL.len()),
+            5 => (&mut self.operation, AclOperation::ALL.len()),
+            6 => (&mut self.permission, AclPermission::ALL.len()),
+            _ => return,
+        };
+        *value = (*value as isize + delta).rem_euclid(len as isize) as usize;
+    }
+
+    pub fn text_field(&mut self) -> Option<&mut String> {
+        match self.focused_field {
+            1 => Some(&mut self.resource_name),
+            3 => Some(&mut self.principal),
+            4 => Some(&mut self.host),
+            _ => None,
+        }
+    }
+}
+
+/// Action run when a confirmation dialog is accepted
+#[derive(Debug, Clone)]
+pub enum ConfirmAction {
+    SubmitReassignment,
+    CreateAcl(AclBinding),
+    DeleteAcl(AclBinding),
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfirmDialog {
+    pub title: String,
+    pub message: String,
+    pub action: ConfirmAction,
+}
+
+impl ConfirmDialog {
+    pub fn new(title: &str, message: &str, action: ConfirmAction) -> Self {
+        Self {
+            title: title.to_string(),
+            message: message.to_string(),
+            action,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ElectionResult {
+    pub topic: String,
+    pub partition: i32,
+    pub outcome: Result<String, String>,
+}
+
 /// Sidebar navigation state
 #[derive(Debug, Clone)]
 pub struct SidebarState {
@@ -265,8 +761,13 @@ impl SidebarState {
         Self {
             items: vec![
                 SidebarItem { label: "Dashboard".to_string(), route: Route::Dashboard, indent: 0 },
+                SidebarItem { label: "Brokers".to_string(), route: Route::Brokers, indent: 0 },
                 SidebarItem { label: "Topics".to_string(), route: Route::Topics, indent: 0 },
                 SidebarItem { label: "Consumer Groups".to_string(), route: Route::ConsumerGroups, indent: 0 },
+                SidebarItem { label: "ACLs".to_string(), route: Route::Acls, indent: 0 },
+                SidebarItem { label: "Reassignment".to_string(), route: Route::Reassignment, indent: 0 },
+                SidebarItem { label: "Client Stats".to_string(), route: Route::ClientStats, indent: 0 },
+                SidebarItem { label: "Audit Log".to_string(), route: Route::AuditLog, indent: 0 },
             ],
             selected: 0,
         }
@@ -292,6 +793,7 @@ impl SidebarState {
 /// Dashboard state
 #[derive(Debug, Clone, Default)]
 pub struct DashboardState {
+    pub cluster_id: Option<String>,
     pub controller_id: Option<i32>,
     pub broker_count: usize,
     pub brokers_online: Vec<i32>,
@@ -300,6 +802,71 @@ pub struct DashboardState {
     pub loading: bool,
 }
 
+/// Broker list state
+#[derive(Debug, Clone, Default)]
+pub struct BrokerState {
+    pub brokers: Vec<BrokerInfo>,
+    pub selected: usize,
+    pub loading: bool,
+}
+
+impl BrokerState {
+    pub fn next(&mut self) {
+        if !self.brokers.is_empty() {
+            self.selected = (self.selected + 1) % self.brokers.len();
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if !self.brokers.is_empty() {
+            self.selected = (self.selected + self.brokers.len() - 1) % self.brokers.len();
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BrokerInfo {
+    pub id: i32,
+    pub host: String,
+    pub port: i32,
+    pub is_controller: bool,
+    pub leader_count: usize,
+    pub replica_count: usize,
+}
+
+/// Broker configuration state
+#[derive(Debug, Clone, Default)]
+pub struct BrokerDetailState {
+    pub broker_id: i32,
+    pub config: Vec<ConfigEntryInfo>,
+    pub selected: usize,
+    pub loading: bool,
+}
+
+impl BrokerDetailState {
+    pub fn next(&mut self) {
+        if !self.config.is_empty() {
+            self.selected = (self.selected + 1) % self.config.len();
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if !self.config.is_empty() {
+            self.selected = (self.selected + self.config.len() - 1) % self.config.len();
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigEntryInfo {
+    pub name: String,
+    pub value: Option<String>,
+    pub source: String,
+    pub is_default: bool,
+    pub is_sensitive: bool,
+    pub is_read_only: bool,
+}
+
 /// Topic list state
 #[derive(Debug, Clone, Default)]
 pub struct TopicState {
@@ -350,6 +917,16 @@ pub struct PartitionInfo {
     pub isr: Vec<i32>,
 }
 
+impl PartitionInfo {
+    /// The preferred leader is the first replica in the assignment.
+    pub fn has_preferred_leader(&self) -> bool {
+        match self.replicas.first() {
+            Some(preferred) => *preferred == self.leader,
+            None => true,
+        }
+    }
+}
+
 /// Message browser state
 #[derive(Debug, Clone)]
 pub struct MessageState {
@@ -359,8 +936,20 @@ pub struct MessageState {
     pub consuming: bool,
     pub offset_mode: OffsetMode,
     pub search_query: String,
+    /// Last filter that parsed; kept while the query is being edited
+    pub filter: Option<Filter>,
+    pub filter_error: Option<FilterError>,
     pub show_detail: bool,
     pub auto_scroll: bool,
+    /// Record jumped to with "go to offset", highlighted in the table
+    pub target: Option<(i32, i64)>,
+    /// Browsing page by page instead of tailing
+    pub paged: bool,
+    /// The page shown; `None` until the first one arrives
+    pub page: Option<Page>,
+    pub stats: ConsumeStats,
+    /// Shows the stats panel while consuming
+    pub show_stats: bool,
 }
 
 impl Default for MessageState {
@@ -372,8 +961,15 @@ impl Default for MessageState {
             consuming: false,
             offset_mode: OffsetMode::Latest,
             search_query: String::new(),
+            filter: None,
+            filter_error: None,
             show_detail: false,
             auto_scroll: true,
+            target: None,
+            paged: false,
+            page: None,
+            stats: ConsumeStats::default(),
+            show_stats: true,
         }
     }
 }
@@ -386,18 +982,33 @@ impl MessageState {
         }
     }
 
+    /// Re-parses `search_query`. See `filter` for the syntax.
+    pub fn update_filter(&mut self) {
+        match filter::parse(&self.search_query) {
+            Ok(filter) => {
+                self.filter = filter;
+                self.filter_error = None;
+            }
+            Err(e) => self.filter_error = Some(e),
+        }
+        self.selected = 0;
+    }
+
+    /// The first page for the offset mode: the log start, the last page,
+    /// or the page at an offset or time.
+    pub fn first_page(&self) -> PageAnchor {
+        match self.offset_mode {
+            OffsetMode::Earliest => PageAnchor::From(SearchBound::Edge),
+            OffsetMode::Latest => PageAnchor::Last,
+            OffsetMode::Specific(offset) => PageAnchor::From(SearchBound::Offset(offset)),
+            OffsetMode::Timestamp(ts) => PageAnchor::From(SearchBound::Timestamp(ts)),
+        }
+    }
+
     pub fn filtered_messages(&self) -> Vec<&KafkaMessage> {
-        if self.search_query.is_empty() {
-            self.messages.iter().collect()
-        } else {
-            let query = self.search_query.to_lowercase();
-            self.messages
-                .iter()
-                .filter(|m| {
-                    m.key.as_deref().unwrap_or("").to_lowercase().contains(&query)
-                        || m.value.to_lowercase().contains(&query)
-                })
-                .collect()
+        match &self.filter {
+            None => self.messages.iter().collect(),
+            Some(filter) => self.messages.iter().filter(|m| filter.matches(m)).collect(),
         }
     }
 
@@ -416,52 +1027,382 @@ impl MessageState {
     }
 }
 
-#[derive(Debug, Clone)]
-pub enum OffsetMode {
-    Earliest,
-    Latest,
-    Specific(i64),
-    Timestamp(i64),
-}
-
-#[derive(Debug, Clone)]
-pub struct KafkaMessage {
-    pub partition: i32,
-    pub offset: i64,
-    pub key: Option<String>,
-    pub value: String,
-    pub timestamp: Option<i64>,
-    pub headers: Vec<(String, String)>,
-}
+/// Seconds of rate history kept for the sparkline
+pub const RATE_HISTORY_SECS: usize = 300;
+/// Rates are averaged over this many completed seconds
+const RATE_WINDOW_SECS: usize = 5;
 
-/// Consumer group state
+/// Messages and bytes per second, in one-second buckets
 #[derive(Debug, Clone, Default)]
-pub struct ConsumerGroupState {
-    pub groups: Vec<ConsumerGroupInfo>,
-    pub selected: usize,
-    pub loading: bool,
+pub struct RateHistory {
+    /// Completed seconds, oldest first
+    pub buckets: VecDeque<(u64, u64)>,
+    current: (u64, u64),
 }
 
-impl ConsumerGroupState {
-    pub fn next(&mut self) {
-        if !self.groups.is_empty() {
-            self.selected = (self.selected + 1) % self.groups.len();
-        }
+impl RateHistory {
+    fn record(&mut self, bytes: u64) {
+        self.current.0 += 1;
+        self.current.1 += bytes;
     }
 
-    pub fn previous(&mut self) {
-        if !self.groups.is_empty() {
-            self.selected = (self.selected + self.groups.len() - 1) % self.groups.len();
+    fn close_second(&mut self) {
+        self.buckets.push_back(std::mem::take(&mut self.current));
+        if self.buckets.len() > RATE_HISTORY_SECS {
+            self.buckets.pop_front();
         }
     }
+
+    /// Average messages and bytes per second over the last few seconds
+    pub fn rate(&self) -> (f64, f64) {
+        let recent = self.buckets.iter().rev().take(RATE_WINDOW_SECS);
+        let seconds = recent.len().max(1) as f64;
+        let (messages, bytes) = recent.fold((0, 0), |acc, b| (acc.0 + b.0, acc.1 + b.1));
+        (messages as f64 / seconds, bytes as f64 / seconds)
+    }
 }
 
+/// Where the browser consumer is in a partition, as reported by the backend
 #[derive(Debug, Clone)]
-pub struct ConsumerGroupInfo {
-    pub name: String,
-    pub state: String,
-    pub members: usize,
-    pub topics: Vec<String>,
+pub struct PartitionPosition {
+    pub partition: i32,
+    /// Next offset to read; `None` before the first record
+    pub position: Option<i64>,
+    /// Last known high watermark, from the consumer's fetch responses
+    pub high: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PartitionConsumeStats {
+    pub messages: u64,
+    pub bytes: u64,
+    pub rates: RateHistory,
+    pub position: Option<i64>,
+    pub high: Option<i64>,
+}
+
+impl PartitionConsumeStats {
+    /// Records between the position and the high watermark
+    pub fn behind(&self) -> Option<i64> {
+        Some((self.high? - self.position?).max(0))
+    }
+}
+
+/// Throughput of the live tail, overall and per partition
+#[derive(Debug, Clone, Default)]
+pub struct ConsumeStats {
+    pub rates: RateHistory,
+    pub partitions: BTreeMap<i32, PartitionConsumeStats>,
+    /// Start of the second being counted
+    second_start: Option<Instant>,
+}
+
+impl ConsumeStats {
+    pub fn record(&mut self, message: &KafkaMessage) {
+        self.roll(Instant::now());
+        let bytes = message.total_size() as u64;
+        self.rates.record(bytes);
+        let partition = self.partitions.entry(message.partition).or_default();
+        partition.messages += 1;
+        partition.bytes += bytes;
+        partition.rates.record(bytes);
+    }
+
+    /// Closes every second that has passed, including ones without records.
+    /// Called on every tick so rates fall to zero when the topic goes quiet.
+    pub fn roll(&mut self, now: Instant) {
+        let Some(start) = self.second_start else {
+            self.second_start = Some(now);
+            return;
+        };
+        let elapsed = now.duration_since(start).as_secs() as usize;
+        for _ in 0..elapsed.min(RATE_HISTORY_SECS) {
+            self.rates.close_second();
+            for partition in self.partitions.values_mut() {
+                partition.rates.close_second();
+            }
+        }
+        self.second_start = Some(start + Duration::from_secs(elapsed as u64));
+    }
+
+    pub fn update_positions(&mut self, positions: Vec<PartitionPosition>) {
+        for p in positions {
+            let partition = self.partitions.entry(p.partition).or_default();
+            partition.position = p.position;
+            partition.high = p.high;
+        }
+    }
+
+    /// Records behind the high watermark over all partitions that report it
+    pub fn behind(&self) -> i64 {
+        self.partitions.values().filter_map(|p| p.behind()).sum()
+    }
+}
+
+/// Results of a backend search over a topic range
+#[derive(Debug, Clone, Default)]
+pub struct SearchState {
+    /// Increased for every search; responses for older ones are dropped
+    pub id: u64,
+    pub topic: String,
+    pub query: String,
+    pub range: String,
+    pub scans: Vec<PartitionScan>,
+    pub matches: Vec<KafkaMessage>,
+    pub selected: usize,
+    pub running: bool,
+    pub show_detail: bool,
+    /// How the last search ended
+    pub summary: Option<String>,
+}
+
+impl SearchState {
+    pub fn next(&mut self) {
+        if !self.matches.is_empty() {
+            self.selected = (self.selected + 1) % self.matches.len();
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if !self.matches.is_empty() {
+            self.selected = (self.selected + self.matches.len() - 1) % self.matches.len();
+        }
+    }
+
+    pub fn scanned(&self) -> i64 {
+        self.scans.iter().map(|s| s.scanned()).sum()
+    }
+
+    pub fn total(&self) -> i64 {
+        self.scans.iter().map(|s| s.total()).sum()
+    }
+}
+
+/// Latest record per key of a compacted topic
+#[derive(Debug, Clone, Default)]
+pub struct TopicTableState {
+    /// Increased for every build; responses for older ones are dropped
+    pub id: u64,
+    pub topic: String,
+    pub scans: Vec<PartitionScan>,
+    /// Live keys read so far, while running
+    pub keys: usize,
+    /// Sorted by key, filled in when the read ends
+    pub rows: Vec<KafkaMessage>,
+    pub search_query: String,
+    pub selected: usize,
+    pub running: bool,
+    pub show_detail: bool,
+    pub summary: Option<TableSummary>,
+    /// `cleanup.policy` of the topic, once described
+    pub cleanup_policy: Option<String>,
+}
+
+impl TopicTableState {
+    /// Without compaction the log holds every old value, so the read can be long
+    pub fn is_compacted(&self) -> bool {
+        self.cleanup_policy.as_deref().is_none_or(|p| p.split(',').any(|p| p.trim() == "compact"))
+    }
+
+    /// Matches the search query against keys and values, ignoring case.
+    pub fn filtered_rows(&self) -> Vec<&KafkaMessage> {
+        if self.search_query.is_empty() {
+            self.rows.iter().collect()
+        } else {
+            let query = self.search_query.to_lowercase();
+            self.rows
+                .iter()
+                .filter(|m| {
+                    m.key.as_deref().is_some_and(|k| k.to_lowercase().contains(&query))
+                        || m.value.as_deref().is_some_and(|v| v.to_lowercase().contains(&query))
+                })
+                .collect()
+        }
+    }
+
+    pub fn next(&mut self) {
+        let len = self.filtered_rows().len();
+        if len > 0 {
+            self.selected = (self.selected + 1) % len;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        let len = self.filtered_rows().len();
+        if len > 0 {
+            self.selected = (self.selected + len - 1) % len;
+        }
+    }
+
+    pub fn scanned(&self) -> i64 {
+        self.scans.iter().map(|s| s.scanned()).sum()
+    }
+
+    pub fn total(&self) -> i64 {
+        self.scans.iter().map(|s| s.total()).sum()
+    }
+}
+
+/// Message counts per time bucket of one topic
+#[derive(Debug, Clone, Default)]
+pub struct TimelineState {
+    pub topic: String,
+    pub timeline: Option<Timeline>,
+    pub loading: bool,
+    pub error: Option<String>,
+    /// Selected bucket
+    pub selected: usize,
+    /// Bars for one partition; `None` for the total
+    pub partition: Option<i32>,
+}
+
+impl TimelineState {
+    pub fn next(&mut self) {
+        if let Some(ref timeline) = self.timeline {
+            if self.selected + 1 < timeline.counts.len() {
+                self.selected += 1;
+            }
+        }
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Total, then each partition in turn
+    pub fn cycle_partition(&mut self) {
+        let Some(ref timeline) = self.timeline else { return };
+        self.partition = match self.partition {
+            None => timeline.partitions.first().copied(),
+            Some(p) => timeline.partitions.iter().skip_while(|&&id| id != p).nth(1).copied(),
+        };
+    }
+}
+
+/// Key distribution and partition skew of one topic
+#[derive(Debug, Clone)]
+pub struct AnalysisState {
+    pub topic: String,
+    /// Records sampled per partition
+    pub sample: usize,
+    pub analysis: Option<Analysis>,
+    pub loading: bool,
+    pub error: Option<String>,
+    /// Selected row of the top keys
+    pub selected: usize,
+}
+
+impl Default for AnalysisState {
+    fn default() -> Self {
+        Self {
+            topic: String::new(),
+            sample: analysis::DEFAULT_SAMPLE,
+            analysis: None,
+            loading: false,
+            error: None,
+            selected: 0,
+        }
+    }
+}
+
+impl AnalysisState {
+    pub fn next(&mut self) {
+        let len = self.analysis.as_ref().map_or(0, |a| a.top_keys.len());
+        if self.selected + 1 < len {
+            self.selected += 1;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum OffsetMode {
+    Earliest,
+    Latest,
+    Specific(i64),
+    Timestamp(i64),
+}
+
+/// A consumed record. `None` is a null key, value or header value, which
+/// is not the same as an empty one.
+#[derive(Debug, Clone)]
+pub struct KafkaMessage {
+    pub partition: i32,
+    pub offset: i64,
+    pub key: Option<String>,
+    pub value: Option<String>,
+    pub timestamp: Option<i64>,
+    pub timestamp_type: Option<TimestampType>,
+    pub headers: Vec<(String, Option<String>)>,
+    /// Sizes in bytes as received, before lossy UTF-8 decoding
+    pub key_size: usize,
+    pub value_size: usize,
+    /// Header names and values together
+    pub headers_size: usize,
+    /// Epoch of the partition leader that appended the record, if the broker reports it
+    pub leader_epoch: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimestampType {
+    /// Set by the producer
+    CreateTime,
+    /// Set by the broker, for topics with `message.timestamp.type=LogAppendTime`
+    LogAppendTime,
+}
+
+impl TimestampType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimestampType::CreateTime => "CreateTime",
+            TimestampType::LogAppendTime => "LogAppendTime",
+        }
+    }
+}
+
+impl KafkaMessage {
+    /// A null value; on a compacted topic it deletes the key
+    pub fn is_tombstone(&self) -> bool {
+        self.value.is_none()
+    }
+
+    /// Key, value and headers; the record batch framing is not included
+    pub fn total_size(&self) -> usize {
+        self.key_size + self.value_size + self.headers_size
+    }
+}
+
+/// Consumer group state
+#[derive(Debug, Clone, Default)]
+pub struct ConsumerGroupState {
+    pub groups: Vec<ConsumerGroupInfo>,
+    pub selected: usize,
+    pub loading: bool,
+}
+
+impl ConsumerGroupState {
+    pub fn next(&mut self) {
+        if !self.groups.is_empty() {
+            self.selected = (self.selected + 1) % self.groups.len();
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if !self.groups.is_empty() {
+            self.selected = (self.selected + self.groups.len() - 1) % self.groups.len();
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConsumerGroupInfo {
+    pub name: String,
+    pub state: String,
+    pub members: usize,
+    pub topics: Vec<String>,
     pub lag: Vec<PartitionLag>,
     pub total_lag: i64,
 }
@@ -482,6 +1423,396 @@ pub struct TopicDetailState {
     pub partitions: Vec<PartitionInfo>,
     pub config: Vec<(String, String)>,
     pub selected_partition: usize,
+    pub marked_partitions: Vec<i32>,
+}
+
+impl TopicDetailState {
+    pub fn toggle_mark(&mut self) {
+        if let Some(p) = self.partitions.get(self.selected_partition) {
+            let id = p.id;
+            if let Some(pos) = self.marked_partitions.iter().position(|m| *m == id) {
+                self.marked_partitions.remove(pos);
+            } else {
+                self.marked_partitions.push(id);
+                self.marked_partitions.sort();
+            }
+        }
+    }
+
+    pub fn unpreferred_partitions(&self) -> Vec<i32> {
+        self.partitions.iter().filter(|p| !p.has_preferred_leader()).map(|p| p.id).collect()
+    }
+}
+
+/// Partition reassignment planner state
+#[derive(Debug, Clone, Default)]
+pub struct ReassignmentState {
+    pub plan: Option<ReassignmentPlan>,
+    pub selected: usize,
+    pub loading: bool,
+    pub submitted: bool,
+    pub progress: Vec<MoveProgress>,
+    pub exported_to: Option<String>,
+}
+
+impl ReassignmentState {
+    pub fn move_count(&self) -> usize {
+        self.plan.as_ref().map(|p| p.moves.len()).unwrap_or(0)
+    }
+
+    pub fn status_of(&self, topic: &str, partition: i32) -> Option<MoveStatus> {
+        self.progress
+            .iter()
+            .find(|p| p.topic == topic && p.partition == partition)
+            .map(|p| p.status)
+    }
+
+    pub fn next(&mut self) {
+        let len = self.move_count();
+        if len > 0 {
+            self.selected = (self.selected + 1) % len;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        let len = self.move_count();
+        if len > 0 {
+            self.selected = (self.selected + len - 1) % len;
+        }
+    }
+}
+
+/// Health of the active cluster connection, as reported by the backend
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ConnectionStatus {
+    #[default]
+    Connected,
+    /// Clients still work but librdkafka recently reported errors
+    Degraded(String),
+    /// Brokers are unreachable; the backend retries with backoff
+    Reconnecting { attempt: u32, retry_in_secs: u64 },
+}
+
+/// Client Stats view state: the latest librdkafka statistics per client
+#[derive(Debug, Clone, Default)]
+pub struct ClientStatsState {
+    pub clients: Vec<ClientStats>,
+    pub selected_client: usize,
+}
+
+impl ClientStatsState {
+    pub fn update(&mut self, stats: ClientStats) {
+        match self.clients.iter_mut().find(|c| c.role == stats.role) {
+            Some(existing) => *existing = stats,
+            None => {
+                self.clients.push(stats);
+                self.clients.sort_by_key(|c| c.role);
+            }
+        }
+    }
+
+    pub fn current(&self) -> Option<&ClientStats> {
+        self.clients.get(self.selected_client)
+    }
+
+    pub fn next_client(&mut self) {
+        if !self.clients.is_empty() {
+            self.selected_client = (self.selected_client + 1) % self.clients.len();
+        }
+    }
+
+    pub fn previous_client(&mut self) {
+        if !self.clients.is_empty() {
+            self.selected_client = (self.selected_client + self.clients.len() - 1) % self.clients.len();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ClientRole {
+    Admin,
+    Consumer,
+    Producer,
+}
+
+impl ClientRole {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ClientRole::Admin => "Admin",
+            ClientRole::Consumer => "Consumer",
+            ClientRole::Producer => "Producer",
+        }
+    }
+}
+
+/// One statistics report from a librdkafka client
+#[derive(Debug, Clone)]
+pub struct ClientStats {
+    pub role: ClientRole,
+    pub received_at: chrono::DateTime<chrono::Local>,
+    /// Messages and bytes waiting in the producer queue
+    pub queued_msgs: u64,
+    pub queued_bytes: u64,
+    pub tx_bytes: i64,
+    pub rx_bytes: i64,
+    pub brokers: Vec<BrokerStats>,
+    pub partitions: Vec<PartitionStats>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BrokerStats {
+    pub node_id: i32,
+    pub name: String,
+    pub state: String,
+    /// Round-trip times in microseconds
+    pub rtt_avg: Option<i64>,
+    pub rtt_p99: Option<i64>,
+    /// Requests waiting to be sent / waiting for a response
+    pub outbuf_cnt: i64,
+    pub waitresp_cnt: i64,
+    pub tx_bytes: u64,
+    pub rx_bytes: u64,
+    pub req_timeouts: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct PartitionStats {
+    pub topic: String,
+    pub partition: i32,
+    pub leader: i32,
+    pub fetch_state: String,
+    pub fetchq_cnt: i64,
+    pub next_offset: i64,
+    pub consumer_lag: i64,
+    pub msgq_cnt: i64,
+    pub xmit_msgq_cnt: i64,
+}
+
+/// ACL browser state
+#[derive(Debug, Clone, Default)]
+pub struct AclState {
+    pub bindings: Vec<AclBinding>,
+    pub selected: usize,
+    pub loading: bool,
+    pub search_query: String,
+}
+
+impl AclState {
+    /// Matches the search query against principal and resource name.
+    pub fn filtered_bindings(&self) -> Vec<&AclBinding> {
+        if self.search_query.is_empty() {
+            self.bindings.iter().collect()
+        } else {
+            let query = self.search_query.to_lowercase();
+            self.bindings
+                .iter()
+                .filter(|b| {
+                    b.principal.to_lowercase().contains(&query)
+                        || b.resource_name.to_lowercase().contains(&query)
+                })
+                .collect()
+        }
+    }
+
+    pub fn next(&mut self) {
+        let len = self.filtered_bindings().len();
+        if len > 0 {
+            self.selected = (self.selected + 1) % len;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        let len = self.filtered_bindings().len();
+        if len > 0 {
+            self.selected = (self.selected + len - 1) % len;
+        }
+    }
+}
+
+/// Audit log viewer state, newest entry first
+#[derive(Debug, Clone, Default)]
+pub struct AuditState {
+    pub entries: Vec<AuditEntry>,
+    pub selected: usize,
+    pub search_query: String,
+    pub show_detail: bool,
+    pub error: Option<String>,
+}
+
+impl AuditState {
+    /// Matches the search query against cluster, user, operation and arguments.
+    pub fn filtered_entries(&self) -> Vec<&AuditEntry> {
+        if self.search_query.is_empty() {
+            self.entries.iter().collect()
+        } else {
+            let query = self.search_query.to_lowercase();
+            self.entries
+                .iter()
+                .filter(|e| {
+                    e.cluster.to_lowercase().contains(&query)
+                        || e.user.to_lowercase().contains(&query)
+                        || e.operation.to_lowercase().contains(&query)
+                        || e.result.to_lowercase().contains(&query)
+                        || e.arguments.to_string().to_lowercase().contains(&query)
+                })
+                .collect()
+        }
+    }
+
+    pub fn next(&mut self) {
+        let len = self.filtered_entries().len();
+        if len > 0 {
+            self.selected = (self.selected + 1) % len;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        let len = self.filtered_entries().len();
+        if len > 0 {
+            self.selected = (self.selected + len - 1) % len;
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AclBinding {
+    pub resource_type: AclResourceType,
+    pub resource_name: String,
+    pub pattern_type: AclPatternType,
+    pub principal: String,
+    pub host: String,
+    pub operation: AclOperation,
+    pub permission: AclPermission,
+}
+
+impl AclBinding {
+    pub fn describe(&self) -> String {
+        format!(
+            "{} {} {} on {} {} '{}' from {}",
+            self.permission.label(),
+            self.principal,
+            self.operation.label(),
+            self.pattern_type.label(),
+            self.resource_type.label(),
+            self.resource_name,
+            self.host
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AclResourceType {
+    Topic,
+    Group,
+    Cluster,
+    TransactionalId,
+    Unknown,
+}
+
+impl AclResourceType {
+    pub const ALL: [AclResourceType; 4] = [
+        AclResourceType::Topic,
+        AclResourceType::Group,
+        AclResourceType::Cluster,
+        AclResourceType::TransactionalId,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AclResourceType::Topic => "Topic",
+            AclResourceType::Group => "Group",
+            AclResourceType::Cluster => "Cluster",
+            AclResourceType::TransactionalId => "TransactionalId",
+            AclResourceType::Unknown => "Unknown",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AclPatternType {
+    Literal,
+    Prefixed,
+    Unknown,
+}
+
+impl AclPatternType {
+    pub const ALL: [AclPatternType; 2] = [AclPatternType::Literal, AclPatternType::Prefixed];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AclPatternType::Literal => "Literal",
+            AclPatternType::Prefixed => "Prefixed",
+            AclPatternType::Unknown => "Unknown",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AclOperation {
+    All,
+    Read,
+    Write,
+    Create,
+    Delete,
+    Alter,
+    Describe,
+    ClusterAction,
+    DescribeConfigs,
+    AlterConfigs,
+    IdempotentWrite,
+    Unknown,
+}
+
+impl AclOperation {
+    pub const ALL: [AclOperation; 11] = [
+        AclOperation::All,
+        AclOperation::Read,
+        AclOperation::Write,
+        AclOperation::Create,
+        AclOperation::Delete,
+        AclOperation::Alter,
+        AclOperation::Describe,
+        AclOperation::ClusterAction,
+        AclOperation::DescribeConfigs,
+        AclOperation::AlterConfigs,
+        AclOperation::IdempotentWrite,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AclOperation::All => "All",
+            AclOperation::Read => "Read",
+            AclOperation::Write => "Write",
+            AclOperation::Create => "Create",
+            AclOperation::Delete => "Delete",
+            AclOperation::Alter => "Alter",
+            AclOperation::Describe => "Describe",
+            AclOperation::ClusterAction => "ClusterAction",
+            AclOperation::DescribeConfigs => "DescribeConfigs",
+            AclOperation::AlterConfigs => "AlterConfigs",
+            AclOperation::IdempotentWrite => "IdempotentWrite",
+            AclOperation::Unknown => "Unknown",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AclPermission {
+    Allow,
+    Deny,
+    Unknown,
+}
+
+impl AclPermission {
+    pub const ALL: [AclPermission; 2] = [AclPermission::Allow, AclPermission::Deny];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AclPermission::Allow => "Allow",
+            AclPermission::Deny => "Deny",
+            AclPermission::Unknown => "Unknown",
+        }
+    }
 }
 
 /// Log entry for status bar
@@ -506,10 +1837,20 @@ pub struct App {
     pub active_cluster: Option<usize>,
     pub sidebar: SidebarState,
     pub dashboard: DashboardState,
+    pub brokers: BrokerState,
+    pub broker_detail: BrokerDetailState,
     pub topics: TopicState,
     pub topic_detail: TopicDetailState,
     pub messages: MessageState,
+    pub search: SearchState,
+    pub topic_table: TopicTableState,
+    pub timeline: TimelineState,
+    pub analysis: AnalysisState,
     pub consumer_groups: ConsumerGroupState,
+    pub reassignment: ReassignmentState,
+    pub acls: AclState,
+    pub client_stats: ClientStatsState,
+    pub audit: AuditState,
     pub dialog: Option<Dialog>,
     pub show_help: bool,
     pub focus: Focus,
@@ -517,6 +1858,13 @@ pub struct App {
     pub status_message: String,
     pub logs: Vec<LogEntry>,
     pub cluster_select_index: usize,
+    pub connection: ConnectionStatus,
+    /// When the current view last asked the backend for fresh data
+    pub last_refresh: Option<Instant>,
+    /// When data for the current view last arrived
+    pub last_updated: Option<Instant>,
+    /// Set while an automatic refresh is in flight, to keep it out of the log
+    pub auto_refreshing: bool,
 }
 
 impl App {
@@ -527,10 +1875,20 @@ impl App {
             active_cluster: None,
             sidebar: SidebarState::new(),
             dashboard: DashboardState::default(),
+            brokers: BrokerState::default(),
+            broker_detail: BrokerDetailState::default(),
             topics: TopicState::default(),
             topic_detail: TopicDetailState::default(),
             messages: MessageState::default(),
+            search: SearchState::default(),
+            topic_table: TopicTableState::default(),
+            timeline: TimelineState::default(),
+            analysis: AnalysisState::default(),
             consumer_groups: ConsumerGroupState::default(),
+            reassignment: ReassignmentState::default(),
+            acls: AclState::default(),
+            client_stats: ClientStatsState::default(),
+            audit: AuditState::default(),
             dialog: None,
             show_help: false,
             focus: Focus::Content,
@@ -538,6 +1896,10 @@ impl App {
             status_message: "Welcome to Kafka Eye".to_string(),
             logs: Vec::new(),
             cluster_select_index: 0,
+            connection: ConnectionStatus::default(),
+            last_refresh: None,
+            last_updated: None,
+            auto_refreshing: false,
         }
     }
 
@@ -570,5 +1932,38 @@ impl App {
     pub fn navigate(&mut self, route: Route) {
         self.route = route;
         self.focus = Focus::Content;
+        // Navigating fetches the new view, so start its refresh timer now
+        self.last_refresh = Some(Instant::now());
+        self.last_updated = None;
+    }
+
+    /// Auto-refresh interval for the current view, if it has one.
+    pub fn refresh_interval(&self) -> Option<Duration> {
+        let refresh = &self.config.refresh;
+        let secs = match self.route {
+            Route::Dashboard => refresh.dashboard,
+            Route::Brokers => refresh.brokers,
+            Route::Topics => refresh.topics,
+            Route::TopicDetail(_) => refresh.topic_detail,
+            Route::ConsumerGroups | Route::ConsumerGroupDetail(_) => refresh.consumer_groups,
+            Route::Acls => refresh.acls,
+            _ => 0,
+        };
+        if secs == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(secs))
+        }
+    }
+
+    /// Records that fresh data arrived. Automatic refreshes are not logged
+    /// so they don't bury real messages in the status bar.
+    pub fn data_loaded(&mut self, message: &str) {
+        self.last_updated = Some(Instant::now());
+        if self.auto_refreshing {
+            self.auto_refreshing = false;
+        } else {
+            self.log_info(message);
+        }
     }
 }
@@ -0,0 +1,157 @@
+//! Audit trail of commands that change a cluster. Each one is appended to
+//! `audit.jsonl` next to config.toml as a single JSON line, whether it
+//! succeeded, failed or was rejected, including changes refused in the UI
+//! before they reach the backend.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::config::AppConfig;
+use crate::kafka::client::KafkaCommand;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AuditEntry {
+    /// RFC 3339, local time with offset
+    pub timestamp: String,
+    pub user: String,
+    pub cluster: String,
+    pub operation: String,
+    pub arguments: Value,
+    /// `ok`, `failed`, `partial` (some partitions of a leader election
+    /// failed) or `rejected`
+    pub result: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub message: String,
+}
+
+impl AuditEntry {
+    /// Starts an entry for a mutating command; `None` for read-only ones.
+    pub fn begin(cluster: &str, command: &KafkaCommand) -> Option<Self> {
+        let (operation, arguments) = describe(command)?;
+        Some(Self {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            user: os_user(),
+            cluster: cluster.to_string(),
+            operation: operation.to_string(),
+            arguments,
+            result: String::new(),
+            message: String::new(),
+        })
+    }
+
+    /// Fills in the outcome and appends the entry to the audit file.
+    /// Failing to write is reported but never blocks the operation.
+    pub fn finish(mut self, result: &str, message: impl Into<String>) -> Result<()> {
+        self.result = result.to_string();
+        self.message = message.into();
+        append(&self)
+    }
+}
+
+pub fn audit_path() -> PathBuf {
+    AppConfig::config_path().with_file_name("audit.jsonl")
+}
+
+fn append(entry: &AuditEntry) -> Result<()> {
+    let path = audit_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("failed to create directory {}", parent.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    let line = serde_json::to_string(entry).context("failed to serialize audit entry")?;
+    writeln!(file, "{}", line).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Reads the audit file, newest entry first. Lines that don't parse are skipped.
+pub fn read_entries() -> Result<Vec<AuditEntry>> {
+    let path = audit_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    let mut entries: Vec<AuditEntry> = contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    entries.reverse();
+    Ok(entries)
+}
+
+fn os_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Operation name and arguments of a mutating command. Message values are
+/// not recorded, only their size, which is null for a tombstone.
+fn describe(command: &KafkaCommand) -> Option<(&'static str, Value)> {
+    let described = match command {
+        KafkaCommand::CreateTopic {
+            name,
+            partitions,
+            replication_factor,
+            config,
+        } => (
+            "create_topic",
+            json!({
+                "topic": name,
+                "partitions": partitions,
+                "replication_factor": replication_factor,
+                "config": config,
+            }),
+        ),
+        KafkaCommand::DeleteTopic(name) => ("delete_topic", json!({ "topic": name })),
+        KafkaCommand::ElectPreferredLeaders { topic, partitions } => (
+            "elect_preferred_leaders",
+            json!({ "topic": topic, "partitions": partitions }),
+        ),
+        KafkaCommand::SubmitReassignment(plan) => (
+            "submit_reassignment",
+            json!({
+                "goal": plan.goal.describe(),
+                "moves": plan.moves.iter().map(|m| json!({
+                    "topic": m.topic,
+                    "partition": m.partition,
+                    "from": m.current,
+                    "to": m.target,
+                })).collect::<Vec<_>>(),
+            }),
+        ),
+        KafkaCommand::CreateAcl(binding) | KafkaCommand::DeleteAcl(binding) => {
+            let operation = if matches!(command, KafkaCommand::CreateAcl(_)) { "create_acl" } else { "delete_acl" };
+            (
+                operation,
+                json!({
+                    "resource_type": binding.resource_type.label(),
+                    "resource_name": binding.resource_name,
+                    "pattern_type": binding.pattern_type.label(),
+                    "principal": binding.principal,
+                    "host": binding.host,
+                    "operation": binding.operation.label(),
+                    "permission": binding.permission.label(),
+                }),
+            )
+        }
+        KafkaCommand::ProduceMessage { topic, key, value, headers } => (
+            "produce",
+            json!({
+                "topic": topic,
+                "key": key,
+                "value_bytes": value.as_ref().map(|v| v.len()),
+                "headers": headers.iter().map(|(k, _)| k).collect::<Vec<_>>(),
+            }),
+        ),
+        _ => return None,
+    };
+    Some(described)
+}
@@ -1,12 +1,81 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::crypto::{self, MasterKey};
+use crate::secrets;
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct AppConfig {
+    /// Schema version, see `CONFIG_VERSION`. Files without one are version 0.
+    #[serde(default)]
+    pub version: u32,
     pub clusters: Vec<ClusterConfig>,
+    #[serde(default)]
+    pub refresh: RefreshConfig,
+    #[serde(default)]
+    pub display: DisplayConfig,
+    /// Set when config.toml is encrypted; `save` encrypts with it again
+    #[serde(skip)]
+    pub master_key: Option<MasterKey>,
+}
+
+/// Auto-refresh interval per view, in seconds. 0 turns refreshing off.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct RefreshConfig {
+    pub dashboard: u64,
+    pub brokers: u64,
+    pub topics: u64,
+    pub topic_detail: u64,
+    pub consumer_groups: u64,
+    pub acls: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// Zone record timestamps are shown in
+    pub timezone: TimeZone,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeZone {
+    #[default]
+    Local,
+    Utc,
+}
+
+impl TimeZone {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeZone::Local => "local",
+            TimeZone::Utc => "UTC",
+        }
+    }
+
+    pub fn toggle(&self) -> Self {
+        match self {
+            TimeZone::Local => TimeZone::Utc,
+            TimeZone::Utc => TimeZone::Local,
+        }
+    }
+
+    /// Formats milliseconds since the epoch with a chrono format string
+    pub fn format(&self, millis: i64, format: &str) -> String {
+        let Some(time) = chrono::DateTime::from_timestamp_millis(millis) else {
+            return millis.to_string();
+        };
+        match self {
+            TimeZone::Local => time.with_timezone(&chrono::Local).format(format).to_string(),
+            TimeZone::Utc => time.format(format).to_string(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -14,9 +83,100 @@ pub struct ClusterConfig {
     pub name: String,
     pub brokers: String,
     pub auth: AuthConfig,
+    /// Extra librdkafka properties. Plain keys apply to every client; keys
+    /// prefixed with `admin.`, `consumer.` or `producer.` apply to that
+    /// client only and win over the plain ones.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Environment>,
+    /// Rejects every command that changes the cluster, in the backend
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub read_only: bool,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Environment {
+    Dev,
+    Staging,
+    /// Shown with a red banner; every change needs the cluster name typed
+    Prod,
+}
+
+impl Environment {
+    pub const ALL: [Environment; 3] = [Environment::Dev, Environment::Staging, Environment::Prod];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Environment::Dev => "dev",
+            Environment::Staging => "staging",
+            Environment::Prod => "prod",
+        }
+    }
+}
+
+/// Parses `key=value` pairs separated by `;` or newlines, as typed in the
+/// cluster dialog. A value can be double-quoted to keep `;` in it, and `\`
+/// escapes the next character anywhere. Fragments that aren't `key=value`
+/// are reported rather than dropped.
+pub fn parse_properties(text: &str) -> std::result::Result<BTreeMap<String, String>, String> {
+    let mut entries = Vec::new();
+    let mut current = String::new();
+    // Quotes and escapes are resolved in `current`; `raw` keeps the fragment for errors
+    let mut raw = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let Some(next) = chars.next() else { return Err(format!("'{}\\' ends with a lone \\", raw.trim())) };
+                raw.push(c);
+                raw.push(next);
+                current.push(next);
+            }
+            '"' => {
+                quoted = !quoted;
+                raw.push(c);
+            }
+            ';' | '\n' if !quoted => entries.push((std::mem::take(&mut current), std::mem::take(&mut raw))),
+            _ => {
+                raw.push(c);
+                current.push(c);
+            }
+        }
+    }
+    if quoted {
+        return Err(format!("'{}' has an unclosed quote", raw.trim()));
+    }
+    entries.push((current, raw));
+
+    let mut properties = BTreeMap::new();
+    for (entry, raw) in entries {
+        if raw.trim().is_empty() {
+            continue;
+        }
+        match entry.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                properties.insert(key.trim().to_string(), value.trim().to_string());
+            }
+            _ => return Err(format!("'{}' is not key=value", raw.trim())),
+        }
+    }
+    Ok(properties)
+}
+
+/// The inverse of `parse_properties`
+pub fn format_properties(properties: &BTreeMap<String, String>) -> String {
+    let escape = |text: &str| text.replace('\\', "\\\\").replace(';', "\\;").replace('"', "\\\"");
+    properties
+        .iter()
+        .map(|(k, v)| format!("{}={}", escape(k), escape(v)))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(tag = "type")]
 pub enum AuthConfig {
     None,
@@ -52,12 +212,47 @@ pub enum AuthConfig {
         password: String,
         ca_cert: Option<String>,
     },
+    /// OAUTHBEARER with tokens from an OAuth 2.0 client-credentials grant
+    SaslOAuthBearer {
+        token_endpoint: String,
+        client_id: String,
+        client_secret: String,
+        scope: Option<String>,
+    },
+    SaslSslOAuthBearer {
+        token_endpoint: String,
+        client_id: String,
+        client_secret: String,
+        scope: Option<String>,
+        ca_cert: Option<String>,
+    },
+    /// OAUTHBEARER with an unsigned JWT for `principal`; local development only
+    SaslOAuthBearerUnsecured {
+        principal: String,
+    },
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
+            version: CONFIG_VERSION,
             clusters: Vec::new(),
+            refresh: RefreshConfig::default(),
+            display: DisplayConfig::default(),
+            master_key: None,
+        }
+    }
+}
+
+impl Default for RefreshConfig {
+    fn default() -> Self {
+        Self {
+            dashboard: 10,
+            brokers: 30,
+            topics: 30,
+            topic_detail: 10,
+            consumer_groups: 5,
+            acls: 0,
         }
     }
 }
@@ -68,6 +263,115 @@ impl Default for AuthConfig {
     }
 }
 
+impl AuthConfig {
+    /// The password or client secret, which may be a secret reference.
+    pub fn secret_mut(&mut self) -> Option<&mut String> {
+        match self {
+            AuthConfig::SaslPlain { password, .. }
+            | AuthConfig::SaslScram256 { password, .. }
+            | AuthConfig::SaslScram512 { password, .. }
+            | AuthConfig::SaslSslPlain { password, .. }
+            | AuthConfig::SaslSslScram256 { password, .. }
+            | AuthConfig::SaslSslScram512 { password, .. } => Some(password),
+            AuthConfig::SaslOAuthBearer { client_secret, .. }
+            | AuthConfig::SaslSslOAuthBearer { client_secret, .. } => Some(client_secret),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AuthConfig::None => "None",
+            AuthConfig::SaslPlain { .. } => "SASL/PLAIN",
+            AuthConfig::SaslScram256 { .. } => "SCRAM-SHA-256",
+            AuthConfig::SaslScram512 { .. } => "SCRAM-SHA-512",
+            AuthConfig::Ssl { .. } => "SSL/TLS",
+            AuthConfig::SaslSslPlain { .. } => "SASL_SSL/PLAIN",
+            AuthConfig::SaslSslScram256 { .. } => "SASL_SSL/SCRAM-256",
+            AuthConfig::SaslSslScram512 { .. } => "SASL_SSL/SCRAM-512",
+            AuthConfig::SaslOAuthBearer { .. } => "SASL/OAUTHBEARER",
+            AuthConfig::SaslSslOAuthBearer { .. } => "SASL_SSL/OAUTHBEARER",
+            AuthConfig::SaslOAuthBearerUnsecured { .. } => "OAUTHBEARER (unsecured JWT)",
+        }
+    }
+
+    /// The CA, client certificate and key paths that are set
+    fn cert_paths_mut(&mut self) -> Vec<&mut String> {
+        match self {
+            AuthConfig::Ssl { ca_cert, client_cert, client_key } => {
+                [ca_cert, client_cert, client_key].into_iter().flatten().collect()
+            }
+            AuthConfig::SaslSslPlain { ca_cert, .. }
+            | AuthConfig::SaslSslScram256 { ca_cert, .. }
+            | AuthConfig::SaslSslScram512 { ca_cert, .. }
+            | AuthConfig::SaslSslOAuthBearer { ca_cert, .. } => ca_cert.iter_mut().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn needs_prompt(&self) -> bool {
+        self.clone().secret_mut().is_some_and(|secret| secret == secrets::PROMPT)
+    }
+}
+
+impl ClusterConfig {
+    pub fn is_prod(&self) -> bool {
+        self.environment == Some(Environment::Prod)
+    }
+
+    /// What each certificate or key file of the auth settings and the
+    /// `*.location` properties is, the key it is set with, and its path.
+    fn file_paths(&self) -> Vec<(&str, &str, &str)> {
+        let mut paths = Vec::new();
+        match &self.auth {
+            AuthConfig::Ssl { ca_cert, client_cert, client_key } => {
+                paths.extend(ca_cert.as_deref().map(|p| ("CA cert", "ca_cert", p)));
+                paths.extend(client_cert.as_deref().map(|p| ("client cert", "client_cert", p)));
+                paths.extend(client_key.as_deref().map(|p| ("client key", "client_key", p)));
+            }
+            AuthConfig::SaslSslPlain { ca_cert, .. }
+            | AuthConfig::SaslSslScram256 { ca_cert, .. }
+            | AuthConfig::SaslSslScram512 { ca_cert, .. }
+            | AuthConfig::SaslSslOAuthBearer { ca_cert, .. } => {
+                paths.extend(ca_cert.as_deref().map(|p| ("CA cert", "ca_cert", p)));
+            }
+            _ => {}
+        }
+        for (key, value) in &self.properties {
+            if key.ends_with(".location") && !secrets::is_reference(value) {
+                paths.push((key.as_str(), key.as_str(), value.as_str()));
+            }
+        }
+        paths
+    }
+
+    /// Returns a copy with every secret reference in the auth settings and
+    /// properties replaced by its value. A `password` typed at the prompt
+    /// takes the place of the auth secret and is not resolved. The copy is
+    /// for connecting only and must never be saved.
+    pub fn resolve_secrets(&self, password: Option<&str>) -> Result<ClusterConfig> {
+        let mut resolved = self.clone();
+        if let Some(secret) = resolved.auth.secret_mut() {
+            *secret = match password {
+                Some(password) => password.to_string(),
+                None => secrets::resolve(secret).context("failed to resolve password")?,
+            };
+        }
+        // librdkafka opens these as they are, without expanding `~/`
+        for path in resolved.auth.cert_paths_mut() {
+            *path = secrets::expand_home(path)?.to_string_lossy().into_owned();
+        }
+        for (key, value) in resolved.properties.iter_mut() {
+            if secrets::is_reference(value) {
+                *value = secrets::resolve(value).with_context(|| format!("failed to resolve {}", key))?;
+            } else if key.ends_with(".location") {
+                *value = secrets::expand_home(value)?.to_string_lossy().into_owned();
+            }
+        }
+        Ok(resolved)
+    }
+}
+
 impl AppConfig {
     pub fn config_path() -> PathBuf {
         dirs::config_dir()
@@ -76,28 +380,413 @@ impl AppConfig {
             .join("config.toml")
     }
 
+    /// Loads, migrates and validates the config. Problems with the file
+    /// itself come back as a `ConfigError` so they can be shown in the TUI.
     pub fn load() -> Result<Self> {
         let path = Self::config_path();
         if !path.exists() {
             return Ok(Self::default());
         }
-        let contents =
-            fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
-        let config: Self =
-            toml::from_str(&contents).with_context(|| format!("failed to parse {}", path.display()))?;
+        let (mut contents, master_key) = read_plaintext(&path)?;
+        let mut config: Self = toml::from_str(&contents).map_err(|e| {
+            let line = e.span().map(|span| line_of(&contents, span.start));
+            ConfigError::new(&path, &contents, master_key.is_some(), vec![ConfigIssue {
+                line,
+                message: e.message().trim().replace('\n', ": "),
+            }])
+        })?;
+        config.master_key = master_key;
+
+        if config.version > CONFIG_VERSION {
+            let issue = ConfigIssue {
+                line: contents.lines().position(|l| l.trim_start().starts_with("version")).map(|i| i + 1),
+                message: format!(
+                    "config version {} was written by a newer kafka-eye (this one reads up to {})",
+                    config.version, CONFIG_VERSION
+                ),
+            };
+            return Err(ConfigError::new(&path, &contents, config.master_key.is_some(), vec![issue]).into());
+        }
+        let read_version = config.version;
+        let rewrite = config.migrate();
+        if rewrite {
+            // Issues below must point at lines of the file as it will be written
+            contents = toml::to_string_pretty(&config).context("failed to serialize config")?;
+        }
+
+        let issues = config.validate(&contents);
+        if !issues.is_empty() {
+            let encrypted = config.master_key.is_some();
+            let mut error = ConfigError::new(&path, &contents, encrypted, issues);
+            error.config = Some(Box::new(config));
+            return Err(error.into());
+        }
+        if rewrite {
+            // Keep the old file around in case the migration goes wrong
+            let backup = path.with_extension(format!("toml.v{}.bak", read_version));
+            fs::copy(&path, &backup).with_context(|| format!("failed to back up {}", path.display()))?;
+            config.save()?;
+        }
         Ok(config)
     }
 
+    /// Upgrades a config read from an older layout to `CONFIG_VERSION`.
+    /// Returns whether the file has to be rewritten; a new version number
+    /// alone waits for the next save, so comments and layout survive.
+    fn migrate(&mut self) -> bool {
+        // 0 -> 1: unversioned files. `refresh` and `properties` were added
+        // with serde defaults, so they already read fine; only the version
+        // is stamped.
+        if self.version < 1 {
+            self.version = 1;
+        }
+        false
+    }
+
+    /// Checks what serde can't: broker list syntax, that certificate files
+    /// exist and that cluster names are unique.
+    fn validate(&self, contents: &str) -> Vec<ConfigIssue> {
+        // Issues point at the line of the offending key, or at the
+        // `[[clusters]]` header of the cluster when the key isn't found
+        let lines: Vec<&str> = contents.lines().collect();
+        let headers: Vec<usize> = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| l.trim() == "[[clusters]]")
+            .map(|(i, _)| i)
+            .collect();
+        let mut issues = Vec::new();
+        for (i, cluster) in self.clusters.iter().enumerate() {
+            let header = headers.get(i).copied();
+            let table = header.map_or(0..0, |start| start..headers.get(i + 1).copied().unwrap_or(lines.len()));
+            let mut issue = |key: &str, message: String| {
+                let line = lines[table.clone()]
+                    .iter()
+                    .position(|l| l.split_once('=').is_some_and(|(k, _)| k.trim().trim_matches('"') == key))
+                    .map(|offset| table.start + offset)
+                    .or(header);
+                issues.push(ConfigIssue {
+                    line: line.map(|l| l + 1),
+                    message: format!("cluster '{}': {}", cluster.name, message),
+                })
+            };
+            if cluster.name.trim().is_empty() {
+                issue("name", "name is empty".to_string());
+            }
+            if self.clusters[..i].iter().any(|c| c.name == cluster.name) {
+                issue("name", "another cluster has the same name".to_string());
+            }
+            if let Err(e) = check_brokers(&cluster.brokers) {
+                issue("brokers", e);
+            }
+            for (what, key, path) in cluster.file_paths() {
+                if !secrets::expand_home(path).is_ok_and(|p| p.exists()) {
+                    issue(key, format!("{} {} does not exist", what, path));
+                }
+            }
+            if let AuthConfig::SaslOAuthBearer { token_endpoint, .. } | AuthConfig::SaslSslOAuthBearer { token_endpoint, .. } =
+                &cluster.auth
+            {
+                if !token_endpoint.starts_with("http://") && !token_endpoint.starts_with("https://") {
+                    issue("token_endpoint", format!("token endpoint '{}' is not an http(s) URL", token_endpoint));
+                }
+            }
+        }
+        issues
+    }
+
     pub fn save(&self) -> Result<()> {
         let path = Self::config_path();
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
                 .with_context(|| format!("failed to create directory {}", parent.display()))?;
         }
-        let contents =
+        let mut contents =
             toml::to_string_pretty(self).context("failed to serialize config")?;
-        fs::write(&path, contents)
-            .with_context(|| format!("failed to write {}", path.display()))?;
+        if let Some(ref key) = self.master_key {
+            contents = key.encrypt(&contents)?;
+        }
+        write_private(&path, &contents)
+    }
+}
+
+/// Current config schema version, written on save.
+pub const CONFIG_VERSION: u32 = 1;
+
+#[derive(Debug, Clone)]
+pub struct ConfigIssue {
+    /// 1-based line in the (decrypted) file, when known
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// config.toml could not be parsed or failed validation.
+#[derive(Debug)]
+pub struct ConfigError {
+    pub path: PathBuf,
+    pub issues: Vec<ConfigIssue>,
+    /// Lines of the file, to show the ones the issues point at
+    pub lines: Vec<String>,
+    pub encrypted: bool,
+    /// The parsed config when only validation failed; it can still be used
+    pub config: Option<Box<AppConfig>>,
+}
+
+impl ConfigError {
+    fn new(path: &Path, contents: &str, encrypted: bool, issues: Vec<ConfigIssue>) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            issues,
+            lines: contents.lines().map(str::to_string).collect(),
+            encrypted,
+            config: None,
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "problems in {}", self.path.display())?;
+        for issue in &self.issues {
+            match issue.line {
+                Some(line) => write!(f, "\n  line {}: {}", line, issue.message)?,
+                None => write!(f, "\n  {}", issue.message)?,
+            }
+        }
         Ok(())
     }
 }
+
+impl std::error::Error for ConfigError {}
+
+fn line_of(contents: &str, offset: usize) -> usize {
+    contents[..offset.min(contents.len())].matches('\n').count() + 1
+}
+
+/// Checks a comma separated `host:port` list, optionally with a `proto://` prefix.
+fn check_brokers(brokers: &str) -> std::result::Result<(), String> {
+    if brokers.trim().is_empty() {
+        return Err("broker list is empty".to_string());
+    }
+    for broker in brokers.split(',').map(str::trim) {
+        let address = broker.split_once("://").map_or(broker, |(_, address)| address);
+        match address.rsplit_once(':') {
+            Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => {}
+            _ => return Err(format!("broker '{}' is not host:port", broker)),
+        }
+    }
+    Ok(())
+}
+
+/// Reads config.toml, decrypting it first when it is encrypted.
+fn read_plaintext(path: &Path) -> Result<(String, Option<MasterKey>)> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    if crypto::is_encrypted(&contents) {
+        let (plaintext, key) = unlock(&contents, path)?;
+        Ok((plaintext, Some(key)))
+    } else {
+        Ok((contents, None))
+    }
+}
+
+/// Asks for the master passphrase on the terminal, before the TUI starts.
+fn unlock(contents: &str, path: &Path) -> Result<(String, MasterKey)> {
+    const ATTEMPTS: usize = 3;
+    let mut last_error = None;
+    for _ in 0..ATTEMPTS {
+        let passphrase = rpassword::prompt_password(format!("Passphrase for {}: ", path.display()))
+            .context("failed to read passphrase")?;
+        match crypto::decrypt(contents, &passphrase) {
+            Ok(unlocked) => return Ok(unlocked),
+            Err(e) => {
+                eprintln!("{}", e);
+                last_error = Some(e);
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| anyhow!("no passphrase entered")))
+}
+
+fn read_new_passphrase() -> Result<String> {
+    let passphrase = rpassword::prompt_password("New master passphrase: ").context("failed to read passphrase")?;
+    if passphrase.is_empty() {
+        bail!("the passphrase must not be empty");
+    }
+    let repeated = rpassword::prompt_password("Repeat passphrase: ").context("failed to read passphrase")?;
+    if passphrase != repeated {
+        bail!("passphrases do not match");
+    }
+    Ok(passphrase)
+}
+
+/// Replaces `path` through a temporary file in the same directory, so an
+/// interrupted write leaves the old config whole. The config holds secrets,
+/// so the new file is readable by its owner only.
+fn write_private(path: &Path, contents: &str) -> Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    // A leftover would keep its own mode
+    let _ = fs::remove_file(&tmp);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let written = options.open(&tmp).and_then(|mut file| {
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)
+    });
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp);
+        return Err(e).with_context(|| format!("failed to write {}", path.display()));
+    }
+    // Makes the rename itself durable
+    if let Some(dir) = path.parent().and_then(|dir| fs::File::open(dir).ok()) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+/// `kafka-eye encrypt-config`: encrypts a plaintext config, or changes the
+/// passphrase of an encrypted one. The file is encrypted as it is, so it
+/// doesn't have to be valid.
+pub fn encrypt_config() -> Result<()> {
+    let path = AppConfig::config_path();
+    let (contents, _) = read_plaintext(&path)?;
+    let key = MasterKey::new(&read_new_passphrase()?)?;
+    write_private(&path, &key.encrypt(&contents)?)?;
+    println!("Encrypted {}", path.display());
+    Ok(())
+}
+
+/// `kafka-eye decrypt-config`: writes the config back as plaintext.
+pub fn decrypt_config() -> Result<()> {
+    let path = AppConfig::config_path();
+    let (contents, key) = read_plaintext(&path)?;
+    if key.is_none() {
+        bail!("{} is not encrypted", path.display());
+    }
+    write_private(&path, &contents)?;
+    println!("Decrypted {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn properties_split_on_semicolons_and_newlines() {
+        let props = parse_properties("client.id=eye; consumer.fetch.max.bytes = 1024\nacks=all;").unwrap();
+        assert_eq!(props.len(), 3);
+        assert_eq!(props["client.id"], "eye");
+        assert_eq!(props["consumer.fetch.max.bytes"], "1024");
+        assert_eq!(props["acks"], "all");
+    }
+
+    #[test]
+    fn properties_keep_quoted_and_escaped_semicolons() {
+        let props = parse_properties(r#"ssl.cipher.suites="A;B"; sasl.jaas.config=x required user="u"\;; a=b\;c"#).unwrap();
+        assert_eq!(props["ssl.cipher.suites"], "A;B");
+        assert_eq!(props["sasl.jaas.config"], "x required user=u;");
+        assert_eq!(props["a"], "b;c");
+    }
+
+    #[test]
+    fn properties_report_bad_fragments() {
+        assert!(parse_properties("a=1; oops").unwrap_err().contains("oops"));
+        assert!(parse_properties("=1").is_err());
+        assert!(parse_properties(r#"a="1;2"#).is_err());
+        assert!(parse_properties(r"a=1\").is_err());
+        assert!(parse_properties("  ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn configs_are_replaced_whole_and_owner_only() {
+        let dir = std::env::temp_dir().join(format!("kafka-eye-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        fs::write(&path, "old").unwrap();
+        fs::write(dir.join("config.toml.tmp"), "stale").unwrap();
+
+        write_private(&path, "new").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert!(!dir.join("config.toml.tmp").exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn home_relative_cert_paths_are_expanded() {
+        let cluster: ClusterConfig = toml::from_str(
+            r#"name = "dev"
+brokers = "localhost:9093"
+auth = { type = "Ssl", ca_cert = "~/certs/ca.pem" }
+properties = { "ssl.crl.location" = "~/certs/crl.pem", "client.id" = "~/not-a-path" }
+"#,
+        )
+        .unwrap();
+        let home = dirs::home_dir().unwrap();
+        let resolved = cluster.resolve_secrets(None).unwrap();
+        let AuthConfig::Ssl { ca_cert, .. } = &resolved.auth else { panic!("{:?}", resolved.auth) };
+        assert_eq!(ca_cert.as_deref().map(PathBuf::from), Some(home.join("certs/ca.pem")));
+        assert_eq!(PathBuf::from(&resolved.properties["ssl.crl.location"]), home.join("certs/crl.pem"));
+        assert_eq!(resolved.properties["client.id"], "~/not-a-path");
+    }
+
+    #[test]
+    fn unversioned_configs_are_not_rewritten() {
+        let mut config: AppConfig = toml::from_str("[[clusters]]\nname = \"local\"\nbrokers = \"localhost:9092\"\nauth = { type = \"None\" }\n").unwrap();
+        assert_eq!(config.version, 0);
+        assert!(!config.migrate());
+        assert_eq!(config.version, CONFIG_VERSION);
+    }
+
+    #[test]
+    fn issues_point_at_the_offending_key() {
+        let contents = r#"version = 1
+
+# The dev cluster
+[[clusters]]
+name = "dev"
+brokers = "localhost:9092"
+
+[clusters.auth]
+type = "None"
+
+[[clusters]]
+name = "dev"
+brokers = "not a broker list"
+
+[clusters.auth]
+type = "SaslOAuthBearer"
+token_endpoint = "ftp://idp"
+client_id = "eye"
+client_secret = "s3cret"
+"#;
+        let config: AppConfig = toml::from_str(contents).unwrap();
+        let issues = config.validate(contents);
+        let line = |needle: &str| issues.iter().find(|i| i.message.contains(needle)).and_then(|i| i.line);
+        assert_eq!(issues.len(), 3, "{:?}", issues.iter().map(|i| &i.message).collect::<Vec<_>>());
+        assert_eq!(line("same name"), Some(12));
+        assert_eq!(line("not a broker list"), Some(13));
+        assert_eq!(line("token endpoint"), Some(17));
+    }
+
+    #[test]
+    fn formatted_properties_parse_back() {
+        let mut props = BTreeMap::new();
+        props.insert("ssl.cipher.suites".to_string(), "A;B".to_string());
+        props.insert("sasl.jaas.config".to_string(), r#"m required password="p\w";"#.to_string());
+        assert_eq!(parse_properties(&format_properties(&props)).unwrap(), props);
+    }
+}
@@ -0,0 +1,130 @@
+//! Encryption of config.toml at rest. The file keeps a header line so it can
+//! be told apart from a plaintext config, followed by the base64 of
+//! `salt | nonce | ciphertext`. The key is derived from a master passphrase
+//! with Argon2id and the config is sealed with ChaCha20-Poly1305.
+
+use anyhow::{anyhow, bail, Result};
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+
+const HEADER: &str = "# kafka-eye encrypted config v1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// A key derived from the master passphrase, kept so the config can be
+/// saved again without asking for the passphrase on every change.
+#[derive(Clone)]
+pub struct MasterKey {
+    key: [u8; 32],
+    salt: [u8; SALT_LEN],
+}
+
+impl std::fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("MasterKey(..)")
+    }
+}
+
+impl MasterKey {
+    /// Derives a key for a new passphrase with a fresh salt.
+    pub fn new(passphrase: &str) -> Result<Self> {
+        let mut salt = [0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        Self::derive(passphrase, salt)
+    }
+
+    fn derive(passphrase: &str, salt: [u8; SALT_LEN]) -> Result<Self> {
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+            .map_err(|e| anyhow!("key derivation failed: {}", e))?;
+        Ok(Self { key, salt })
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let cipher = ChaCha20Poly1305::new(&self.key.into());
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| anyhow!("encryption failed"))?;
+
+        let mut sealed = Vec::with_capacity(SALT_LEN + NONCE_LEN + ciphertext.len());
+        sealed.extend_from_slice(&self.salt);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(format!("{}\n{}\n", HEADER, STANDARD.encode(sealed)))
+    }
+}
+
+pub fn is_encrypted(contents: &str) -> bool {
+    contents.starts_with(HEADER)
+}
+
+/// Decrypts an encrypted config, returning the plaintext and the key so
+/// later saves can reuse it.
+pub fn decrypt(contents: &str, passphrase: &str) -> Result<(String, MasterKey)> {
+    let Some(body) = contents.strip_prefix(HEADER) else {
+        bail!("not an encrypted config");
+    };
+    let sealed = STANDARD
+        .decode(body.trim())
+        .map_err(|e| anyhow!("encrypted config is corrupted: {}", e))?;
+    if sealed.len() < SALT_LEN + NONCE_LEN {
+        bail!("encrypted config is truncated");
+    }
+    let (salt, rest) = sealed.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let key = MasterKey::derive(passphrase, salt.try_into()?)?;
+    let cipher = ChaCha20Poly1305::new(&key.key.into());
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("wrong passphrase or corrupted config"))?;
+    Ok((String::from_utf8(plaintext)?, key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "[[clusters]]\nname = \"dev\"\n";
+
+    #[test]
+    fn encrypted_configs_decrypt_with_their_passphrase() {
+        let sealed = MasterKey::new("correct horse").unwrap().encrypt(CONFIG).unwrap();
+        assert!(is_encrypted(&sealed));
+        assert!(!sealed.contains("clusters"));
+
+        let (plaintext, key) = decrypt(&sealed, "correct horse").unwrap();
+        assert_eq!(plaintext, CONFIG);
+        // The returned key saves again without asking for the passphrase
+        let resealed = key.encrypt("changed").unwrap();
+        assert_eq!(decrypt(&resealed, "correct horse").unwrap().0, "changed");
+    }
+
+    #[test]
+    fn wrong_passphrases_are_rejected() {
+        let sealed = MasterKey::new("correct horse").unwrap().encrypt(CONFIG).unwrap();
+        let err = decrypt(&sealed, "battery staple").unwrap_err();
+        assert!(err.to_string().contains("wrong passphrase"));
+    }
+
+    #[test]
+    fn tampered_configs_are_rejected() {
+        let sealed = MasterKey::new("correct horse").unwrap().encrypt(CONFIG).unwrap();
+        let body = sealed.strip_prefix(HEADER).unwrap().trim();
+        let mut bytes = STANDARD.decode(body).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        let tampered = format!("{}\n{}\n", HEADER, STANDARD.encode(&bytes));
+        assert!(decrypt(&tampered, "correct horse").is_err());
+
+        let truncated = format!("{}\n{}\n", HEADER, STANDARD.encode(&bytes[..SALT_LEN]));
+        assert!(decrypt(&truncated, "correct horse").unwrap_err().to_string().contains("truncated"));
+        assert!(decrypt(CONFIG, "correct horse").is_err());
+    }
+}
@@ -0,0 +1,809 @@
+//! Filter language for the message browser.
+//!
+//! ```text
+//! value.order.status == "FAILED" and not header.retry exists
+//! key ~ /^user-\d+$/ or (partition == 3 and offset >= 1000)
+//! timestamp >= "2024-05-01T12:00:00" and value.items[0].qty > 10
+//! timestamp >= "-1h"
+//! ```
+//!
+//! Fields are `key`, `value`, `value.<json path>`, `header.<name>`,
+//! `partition`, `offset` and `timestamp`. Operators are `==`, `!=`, `<`,
+//! `<=`, `>`, `>=`, `~` / `!~` (regex, `/.../i` for case-insensitive) and
+//! `exists`. Terms combine with `and`, `or`, `not` and parentheses; terms
+//! next to each other are and-ed. A bare word or string searches key and
+//! value, like the old substring filter, and so does a whole query that
+//! doesn't parse but has no operators in it, such as `user@example.com`.
+//! Null keys, tombstone values and null header values compare equal to
+//! `null`.
+
+use std::fmt;
+
+use regex::{Regex, RegexBuilder};
+use serde_json::Value;
+
+use crate::app::KafkaMessage;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterError {
+    /// Character offset into the query
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (col {})", self.message, self.position + 1)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Filter {
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+    /// Case-insensitive substring of key or value
+    Contains(String),
+    Exists(Field),
+    Compare(Field, CompareOp, Literal),
+    Matches(Field, Regex, bool),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Field {
+    Key,
+    Value,
+    /// Path into the value parsed as JSON
+    ValuePath(Vec<PathSegment>),
+    Header(String),
+    Partition,
+    Offset,
+    Timestamp,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    Name(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    String(String),
+    Number(f64),
+    Bool(bool),
+    Null,
+}
+
+impl Filter {
+    pub fn matches(&self, message: &KafkaMessage) -> bool {
+        match self {
+            Filter::And(a, b) => a.matches(message) && b.matches(message),
+            Filter::Or(a, b) => a.matches(message) || b.matches(message),
+            Filter::Not(f) => !f.matches(message),
+            Filter::Contains(text) => {
+                let text = text.to_lowercase();
+                message.key.as_deref().unwrap_or("").to_lowercase().contains(&text)
+                    || message.value.as_deref().unwrap_or("").to_lowercase().contains(&text)
+            }
+            // A header with a null value is still there
+            Filter::Exists(Field::Header(name)) => message.headers.iter().any(|(k, _)| k == name),
+            Filter::Exists(field) => !matches!(field.resolve(message), None | Some(Value::Null)),
+            Filter::Compare(field, op, literal) => match field.resolve(message) {
+                Some(value) => compare(&value, *op, literal),
+                // A missing field only equals null
+                None => match op {
+                    CompareOp::Eq => *literal == Literal::Null,
+                    CompareOp::Ne => *literal != Literal::Null,
+                    _ => false,
+                },
+            },
+            Filter::Matches(field, regex, negated) => {
+                let found = field.resolve(message).is_some_and(|v| regex.is_match(&as_text(&v)));
+                found != *negated
+            }
+        }
+    }
+}
+
+impl Field {
+    fn resolve(&self, message: &KafkaMessage) -> Option<Value> {
+        match self {
+            Field::Key => message.key.clone().map(Value::String),
+            Field::Value => message.value.clone().map(Value::String),
+            Field::ValuePath(path) => {
+                let mut current = serde_json::from_str::<Value>(message.value.as_deref()?).ok()?;
+                for segment in path {
+                    current = match segment {
+                        PathSegment::Name(name) => current.get_mut(name.as_str())?.take(),
+                        PathSegment::Index(index) => current.get_mut(*index)?.take(),
+                    };
+                }
+                Some(current)
+            }
+            Field::Header(name) => message
+                .headers
+                .iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.clone().map(Value::String).unwrap_or(Value::Null)),
+            Field::Partition => Some(Value::from(message.partition)),
+            Field::Offset => Some(Value::from(message.offset)),
+            Field::Timestamp => message.timestamp.map(Value::from),
+        }
+    }
+}
+
+/// Strings without quotes, everything else as JSON.
+fn as_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn compare(value: &Value, op: CompareOp, literal: &Literal) -> bool {
+    let ordering = match literal {
+        Literal::Null => Some(if value.is_null() { std::cmp::Ordering::Equal } else { std::cmp::Ordering::Greater }),
+        Literal::Bool(b) => match value {
+            Value::Bool(v) => Some(v.cmp(b)),
+            other => Some(as_text(other).cmp(&b.to_string())),
+        },
+        Literal::Number(n) => as_number(value).and_then(|v| v.partial_cmp(n)),
+        Literal::String(s) => Some(as_text(value).as_str().cmp(s.as_str())),
+    };
+    let Some(ordering) = ordering else {
+        return op == CompareOp::Ne;
+    };
+    match op {
+        CompareOp::Eq => ordering.is_eq(),
+        CompareOp::Ne => ordering.is_ne(),
+        CompareOp::Lt => ordering.is_lt(),
+        CompareOp::Le => ordering.is_le(),
+        CompareOp::Gt => ordering.is_gt(),
+        CompareOp::Ge => ordering.is_ge(),
+    }
+}
+
+/// Parses a filter query. An empty query gives `None`, which matches everything.
+pub fn parse(query: &str) -> Result<Option<Filter>, FilterError> {
+    match parse_query(query) {
+        // Plain text with characters the language has no use for
+        Err(_) if !OPERATORS.iter().any(|op| query.contains(op)) && !query.contains(['(', ')']) => {
+            Ok(Some(Filter::Contains(query.trim().to_string())))
+        }
+        result => result,
+    }
+}
+
+fn parse_query(query: &str) -> Result<Option<Filter>, FilterError> {
+    let tokens = tokenize(query)?;
+    if tokens.is_empty() {
+        return Ok(None);
+    }
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        end: query.chars().count(),
+    };
+    let filter = parser.or()?;
+    if let Some((token, position)) = parser.tokens.get(parser.pos) {
+        return Err(FilterError {
+            position: *position,
+            message: format!("unexpected {}", token.describe()),
+        });
+    }
+    Ok(Some(filter))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    String(String),
+    Number(f64),
+    Regex(String, bool),
+    Op(&'static str),
+    LParen,
+    RParen,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Word(w) => format!("'{}'", w),
+            Token::String(s) => format!("\"{}\"", s),
+            Token::Number(n) => n.to_string(),
+            Token::Regex(r, _) => format!("/{}/", r),
+            Token::Op(op) => format!("'{}'", op),
+            Token::LParen => "'('".to_string(),
+            Token::RParen => "')'".to_string(),
+        }
+    }
+}
+
+const OPERATORS: [&str; 10] = ["==", "!=", "<=", ">=", "!~", "&&", "||", "<", ">", "~"];
+
+fn tokenize(query: &str) -> Result<Vec<(Token, usize)>, FilterError> {
+    let chars: Vec<char> = query.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == '(' || c == ')' {
+            tokens.push((if c == '(' { Token::LParen } else { Token::RParen }, start));
+            i += 1;
+        } else if c == '"' || c == '\'' {
+            let mut text = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => {
+                        return Err(FilterError {
+                            position: start,
+                            message: "unterminated string".to_string(),
+                        })
+                    }
+                    Some('\\') if i + 1 < chars.len() => {
+                        text.push(chars[i + 1]);
+                        i += 2;
+                    }
+                    Some(&ch) if ch == c => {
+                        i += 1;
+                        break;
+                    }
+                    Some(&ch) => {
+                        text.push(ch);
+                        i += 1;
+                    }
+                }
+            }
+            tokens.push((Token::String(text), start));
+        } else if c == '/' {
+            let mut pattern = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => {
+                        return Err(FilterError {
+                            position: start,
+                            message: "unterminated regex".to_string(),
+                        })
+                    }
+                    // `\/` is a literal slash, other escapes belong to the regex
+                    Some('\\') if chars.get(i + 1) == Some(&'/') => {
+                        pattern.push('/');
+                        i += 2;
+                    }
+                    Some('\\') if i + 1 < chars.len() => {
+                        pattern.push('\\');
+                        pattern.push(chars[i + 1]);
+                        i += 2;
+                    }
+                    Some('/') => {
+                        i += 1;
+                        break;
+                    }
+                    Some(&ch) => {
+                        pattern.push(ch);
+                        i += 1;
+                    }
+                }
+            }
+            let case_insensitive = chars.get(i) == Some(&'i');
+            if case_insensitive {
+                i += 1;
+            }
+            tokens.push((Token::Regex(pattern, case_insensitive), start));
+        } else if c.is_ascii_digit() || (c == '-' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit())) {
+            i += 1;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let is_word_char = |c: Option<&char>| c.is_some_and(|c| c.is_alphanumeric() || *c == '_');
+            // A relative time such as -1h is one literal, as if it were quoted
+            if c == '-' && chars.get(i).is_some_and(|u| "smhd".contains(*u)) && !is_word_char(chars.get(i + 1)) {
+                i += 1;
+                tokens.push((Token::String(chars[start..i].iter().collect()), start));
+                continue;
+            }
+            if is_word_char(chars.get(i)) {
+                return Err(FilterError {
+                    position: i,
+                    message: format!("expected a space or operator after the number {}", chars[start..i].iter().collect::<String>()),
+                });
+            }
+            let text: String = chars[start..i].iter().collect();
+            let number = text.parse().map_err(|_| FilterError {
+                position: start,
+                message: format!("invalid number {}", text),
+            })?;
+            tokens.push((Token::Number(number), start));
+        } else if let Some(op) = OPERATORS.iter().find(|op| {
+            op.chars().enumerate().all(|(j, oc)| chars.get(i + j) == Some(&oc))
+        }) {
+            i += op.len();
+            tokens.push((Token::Op(op), start));
+        } else if c == '!' {
+            tokens.push((Token::Op("!"), start));
+            i += 1;
+        } else if c.is_alphanumeric() || c == '_' {
+            // Words include paths such as value.items[0].id and header names such as trace-id
+            while i < chars.len() && (chars[i].is_alphanumeric() || "_.-[]".contains(chars[i])) {
+                i += 1;
+            }
+            tokens.push((Token::Word(chars[start..i].iter().collect()), start));
+        } else {
+            return Err(FilterError {
+                position: start,
+                message: format!("unexpected character '{}'", c),
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    /// Position reported for errors at the end of the query
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens.get(self.pos).map(|(_, p)| *p).unwrap_or(self.end)
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, FilterError> {
+        Err(FilterError {
+            position: self.position(),
+            message: message.into(),
+        })
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword))
+    }
+
+    fn or(&mut self) -> Result<Filter, FilterError> {
+        let mut left = self.and()?;
+        while self.is_keyword("or") || self.peek() == Some(&Token::Op("||")) {
+            self.pos += 1;
+            let right = self.and()?;
+            left = Filter::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Filter, FilterError> {
+        let mut left = self.unary()?;
+        loop {
+            if self.is_keyword("and") || self.peek() == Some(&Token::Op("&&")) {
+                self.pos += 1;
+            } else if self.peek().is_none() || self.peek() == Some(&Token::RParen) || self.is_keyword("or") || self.peek() == Some(&Token::Op("||")) {
+                break;
+            }
+            let right = self.unary()?;
+            left = Filter::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Filter, FilterError> {
+        if self.is_keyword("not") || self.peek() == Some(&Token::Op("!")) {
+            self.pos += 1;
+            return Ok(Filter::Not(Box::new(self.unary()?)));
+        }
+        match self.peek().cloned() {
+            Some(Token::LParen) => {
+                self.pos += 1;
+                let inner = self.or()?;
+                if self.peek() != Some(&Token::RParen) {
+                    return self.error("expected ')'");
+                }
+                self.pos += 1;
+                Ok(inner)
+            }
+            Some(Token::String(text)) => {
+                self.pos += 1;
+                Ok(Filter::Contains(text))
+            }
+            Some(Token::Number(n)) => {
+                self.pos += 1;
+                Ok(Filter::Contains(n.to_string()))
+            }
+            Some(Token::Word(word)) => self.predicate(word),
+            Some(other) => self.error(format!("expected a filter term, found {}", other.describe())),
+            None => self.error("expected a filter term"),
+        }
+    }
+
+    fn predicate(&mut self, word: String) -> Result<Filter, FilterError> {
+        let field_position = self.position();
+        self.pos += 1;
+        let op = match self.peek() {
+            Some(Token::Op(op)) if !matches!(*op, "&&" | "||" | "!") => Some(*op),
+            Some(Token::Word(w)) if w.eq_ignore_ascii_case("exists") => Some("exists"),
+            _ => None,
+        };
+        let Some(op) = op else {
+            // A bare word is a substring search
+            return Ok(Filter::Contains(word));
+        };
+        let field = parse_field(&word).map_err(|message| FilterError {
+            position: field_position,
+            message,
+        })?;
+        self.pos += 1;
+        if op == "exists" {
+            return Ok(Filter::Exists(field));
+        }
+
+        let value_position = self.position();
+        let Some(value) = self.peek().cloned() else {
+            return self.error(format!("expected a value after '{}'", op));
+        };
+        self.pos += 1;
+        if op == "~" || op == "!~" {
+            let (pattern, case_insensitive) = match value {
+                Token::Regex(pattern, ci) => (pattern, ci),
+                Token::String(pattern) => (pattern, false),
+                other => {
+                    return Err(FilterError {
+                        position: value_position,
+                        message: format!("expected /regex/ after '{}', found {}", op, other.describe()),
+                    })
+                }
+            };
+            let regex = RegexBuilder::new(&pattern)
+                .case_insensitive(case_insensitive)
+                .build()
+                .map_err(|e| FilterError {
+                    position: value_position,
+                    message: format!(
+                        "invalid regex: {}",
+                        e.to_string().lines().last().unwrap_or_default().trim().trim_start_matches("error: ")
+                    ),
+                })?;
+            return Ok(Filter::Matches(field, regex, op == "!~"));
+        }
+
+        let compare_op = match op {
+            "==" => CompareOp::Eq,
+            "!=" => CompareOp::Ne,
+            "<" => CompareOp::Lt,
+            "<=" => CompareOp::Le,
+            ">" => CompareOp::Gt,
+            _ => CompareOp::Ge,
+        };
+        let literal = match value {
+            Token::String(s) if field == Field::Timestamp => Literal::Number(parse_time(&s).ok_or_else(|| FilterError {
+                position: value_position,
+                message: format!("invalid time \"{}\", expected e.g. 2024-05-01T12:00:00 or -1h", s),
+            })? as f64),
+            Token::String(s) => Literal::String(s),
+            Token::Number(n) => Literal::Number(n),
+            Token::Word(w) if w == "true" || w == "false" => Literal::Bool(w == "true"),
+            Token::Word(w) if w == "null" => Literal::Null,
+            other => {
+                return Err(FilterError {
+                    position: value_position,
+                    message: format!("expected a value after '{}', found {}", op, other.describe()),
+                })
+            }
+        };
+        Ok(Filter::Compare(field, compare_op, literal))
+    }
+}
+
+fn parse_field(word: &str) -> Result<Field, String> {
+    match word {
+        "key" => return Ok(Field::Key),
+        "value" => return Ok(Field::Value),
+        "partition" => return Ok(Field::Partition),
+        "offset" => return Ok(Field::Offset),
+        "timestamp" => return Ok(Field::Timestamp),
+        _ => {}
+    }
+    if let Some(name) = word.strip_prefix("header.") {
+        if name.is_empty() {
+            return Err("expected a header name after 'header.'".to_string());
+        }
+        return Ok(Field::Header(name.to_string()));
+    }
+    let Some(path) = word.strip_prefix("value").filter(|p| p.starts_with(['.', '['])) else {
+        return Err(format!(
+            "unknown field '{}' (use key, value, value.<path>, header.<name>, partition, offset or timestamp)",
+            word
+        ));
+    };
+    let mut segments = Vec::new();
+    let mut rest = path;
+    while !rest.is_empty() {
+        if let Some((index, after)) = rest.strip_prefix('[').and_then(|r| r.split_once(']')) {
+            if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(format!("invalid index in '{}'", word));
+            }
+            segments.push(PathSegment::Index(index.parse().map_err(|_| format!("invalid index in '{}'", word))?));
+            rest = after;
+        } else if let Some(after_dot) = rest.strip_prefix('.') {
+            let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+            if end == 0 {
+                return Err(format!("empty path segment in '{}'", word));
+            }
+            segments.push(PathSegment::Name(after_dot[..end].to_string()));
+            rest = &after_dot[end..];
+        } else {
+            return Err(format!("invalid path '{}'", word));
+        }
+    }
+    Ok(Field::ValuePath(segments))
+}
+
+/// Milliseconds since the epoch for an RFC 3339 time, a local date-time,
+/// a local date, `now`, or a time relative to now such as `-30m`, `-2h` or `-1d`.
+pub fn parse_time(text: &str) -> Option<i64> {
+    let now = chrono::Local::now().timestamp_millis();
+    if text == "now" {
+        return Some(now);
+    }
+    if let Some(relative) = text.strip_prefix('-') {
+        let units = [('s', 1_000), ('m', 60_000), ('h', 3_600_000), ('d', 86_400_000)];
+        let split = units.iter().find_map(|&(unit, ms)| Some((relative.strip_suffix(unit)?, ms)));
+        if let Some((amount, unit_ms)) = split {
+            let ago = amount.parse::<i64>().ok()?.checked_mul(unit_ms)?;
+            return now.checked_sub(ago);
+        }
+    }
+    if let Ok(t) = chrono::DateTime::parse_from_rfc3339(text) {
+        return Some(t.timestamp_millis());
+    }
+    let naive = chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S")
+        .or_else(|_| chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S"))
+        .ok()
+        .or_else(|| chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0))?;
+    naive.and_local_timezone(chrono::Local).earliest().map(|t| t.timestamp_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(key: Option<&str>, value: Option<&str>) -> KafkaMessage {
+        KafkaMessage {
+            partition: 3,
+            offset: 1200,
+            key: key.map(String::from),
+            value: value.map(String::from),
+            timestamp: Some(chrono::Utc::now().timestamp_millis() - 10 * 60_000),
+            timestamp_type: None,
+            headers: vec![("trace-id".to_string(), Some("abc".to_string())), ("empty".to_string(), None)],
+            key_size: 0,
+            value_size: 0,
+            headers_size: 0,
+            leader_epoch: None,
+        }
+    }
+
+    fn matches(query: &str, msg: &KafkaMessage) -> bool {
+        parse(query).unwrap().expect("non-empty query").matches(msg)
+    }
+
+    fn error(query: &str) -> FilterError {
+        parse(query).unwrap_err()
+    }
+
+    #[test]
+    fn empty_query_matches_everything() {
+        assert!(parse("").unwrap().is_none());
+        assert!(parse("   ").unwrap().is_none());
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        // a or (b and c)
+        assert!(matches("a or b and c", &message(None, Some("a"))));
+        assert!(!matches("a or b and c", &message(None, Some("b"))));
+        assert!(matches("a or b and c", &message(None, Some("b c"))));
+        assert!(!matches("(a or b) and c", &message(None, Some("a"))));
+        assert!(matches("x || y && z", &message(None, Some("x"))));
+    }
+
+    #[test]
+    fn adjacent_terms_are_and_ed() {
+        assert!(matches("foo bar", &message(None, Some("bar foo"))));
+        assert!(!matches("foo bar", &message(None, Some("foo"))));
+        assert!(matches("partition == 3 offset >= 1000", &message(None, None)));
+    }
+
+    #[test]
+    fn not_negates_the_next_term() {
+        assert!(matches("not foo", &message(None, Some("bar"))));
+        assert!(!matches("!foo", &message(None, Some("foo"))));
+        assert!(matches("not key == \"a\" and bar", &message(Some("b"), Some("bar"))));
+        assert!(!matches("not (foo or bar)", &message(None, Some("bar"))));
+    }
+
+    #[test]
+    fn substring_search_ignores_case_and_covers_the_key() {
+        assert!(matches("ERROR", &message(None, Some("an error occurred"))));
+        assert!(matches("\"user 42\"", &message(Some("User 42"), None)));
+    }
+
+    #[test]
+    fn regex_flags_and_escaped_slashes() {
+        let msg = message(Some("user-42"), Some("path a/b/c"));
+        assert!(matches(r"key ~ /^user-\d+$/", &msg));
+        assert!(!matches(r"key ~ /^USER-\d+$/", &msg));
+        assert!(matches(r"key ~ /^USER-\d+$/i", &msg));
+        assert!(matches(r"value ~ /a\/b\/c/", &msg));
+        assert!(matches(r"key !~ /^order-/", &msg));
+        assert!(matches(r#"key ~ "\\d+""#, &msg));
+    }
+
+    #[test]
+    fn json_paths_with_indices() {
+        let msg = message(None, Some(r#"{"order":{"status":"FAILED"},"items":[{"qty":5},{"qty":12,"sku":"x"}]}"#));
+        assert!(matches(r#"value.order.status == "FAILED""#, &msg));
+        assert!(matches("value.items[1].qty > 10", &msg));
+        assert!(!matches("value.items[0].qty > 10", &msg));
+        assert!(matches("value.items[1].sku exists", &msg));
+        assert!(!matches("value.items[5] exists", &msg));
+        assert!(error("value.items[x] == 1").message.starts_with("invalid index"));
+        assert!(error("value.items[] == 1").message.starts_with("invalid index"));
+        assert!(matches("value.items[0].qty == \"5\"", &msg));
+    }
+
+    #[test]
+    fn missing_fields_and_nulls() {
+        let json = message(None, Some(r#"{"a":null,"b":1}"#));
+        assert!(matches("value.missing == null", &json));
+        assert!(!matches("value.missing != null", &json));
+        assert!(!matches("value.missing > 0", &json));
+        assert!(!matches("value.missing exists", &json));
+        assert!(matches("value.a == null", &json));
+        assert!(!matches("value.a exists", &json));
+        assert!(matches("value.b != null", &json));
+
+        let tombstone = message(None, None);
+        assert!(matches("key == null and value == null", &tombstone));
+        assert!(!matches("key exists", &tombstone));
+        assert!(!matches("value.x exists", &tombstone));
+
+        // A header with a null value exists and equals null
+        assert!(matches("header.empty exists and header.empty == null", &tombstone));
+        assert!(matches("header.trace-id == \"abc\"", &tombstone));
+        assert!(!matches("header.missing exists", &tombstone));
+        assert!(matches("header.missing == null", &tombstone));
+    }
+
+    #[test]
+    fn timestamp_literals() {
+        let msg = message(None, None);
+        assert!(matches(r#"timestamp >= "-1h""#, &msg));
+        assert!(!matches(r#"timestamp >= "-5m""#, &msg));
+        assert!(matches(r#"timestamp > "2000-01-01""#, &msg));
+        assert!(matches(r#"timestamp > "2000-01-01T00:00:00Z" and timestamp < "now""#, &msg));
+        let e = error(r#"timestamp >= "yesterday""#);
+        assert!(e.message.starts_with("invalid time"), "{}", e);
+        assert_eq!(e.position, 13);
+    }
+
+    #[test]
+    fn unquoted_relative_times() {
+        let msg = message(None, None);
+        assert!(matches("timestamp >= -1h", &msg));
+        assert!(!matches("timestamp >= -5m", &msg));
+        assert!(matches("timestamp >= -1d and partition == 3", &msg));
+        assert!(matches!(
+            parse("timestamp >= -1h"),
+            Ok(Some(Filter::Compare(Field::Timestamp, CompareOp::Ge, Literal::Number(_))))
+        ));
+        // A word glued to a number is an error, not an implicit `and`
+        let e = error("offset > 5abc");
+        assert_eq!(e.position, 10);
+        assert!(e.message.contains("after the number 5"), "{}", e);
+        assert!(parse("timestamp >= -1hour").is_err());
+    }
+
+    #[test]
+    fn errors_report_their_column() {
+        let e = error("key == ");
+        assert_eq!(e.position, 7);
+        assert!(e.message.contains("expected a value"), "{}", e);
+
+        let e = error("foo and nope == 1");
+        assert_eq!(e.position, 8);
+        assert!(e.message.starts_with("unknown field 'nope'"), "{}", e);
+
+        let e = error("(key == \"a\"");
+        assert_eq!(e.position, 11);
+        assert_eq!(e.to_string(), "expected ')' (col 12)");
+
+        let e = error("key ~ /[/");
+        assert_eq!(e.position, 6);
+        assert!(e.message.starts_with("invalid regex"), "{}", e);
+
+        assert_eq!(error("key == \"open").message, "unterminated string");
+        assert_eq!(error("a)").position, 1);
+    }
+
+    #[test]
+    fn relative_times() {
+        let now = chrono::Local::now().timestamp_millis();
+        let close = |text: &str, ago: i64| {
+            let t = parse_time(text).unwrap_or_else(|| panic!("{} didn't parse", text));
+            assert!((now - ago - t).abs() < 5_000, "{} gave {}", text, t);
+        };
+        close("now", 0);
+        close("-30s", 30_000);
+        close("-15m", 15 * 60_000);
+        close("-2h", 2 * 3_600_000);
+        close("-1d", 86_400_000);
+        assert_eq!(parse_time("-1w"), None);
+        assert_eq!(parse_time("-h"), None);
+        assert_eq!(parse_time("-"), None);
+        assert_eq!(parse_time("-99999999999999999d"), None);
+    }
+
+    #[test]
+    fn absolute_times() {
+        assert_eq!(parse_time("2024-05-01T12:00:00Z"), Some(1_714_564_800_000));
+        assert_eq!(parse_time("2024-05-01T14:00:00+02:00"), Some(1_714_564_800_000));
+        let local = chrono::NaiveDate::from_ymd_opt(2024, 5, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+        let local = local.and_local_timezone(chrono::Local).earliest().unwrap().timestamp_millis();
+        assert_eq!(parse_time("2024-05-01T12:00:00"), Some(local));
+        assert_eq!(parse_time("2024-05-01 12:00:00"), Some(local));
+        assert!(parse_time("2024-05-01").is_some());
+        assert_eq!(parse_time("2024-13-01"), None);
+        assert_eq!(parse_time(""), None);
+    }
+
+    #[test]
+    fn non_ascii_times_are_rejected_without_panicking() {
+        for text in ["-1é", "-é", "-1日", "-🕐", "é", "2024-05-01T12:00:00é"] {
+            assert_eq!(parse_time(text), None, "{}", text);
+        }
+        let e = error(r#"timestamp >= "-1é""#);
+        assert!(e.message.starts_with("invalid time"), "{}", e);
+    }
+
+    #[test]
+    fn plain_text_without_operators_is_a_substring_search() {
+        let msg = message(Some("user@example.com"), Some("error: disk full #42"));
+        for query in ["user@example.com", "error:", "#42", "a=b", "full!", "path/to"] {
+            assert!(matches!(parse(query), Ok(Some(Filter::Contains(_)))), "{}", query);
+        }
+        assert!(matches("user@example.com", &msg));
+        assert!(matches("error: disk", &msg));
+        // With an operator in it, the query is meant as one and errors stay
+        assert!(parse("key == user@example.com").is_err());
+        assert!(parse("(user@example.com").is_err());
+    }
+}
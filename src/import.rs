@@ -0,0 +1,468 @@
+//! Imports clusters from Java `client.properties` files and kcat configs.
+//! Both are `key=value` files; Java settings are mapped onto `AuthConfig`
+//! and librdkafka settings are kept as extra properties.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::{anyhow, bail, Context, Result};
+use regex::Regex;
+
+use crate::config::{AuthConfig, ClusterConfig};
+use crate::secrets;
+
+/// Java client settings that librdkafka would reject. They are dropped
+/// with a note instead of being kept as properties.
+const JAVA_ONLY: [&str; 13] = [
+    "ssl.truststore.",
+    "ssl.keystore.type",
+    "ssl.keymanager.",
+    "ssl.trustmanager.",
+    "ssl.protocol",
+    "ssl.enabled.protocols",
+    "sasl.login.",
+    "sasl.client.callback.handler.class",
+    "client.dns.lookup",
+    "key.serializer",
+    "value.serializer",
+    "key.deserializer",
+    "value.deserializer",
+];
+
+#[derive(Debug, Clone)]
+pub struct ImportedCluster {
+    pub source: PathBuf,
+    pub cluster: ClusterConfig,
+    /// Anything the user should check: dropped settings, unsupported auth, ...
+    pub notes: Vec<String>,
+}
+
+/// Config files that exist on this machine and are worth offering.
+pub fn default_paths() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Some(config) = dirs::config_dir() {
+        candidates.push(config.join("kcat.conf"));
+    }
+    if let Some(home) = dirs::home_dir() {
+        candidates.push(home.join(".config").join("kcat.conf"));
+        candidates.push(home.join(".kcat.conf"));
+    }
+    candidates.push(PathBuf::from("client.properties"));
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for path in candidates {
+        if path.is_file() && !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+pub fn import_file(path: &Path) -> Result<ImportedCluster> {
+    let contents = std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let name = match path.file_stem().and_then(|s| s.to_str()) {
+        Some(stem) if stem.starts_with('.') => stem.trim_start_matches('.').to_string(),
+        Some(stem) => stem.to_string(),
+        None => "imported".to_string(),
+    };
+    let (cluster, notes) = to_cluster(&name, parse(&contents))
+        .with_context(|| format!("failed to import {}", path.display()))?;
+    Ok(ImportedCluster {
+        source: path.to_path_buf(),
+        cluster,
+        notes,
+    })
+}
+
+/// Parses a `.properties` file: `#` and `!` comments, `=` or `:` separators
+/// and `\` line continuations, which JAAS configs often use.
+fn parse(contents: &str) -> BTreeMap<String, String> {
+    let mut properties = BTreeMap::new();
+    let mut logical = String::new();
+    for line in contents.lines() {
+        let line = line.trim();
+        if logical.is_empty() && (line.is_empty() || line.starts_with('#') || line.starts_with('!')) {
+            continue;
+        }
+        if let Some(continued) = line.strip_suffix('\\') {
+            logical.push_str(continued);
+            logical.push(' ');
+            continue;
+        }
+        logical.push_str(line);
+        let entry = std::mem::take(&mut logical);
+        let Some(pos) = entry.find(['=', ':']) else { continue };
+        let key = entry[..pos].trim();
+        if !key.is_empty() {
+            properties.insert(key.to_string(), entry[pos + 1..].trim().to_string());
+        }
+    }
+    properties
+}
+
+/// Options of a `sasl.jaas.config` login module, e.g. `username="x" password="y"`.
+fn parse_jaas(config: &str) -> HashMap<String, String> {
+    static OPTION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"(\w+)\s*=\s*(?:"([^"]*)"|([^\s;"]+))"#).unwrap());
+    OPTION
+        .captures_iter(config)
+        .map(|c| {
+            let value = c.get(2).or_else(|| c.get(3)).map(|m| m.as_str()).unwrap_or_default();
+            (c[1].to_string(), value.to_string())
+        })
+        .collect()
+}
+
+fn to_cluster(name: &str, mut props: BTreeMap<String, String>) -> Result<(ClusterConfig, Vec<String>)> {
+    let mut notes = Vec::new();
+    let brokers = props
+        .remove("bootstrap.servers")
+        .or_else(|| props.remove("metadata.broker.list"))
+        .ok_or_else(|| anyhow!("no bootstrap.servers"))?;
+    let protocol = props.remove("security.protocol").unwrap_or_else(|| "PLAINTEXT".to_string()).to_uppercase();
+    let mechanism = props
+        .remove("sasl.mechanism")
+        .or_else(|| props.remove("sasl.mechanisms"))
+        .unwrap_or_else(|| "GSSAPI".to_string())
+        .to_uppercase();
+    let jaas = props.remove("sasl.jaas.config").map(|c| parse_jaas(&c)).unwrap_or_default();
+
+    // CA: librdkafka's ssl.ca.location, or a Java truststore if it is PEM
+    let truststore_pem = props.get("ssl.truststore.type").is_some_and(|t| t.eq_ignore_ascii_case("PEM"));
+    let mut ca_cert = props.remove("ssl.ca.location");
+    if let Some(truststore) = props.get("ssl.truststore.location").cloned() {
+        if truststore_pem && ca_cert.is_none() {
+            // Used up, so it isn't reported as dropped
+            props.remove("ssl.truststore.location");
+            props.remove("ssl.truststore.type");
+            ca_cert = Some(truststore);
+        } else if ca_cert.is_none() {
+            notes.push(format!("truststore {} is not PEM; convert it and set the CA path", truststore));
+        }
+    }
+    // A PEM keystore holds both the client certificate and its key
+    if props.get("ssl.keystore.type").is_some_and(|t| t.eq_ignore_ascii_case("PEM")) {
+        if let Some(keystore) = props.remove("ssl.keystore.location") {
+            props.entry("ssl.certificate.location".to_string()).or_insert_with(|| keystore.clone());
+            props.entry("ssl.key.location".to_string()).or_insert(keystore);
+        }
+    } else if props.get("ssl.keystore.type").is_some_and(|t| t.eq_ignore_ascii_case("JKS")) {
+        if let Some(keystore) = props.remove("ssl.keystore.location") {
+            notes.push(format!("JKS keystore {} can't be used; convert it to PKCS#12 or PEM", keystore));
+        }
+        props.remove("ssl.keystore.password");
+    }
+
+    let username = props.remove("sasl.username").or_else(|| jaas.get("username").cloned());
+    let password = props.remove("sasl.password").or_else(|| jaas.get("password").cloned());
+    let tls = match protocol.as_str() {
+        "PLAINTEXT" | "SASL_PLAINTEXT" => false,
+        "SSL" | "SASL_SSL" => true,
+        other => bail!("unknown security.protocol {}", other),
+    };
+
+    let auth = match (protocol.as_str(), mechanism.as_str()) {
+        ("PLAINTEXT", _) => AuthConfig::None,
+        ("SSL", _) => AuthConfig::Ssl {
+            ca_cert,
+            client_cert: props.remove("ssl.certificate.location"),
+            client_key: props.remove("ssl.key.location"),
+        },
+        (_, "PLAIN" | "SCRAM-SHA-256" | "SCRAM-SHA-512") => {
+            if username.is_none() || password.is_none() {
+                notes.push("no username/password found; fill them in after importing".to_string());
+            }
+            let username = username.unwrap_or_default();
+            let password = password.unwrap_or_default();
+            if !password.is_empty() && !secrets::is_reference(&password) {
+                notes.push("password will be stored in plaintext; consider a secret reference".to_string());
+            }
+            match (tls, mechanism.as_str()) {
+                (false, "PLAIN") => AuthConfig::SaslPlain { username, password },
+                (false, "SCRAM-SHA-256") => AuthConfig::SaslScram256 { username, password },
+                (false, _) => AuthConfig::SaslScram512 { username, password },
+                (true, "PLAIN") => AuthConfig::SaslSslPlain { username, password, ca_cert },
+                (true, "SCRAM-SHA-256") => AuthConfig::SaslSslScram256 { username, password, ca_cert },
+                (true, _) => AuthConfig::SaslSslScram512 { username, password, ca_cert },
+            }
+        }
+        (_, "OAUTHBEARER") => oauth_bearer(&mut props, &jaas, tls, ca_cert, &mut notes),
+        (_, other) => {
+            notes.push(format!("SASL mechanism {} is kept as properties only", other));
+            props.insert("security.protocol".to_string(), protocol.clone());
+            props.insert("sasl.mechanism".to_string(), other.to_string());
+            AuthConfig::None
+        }
+    };
+
+    props.retain(|key, _| {
+        let java_only = JAVA_ONLY.iter().any(|prefix| key.starts_with(prefix));
+        if java_only {
+            notes.push(format!("dropped Java-only setting {}", key));
+        }
+        !java_only
+    });
+
+    Ok((
+        ClusterConfig {
+            name: name.to_string(),
+            brokers,
+            auth,
+            properties: props,
+            environment: None,
+            read_only: false,
+        },
+        notes,
+    ))
+}
+
+fn oauth_bearer(
+    props: &mut BTreeMap<String, String>,
+    jaas: &HashMap<String, String>,
+    tls: bool,
+    ca_cert: Option<String>,
+    notes: &mut Vec<String>,
+) -> AuthConfig {
+    // Tokens are fetched by kafka-eye itself, so librdkafka's own OIDC
+    // settings must not be passed through
+    props.remove("sasl.oauthbearer.method");
+    let unsecure = props
+        .remove("enable.sasl.oauthbearer.unsecure.jwt")
+        .is_some_and(|v| v == "true");
+    let unsecured_principal = jaas.get("unsecuredLoginStringClaim_sub").cloned().or_else(|| {
+        let config = props.remove("sasl.oauthbearer.config")?;
+        let principal = config.split_whitespace().find_map(|kv| kv.strip_prefix("principal="))?.to_string();
+        unsecure.then_some(principal)
+    });
+    if let Some(principal) = unsecured_principal {
+        // The unsecured variant has no TLS settings of its own, so they go
+        // into the properties, which override the auth settings
+        if tls {
+            props.insert("security.protocol".to_string(), "SASL_SSL".to_string());
+            if let Some(ca) = ca_cert {
+                props.insert("ssl.ca.location".to_string(), ca);
+            }
+        }
+        return AuthConfig::SaslOAuthBearerUnsecured { principal };
+    }
+
+    let token_endpoint = props.remove("sasl.oauthbearer.token.endpoint.url").unwrap_or_default();
+    let client_id = props
+        .remove("sasl.oauthbearer.client.id")
+        .or_else(|| jaas.get("clientId").cloned())
+        .unwrap_or_default();
+    let client_secret = props
+        .remove("sasl.oauthbearer.client.secret")
+        .or_else(|| jaas.get("clientSecret").cloned())
+        .unwrap_or_default();
+    let scope = props.remove("sasl.oauthbearer.scope").or_else(|| jaas.get("scope").cloned());
+    if token_endpoint.is_empty() || client_id.is_empty() {
+        notes.push("no token endpoint/client id found; fill them in after importing".to_string());
+    }
+    if !client_secret.is_empty() && !secrets::is_reference(&client_secret) {
+        notes.push("client secret will be stored in plaintext; consider a secret reference".to_string());
+    }
+    if tls {
+        AuthConfig::SaslSslOAuthBearer {
+            token_endpoint,
+            client_id,
+            client_secret,
+            scope,
+            ca_cert,
+        }
+    } else {
+        AuthConfig::SaslOAuthBearer {
+            token_endpoint,
+            client_id,
+            client_secret,
+            scope,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import(contents: &str) -> (ClusterConfig, Vec<String>) {
+        to_cluster("test", parse(contents)).unwrap()
+    }
+
+    #[test]
+    fn properties_files_parse() {
+        let props = parse(
+            "# comment\n! also a comment\nbootstrap.servers = a:9092,b:9092\nclient.id: eye\n\
+             sasl.jaas.config=org.apache.kafka.common.security.scram.ScramLoginModule required \\\n    \
+             username=\"svc\" \\\n    password=\"p w\";\nempty=\n",
+        );
+        assert_eq!(props["bootstrap.servers"], "a:9092,b:9092");
+        assert_eq!(props["client.id"], "eye");
+        assert_eq!(props["empty"], "");
+        let jaas = parse_jaas(&props["sasl.jaas.config"]);
+        assert_eq!(jaas["username"], "svc");
+        assert_eq!(jaas["password"], "p w");
+        assert_eq!(props.len(), 4);
+    }
+
+    #[test]
+    fn jaas_options_quoted_and_bare() {
+        let jaas = parse_jaas(r#"x.OAuthBearerLoginModule required clientId=eye clientSecret="s;e c" scope=kafka;"#);
+        assert_eq!(jaas["clientId"], "eye");
+        assert_eq!(jaas["clientSecret"], "s;e c");
+        assert_eq!(jaas["scope"], "kafka");
+    }
+
+    #[test]
+    fn protocol_and_mechanism_map_onto_auth() {
+        let cases: [(&str, AuthConfig); 7] = [
+            ("", AuthConfig::None),
+            (
+                "security.protocol=SSL\nssl.ca.location=/ca.pem\nssl.certificate.location=/c.pem\nssl.key.location=/k.pem",
+                AuthConfig::Ssl {
+                    ca_cert: Some("/ca.pem".into()),
+                    client_cert: Some("/c.pem".into()),
+                    client_key: Some("/k.pem".into()),
+                },
+            ),
+            (
+                "security.protocol=SASL_PLAINTEXT\nsasl.mechanism=PLAIN\nsasl.username=u\nsasl.password=p",
+                AuthConfig::SaslPlain { username: "u".into(), password: "p".into() },
+            ),
+            (
+                "security.protocol=sasl_plaintext\nsasl.mechanism=SCRAM-SHA-256\nsasl.username=u\nsasl.password=p",
+                AuthConfig::SaslScram256 { username: "u".into(), password: "p".into() },
+            ),
+            (
+                "security.protocol=SASL_SSL\nsasl.mechanism=PLAIN\nsasl.username=u\nsasl.password=p",
+                AuthConfig::SaslSslPlain { username: "u".into(), password: "p".into(), ca_cert: None },
+            ),
+            (
+                "security.protocol=SASL_SSL\nsasl.mechanism=SCRAM-SHA-512\nsasl.username=u\nsasl.password=env:PW",
+                AuthConfig::SaslSslScram512 { username: "u".into(), password: "env:PW".into(), ca_cert: None },
+            ),
+            (
+                "security.protocol=SASL_PLAINTEXT\nsasl.mechanism=SCRAM-SHA-512\nsasl.username=u\nsasl.password=p",
+                AuthConfig::SaslScram512 { username: "u".into(), password: "p".into() },
+            ),
+        ];
+        for (settings, auth) in cases {
+            let (cluster, _) = import(&format!("bootstrap.servers=k:9092\n{}", settings));
+            assert_eq!(cluster.auth, auth, "{}", settings);
+            assert!(cluster.properties.is_empty(), "{}: {:?}", settings, cluster.properties);
+        }
+        assert!(to_cluster("test", parse("bootstrap.servers=k:9092\nsecurity.protocol=TLS")).is_err());
+        assert!(to_cluster("test", parse("security.protocol=SSL")).is_err());
+    }
+
+    #[test]
+    fn java_sasl_ssl_scram_file() {
+        let (cluster, notes) = import(
+            r#"bootstrap.servers=broker1:9093,broker2:9093
+security.protocol=SASL_SSL
+sasl.mechanism=SCRAM-SHA-512
+sasl.jaas.config=org.apache.kafka.common.security.scram.ScramLoginModule required \
+    username="svc-eye" \
+    password="hunter2";
+ssl.truststore.type=PEM
+ssl.truststore.location=/etc/kafka/ca.pem
+ssl.endpoint.identification.algorithm=https
+key.deserializer=org.apache.kafka.common.serialization.StringDeserializer
+"#,
+        );
+        assert_eq!(cluster.brokers, "broker1:9093,broker2:9093");
+        assert_eq!(
+            cluster.auth,
+            AuthConfig::SaslSslScram512 {
+                username: "svc-eye".into(),
+                password: "hunter2".into(),
+                ca_cert: Some("/etc/kafka/ca.pem".into()),
+            }
+        );
+        assert_eq!(cluster.properties.len(), 1);
+        assert_eq!(cluster.properties["ssl.endpoint.identification.algorithm"], "https");
+        assert!(notes.iter().any(|n| n.contains("plaintext")), "{:?}", notes);
+        assert!(notes.iter().any(|n| n == "dropped Java-only setting key.deserializer"), "{:?}", notes);
+        assert!(!notes.iter().any(|n| n.contains("truststore")), "{:?}", notes);
+    }
+
+    #[test]
+    fn java_keystores() {
+        let (cluster, notes) = import(
+            "bootstrap.servers=k:9093\nsecurity.protocol=SSL\n\
+             ssl.truststore.location=/ts.jks\nssl.truststore.password=x\n\
+             ssl.keystore.type=JKS\nssl.keystore.location=/ks.jks\nssl.keystore.password=x\n",
+        );
+        assert_eq!(cluster.auth, AuthConfig::Ssl { ca_cert: None, client_cert: None, client_key: None });
+        assert!(notes.iter().any(|n| n.contains("truststore /ts.jks is not PEM")), "{:?}", notes);
+        assert!(notes.iter().any(|n| n.contains("JKS keystore /ks.jks")), "{:?}", notes);
+        assert!(cluster.properties.is_empty(), "{:?}", cluster.properties);
+
+        let (cluster, _) = import(
+            "bootstrap.servers=k:9093\nsecurity.protocol=SSL\nssl.keystore.type=PEM\nssl.keystore.location=/client.pem\n",
+        );
+        assert_eq!(
+            cluster.auth,
+            AuthConfig::Ssl {
+                ca_cert: None,
+                client_cert: Some("/client.pem".into()),
+                client_key: Some("/client.pem".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn kcat_file() {
+        let (cluster, notes) = import(
+            "metadata.broker.list=kafka:9092\nsecurity.protocol=sasl_ssl\nsasl.mechanisms=PLAIN\n\
+             sasl.username=eye\nsasl.password=cmd:pass kafka\nssl.ca.location=/ca.pem\n\
+             fetch.max.bytes=1048576\n",
+        );
+        assert_eq!(cluster.brokers, "kafka:9092");
+        assert_eq!(
+            cluster.auth,
+            AuthConfig::SaslSslPlain {
+                username: "eye".into(),
+                password: "cmd:pass kafka".into(),
+                ca_cert: Some("/ca.pem".into()),
+            }
+        );
+        assert_eq!(cluster.properties.len(), 1);
+        assert_eq!(cluster.properties["fetch.max.bytes"], "1048576");
+        assert!(notes.is_empty(), "{:?}", notes);
+    }
+
+    #[test]
+    fn oauthbearer_files() {
+        let (cluster, notes) = import(
+            r#"bootstrap.servers=k:9093
+security.protocol=SASL_SSL
+sasl.mechanism=OAUTHBEARER
+sasl.oauthbearer.token.endpoint.url=https://idp/token
+sasl.login.callback.handler.class=org.apache.kafka.common.security.oauthbearer.OAuthBearerLoginCallbackHandler
+sasl.jaas.config=org.apache.kafka.common.security.oauthbearer.OAuthBearerLoginModule required     clientId="eye" clientSecret="file:~/.eye-secret" scope="kafka";
+ssl.ca.location=/ca.pem
+"#,
+        );
+        assert_eq!(
+            cluster.auth,
+            AuthConfig::SaslSslOAuthBearer {
+                token_endpoint: "https://idp/token".into(),
+                client_id: "eye".into(),
+                client_secret: "file:~/.eye-secret".into(),
+                scope: Some("kafka".into()),
+                ca_cert: Some("/ca.pem".into()),
+            }
+        );
+        assert!(cluster.properties.is_empty(), "{:?}", cluster.properties);
+        assert_eq!(notes, ["dropped Java-only setting sasl.login.callback.handler.class"]);
+
+        // Unsecured tokens over TLS keep the protocol and CA as properties
+        let (cluster, _) = import(
+            "bootstrap.servers=k:9093\nsecurity.protocol=SASL_SSL\nsasl.mechanism=OAUTHBEARER\n\
+             ssl.ca.location=/ca.pem\nenable.sasl.oauthbearer.unsecure.jwt=true\n\
+             sasl.oauthbearer.config=principal=admin\n",
+        );
+        assert_eq!(cluster.auth, AuthConfig::SaslOAuthBearerUnsecured { principal: "admin".into() });
+        assert_eq!(cluster.properties["security.protocol"], "SASL_SSL");
+        assert_eq!(cluster.properties["ssl.ca.location"], "/ca.pem");
+    }
+}
@@ -0,0 +1,163 @@
+//! Key distribution and partition skew of a topic. The newest records of
+//! every partition are sampled for key statistics and record sizes; the
+//! skew score uses the watermarks, so it covers the whole topic.
+
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+
+use rdkafka::consumer::{BaseConsumer, ConsumerContext};
+
+use crate::kafka::{page, search};
+
+/// Keys listed in the report, most frequent first
+pub const TOP_KEYS: usize = 20;
+
+/// Records sampled per partition unless changed in the view
+pub const DEFAULT_SAMPLE: usize = 1_000;
+
+/// Samples are held in memory, so they are kept to this per partition
+pub const MAX_SAMPLE: usize = 64_000;
+
+/// Records the read deadline allows per second, on top of the page read deadline
+const RECORDS_PER_SECOND: usize = 5_000;
+
+#[derive(Debug, Clone)]
+pub struct PartitionAnalysis {
+    pub partition: i32,
+    /// Offsets between the watermarks; includes compacted-away ones
+    pub records: i64,
+    /// Newest offsets asked for, fewer when the partition is smaller
+    pub requested: usize,
+    pub sampled: usize,
+    /// The read deadline passed before the requested offsets were read
+    pub truncated: bool,
+    /// Key, value and headers of the sampled records
+    pub sampled_bytes: u64,
+    pub null_keys: usize,
+    pub distinct_keys: usize,
+}
+
+impl PartitionAnalysis {
+    pub fn average_size(&self) -> Option<u64> {
+        (self.sampled > 0).then(|| self.sampled_bytes / self.sampled as u64)
+    }
+
+    /// Average sampled record size times the record count
+    pub fn estimated_bytes(&self) -> Option<u64> {
+        self.average_size().map(|avg| avg * self.records.max(0) as u64)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyCount {
+    pub key: String,
+    pub count: usize,
+    /// More than one means the key isn't partitioned by key alone
+    pub partitions: Vec<i32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    pub partitions: Vec<PartitionAnalysis>,
+    pub sampled: usize,
+    pub null_keys: usize,
+    pub distinct_keys: usize,
+    pub top_keys: Vec<KeyCount>,
+    /// Records in the largest partition over the mean; 1.0 is perfectly even
+    pub skew: f64,
+}
+
+impl Analysis {
+    pub fn null_key_ratio(&self) -> f64 {
+        if self.sampled == 0 {
+            0.0
+        } else {
+            self.null_keys as f64 / self.sampled as f64
+        }
+    }
+
+    pub fn total_records(&self) -> i64 {
+        self.partitions.iter().map(|p| p.records).sum()
+    }
+
+    /// Partitions whose sample was cut short by the read deadline
+    pub fn truncated(&self) -> usize {
+        self.partitions.iter().filter(|p| p.truncated).count()
+    }
+}
+
+/// Samples up to `sample_size` of the newest records of every partition.
+pub fn run<C: ConsumerContext>(consumer: &BaseConsumer<C>, topic: &str, sample_size: usize) -> Result<Analysis, String> {
+    let (partitions, watermarks) = search::partitions_with_watermarks(consumer, topic)?;
+    let ranges: Vec<(i64, i64)> = watermarks
+        .iter()
+        .map(|&(low, high)| ((high - sample_size as i64).max(low), high))
+        .collect();
+    let requested: usize = ranges.iter().map(|&(start, end)| (end - start).max(0) as usize).sum();
+    let deadline = page::READ_DEADLINE + Duration::from_secs((requested / RECORDS_PER_SECOND) as u64);
+    let read = page::read_ranges(consumer, topic, &partitions, &ranges, deadline)?;
+    let records = read.records;
+
+    let mut keys: HashMap<&str, (usize, BTreeSet<i32>)> = HashMap::new();
+    let mut per_partition: Vec<PartitionAnalysis> = partitions
+        .iter()
+        .zip(watermarks.iter().zip(&ranges))
+        .map(|(&partition, (&(low, high), &(start, end)))| PartitionAnalysis {
+            partition,
+            records: high - low,
+            requested: (end - start).max(0) as usize,
+            sampled: 0,
+            truncated: read.unfinished.contains(&partition),
+            sampled_bytes: 0,
+            null_keys: 0,
+            distinct_keys: 0,
+        })
+        .collect();
+    let mut partition_keys: HashMap<i32, BTreeSet<&str>> = HashMap::new();
+    for record in &records {
+        let Some(stats) = per_partition.iter_mut().find(|p| p.partition == record.partition) else { continue };
+        stats.sampled += 1;
+        stats.sampled_bytes += record.total_size() as u64;
+        match record.key.as_deref() {
+            Some(key) => {
+                let entry = keys.entry(key).or_default();
+                entry.0 += 1;
+                entry.1.insert(record.partition);
+                partition_keys.entry(record.partition).or_default().insert(key);
+            }
+            None => stats.null_keys += 1,
+        }
+    }
+    for stats in &mut per_partition {
+        stats.distinct_keys = partition_keys.get(&stats.partition).map_or(0, |k| k.len());
+    }
+
+    let distinct_keys = keys.len();
+    let mut top_keys: Vec<KeyCount> = keys
+        .into_iter()
+        .map(|(key, (count, partitions))| KeyCount {
+            key: key.to_string(),
+            count,
+            partitions: partitions.into_iter().collect(),
+        })
+        .collect();
+    top_keys.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+    top_keys.truncate(TOP_KEYS);
+
+    let sizes: Vec<i64> = per_partition.iter().map(|p| p.records.max(0)).collect();
+    let mean = sizes.iter().sum::<i64>() as f64 / sizes.len().max(1) as f64;
+    let skew = if mean > 0.0 {
+        sizes.iter().copied().max().unwrap_or(0) as f64 / mean
+    } else {
+        1.0
+    };
+
+    Ok(Analysis {
+        sampled: records.len(),
+        null_keys: per_partition.iter().map(|p| p.null_keys).sum(),
+        distinct_keys,
+        partitions: per_partition,
+        top_keys,
+        skew,
+    })
+}
@@ -1,26 +1,58 @@
 #![allow(dead_code)]
 
 use crate::app::*;
+use crate::audit::AuditEntry;
 use crate::config::{AuthConfig, ClusterConfig};
-use anyhow::Result;
-use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, TopicReplication};
-use rdkafka::client::DefaultClientContext;
+use crate::filter::Filter;
+use crate::kafka::compacted::{self, TableSummary};
+use crate::kafka::context::{EyeContext, HealthMonitor};
+use crate::kafka::native;
+use crate::kafka::oauth::TokenSource;
+use crate::kafka::page::{self, Page, PageAnchor};
+use crate::kafka::reassign::{self, MoveProgress, ReassignmentGoal, ReassignmentPlan};
+use crate::kafka::search::{self, PartitionScan, SearchRange};
+use crate::kafka::analysis::{self, Analysis};
+use crate::kafka::timeline::{self, BucketSize, Timeline};
+use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, ResourceSpecifier, TopicReplication};
+use rdkafka::client::{Client, DefaultClientContext};
 use rdkafka::config::ClientConfig;
 use rdkafka::consumer::{BaseConsumer, Consumer};
-use rdkafka::metadata::Metadata;
-use rdkafka::message::Headers;
+use rdkafka::metadata::{Metadata, MetadataTopic};
+use rdkafka::message::{BorrowedMessage, Headers};
 use rdkafka::producer::{FutureProducer, FutureRecord};
-use rdkafka::TopicPartitionList;
+use rdkafka::{ClientContext, TopicPartitionList};
 use std::collections::HashMap;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
+/// How often the backend looks at client health while connected
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+/// A client error keeps the connection marked degraded this long
+const DEGRADED_WINDOW: Duration = Duration::from_secs(30);
+const MAX_RECONNECT_BACKOFF_SECS: u64 = 60;
+/// How often librdkafka reports statistics to the client context
+const STATISTICS_INTERVAL_MS: &str = "5000";
+/// How often the browser consumer's positions are sent while consuming
+const POSITION_REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+type EyeAdmin = AdminClient<EyeContext>;
+type EyeConsumer = BaseConsumer<EyeContext>;
+type EyeProducer = FutureProducer<EyeContext>;
+
 /// Commands sent from UI to Kafka backend
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum KafkaCommand {
-    Connect(ClusterConfig),
+    Connect {
+        cluster: ClusterConfig,
+        /// Typed at the prompt; used as is instead of the cluster's secret
+        password: Option<String>,
+    },
     Disconnect,
     FetchMetadata,
+    FetchBrokers,
+    FetchBrokerConfig(i32),
     FetchTopics,
     FetchTopicDetail(String),
     CreateTopic {
@@ -30,20 +62,125 @@ pub enum KafkaCommand {
         config: HashMap<String, String>,
     },
     DeleteTopic(String),
+    ElectPreferredLeaders {
+        topic: String,
+        partitions: Vec<i32>,
+    },
+    PlanReassignment(ReassignmentGoal),
+    SubmitReassignment(ReassignmentPlan),
+    TrackReassignment(ReassignmentPlan),
+    FetchAcls,
+    CreateAcl(AclBinding),
+    DeleteAcl(AclBinding),
     StartConsuming {
         topic: String,
         offset_mode: OffsetMode,
     },
     StopConsuming,
+    /// Scan a range of a topic with a dedicated consumer, see `kafka::search`
+    StartSearch {
+        /// Echoed in the responses, so late results of a replaced search can be told apart
+        id: u64,
+        topic: String,
+        filter: Filter,
+        range: SearchRange,
+    },
+    CancelSearch,
+    /// Latest record per key of a compacted topic, see `kafka::compacted`
+    StartTable {
+        /// Echoed in the responses like the search id
+        id: u64,
+        topic: String,
+    },
+    CancelTable,
+    /// One page of records for the paged browser, see `kafka::page`
+    FetchPage {
+        topic: String,
+        anchor: PageAnchor,
+    },
+    /// Message counts per bucket between two times, see `kafka::timeline`
+    FetchTimeline {
+        topic: String,
+        from: i64,
+        to: i64,
+        bucket: BucketSize,
+    },
+    /// Key distribution and partition skew from the newest `sample` records
+    /// of every partition, see `kafka::analysis`
+    AnalyzeTopic {
+        topic: String,
+        sample: usize,
+    },
+    /// One record and up to `context` records on either side of it
+    FetchRecord {
+        topic: String,
+        partition: i32,
+        offset: i64,
+        context: i64,
+    },
     FetchConsumerGroups,
     FetchConsumerGroupDetail(String),
+    /// `None` sends a null key, value (a tombstone) or header value
     ProduceMessage {
         topic: String,
         key: Option<String>,
-        value: String,
-        headers: Vec<(String, String)>,
+        value: Option<String>,
+        headers: Vec<(String, Option<String>)>,
+    },
+    TestConnection {
+        cluster: ClusterConfig,
+        password: Option<String>,
     },
-    TestConnection(ClusterConfig),
+}
+
+impl KafkaCommand {
+    /// Describes the change for commands that modify the cluster. These are
+    /// rejected on read-only clusters and need typed confirmation on prod.
+    /// Offset resets and config changes belong here once they exist.
+    pub fn mutation(&self) -> Option<String> {
+        match self {
+            KafkaCommand::CreateTopic { name, .. } => Some(format!("create topic '{}'", name)),
+            KafkaCommand::DeleteTopic(name) => Some(format!("delete topic '{}'", name)),
+            KafkaCommand::ElectPreferredLeaders { topic, .. } => Some(format!("elect preferred leaders for '{}'", topic)),
+            KafkaCommand::SubmitReassignment(_) => Some("submit a partition reassignment".to_string()),
+            KafkaCommand::CreateAcl(binding) => Some(format!("create ACL {}", binding.describe())),
+            KafkaCommand::DeleteAcl(binding) => Some(format!("delete ACL {}", binding.describe())),
+            KafkaCommand::ProduceMessage { topic, .. } => Some(format!("produce to '{}'", topic)),
+            _ => None,
+        }
+    }
+
+    /// Tells the UI the command failed as a whole. An election answers with
+    /// its own result, so only its dialog stops waiting.
+    fn failure(&self, message: String) -> KafkaResponse {
+        match self {
+            KafkaCommand::ElectPreferredLeaders { topic, partitions } => election_failed(topic, partitions, &message),
+            _ => KafkaResponse::Error(message),
+        }
+    }
+
+    /// Commands served by the connection's admin, consumer or producer,
+    /// which are gone while disconnected or reconnecting. Scans open their
+    /// own consumer from the cluster, so they are answered in their handler
+    /// when there is none.
+    fn needs_client(&self) -> bool {
+        !matches!(
+            self,
+            KafkaCommand::Connect { .. }
+                | KafkaCommand::Disconnect
+                | KafkaCommand::TestConnection { .. }
+                | KafkaCommand::StopConsuming
+                | KafkaCommand::StartSearch { .. }
+                | KafkaCommand::CancelSearch
+                | KafkaCommand::StartTable { .. }
+                | KafkaCommand::CancelTable
+                | KafkaCommand::FetchPage { .. }
+                | KafkaCommand::FetchTimeline { .. }
+                | KafkaCommand::AnalyzeTopic { .. }
+                | KafkaCommand::FetchRecord { .. }
+                | KafkaCommand::SubmitReassignment(_)
+        )
+    }
 }
 
 /// Responses from Kafka backend to UI
@@ -52,13 +189,21 @@ pub enum KafkaResponse {
     Connected(String),
     Disconnected,
     ConnectionFailed(String),
+    ConnectionStatus(ConnectionStatus),
+    ClientStats(ClientStats),
     MetadataUpdate {
+        cluster_id: Option<String>,
         controller_id: Option<i32>,
         broker_count: usize,
         brokers_online: Vec<i32>,
         topic_count: usize,
         partition_count: usize,
     },
+    BrokerList(Vec<BrokerInfo>),
+    BrokerConfig {
+        broker_id: i32,
+        entries: Vec<ConfigEntryInfo>,
+    },
     TopicList(Vec<TopicInfo>),
     TopicDetail {
         name: String,
@@ -66,7 +211,73 @@ pub enum KafkaResponse {
     },
     TopicCreated(String),
     TopicDeleted(String),
+    LeaderElectionResult {
+        topic: String,
+        results: Vec<ElectionResult>,
+    },
+    ReassignmentPlanned(ReassignmentPlan),
+    ReassignmentSubmitted(String),
+    ReassignmentProgress(Vec<MoveProgress>),
+    AclList(Vec<AclBinding>),
+    AclCreated(AclBinding),
+    AclDeleted {
+        binding: AclBinding,
+        count: usize,
+    },
     Messages(Vec<KafkaMessage>),
+    /// Positions of the browser consumer, about once a second while consuming
+    ConsumerPositions(Vec<PartitionPosition>),
+    Record {
+        topic: String,
+        partition: i32,
+        offset: i64,
+        records: Vec<KafkaMessage>,
+    },
+    Page {
+        topic: String,
+        page: Page,
+    },
+    Timeline {
+        topic: String,
+        timeline: Result<Timeline, String>,
+    },
+    Analysis {
+        topic: String,
+        sample: usize,
+        analysis: Result<Analysis, String>,
+    },
+    SearchProgress {
+        id: u64,
+        scans: Vec<PartitionScan>,
+    },
+    SearchMatches {
+        id: u64,
+        matches: Vec<KafkaMessage>,
+    },
+    SearchFinished {
+        id: u64,
+        scanned: u64,
+        matched: usize,
+        /// Why the search ended before the end of the range
+        stopped: Option<String>,
+    },
+    TableProgress {
+        id: u64,
+        scans: Vec<PartitionScan>,
+        /// Live keys so far
+        keys: usize,
+    },
+    TableFinished {
+        id: u64,
+        /// Sorted by key
+        rows: Vec<KafkaMessage>,
+        summary: TableSummary,
+    },
+    /// `cleanup.policy` of the topic a table is read from
+    TableCleanupPolicy {
+        id: u64,
+        policy: String,
+    },
     ConsumerGroupList(Vec<ConsumerGroupInfo>),
     ConsumerGroupDetail(ConsumerGroupInfo),
     MessageProduced {
@@ -82,11 +293,41 @@ pub enum KafkaResponse {
     },
 }
 
-fn build_client_config(cluster: &ClusterConfig) -> ClientConfig {
+/// Every partition of an election that failed before any of them ran
+fn election_failed(topic: &str, partitions: &[i32], message: &str) -> KafkaResponse {
+    KafkaResponse::LeaderElectionResult {
+        topic: topic.to_string(),
+        results: partitions
+            .iter()
+            .map(|&partition| ElectionResult {
+                topic: topic.to_string(),
+                partition,
+                outcome: Err(message.to_string()),
+            })
+            .collect(),
+    }
+}
+
+/// Answer to commands sent while there is no cluster
+const NOT_CONNECTED: &str = "not connected";
+
+/// Prefixes that scope a cluster property to a single client
+const ROLE_PREFIXES: [&str; 3] = ["admin.", "consumer.", "producer."];
+
+fn role_prefix(role: ClientRole) -> &'static str {
+    match role {
+        ClientRole::Admin => ROLE_PREFIXES[0],
+        ClientRole::Consumer => ROLE_PREFIXES[1],
+        ClientRole::Producer => ROLE_PREFIXES[2],
+    }
+}
+
+fn build_client_config(cluster: &ClusterConfig, role: ClientRole) -> ClientConfig {
     let mut config = ClientConfig::new();
     config.set("bootstrap.servers", &cluster.brokers);
     config.set("socket.timeout.ms", "10000");
     config.set("session.timeout.ms", "10000");
+    config.set("statistics.interval.ms", STATISTICS_INTERVAL_MS);
 
     match &cluster.auth {
         AuthConfig::None => {}
@@ -151,12 +392,351 @@ fn build_client_config(cluster: &ClusterConfig) -> ClientConfig {
                 config.set("ssl.ca.location", ca);
             }
         }
+        // Tokens come from EyeContext::generate_oauth_token, see kafka::oauth
+        AuthConfig::SaslOAuthBearer { .. } | AuthConfig::SaslOAuthBearerUnsecured { .. } => {
+            config.set("security.protocol", "SASL_PLAINTEXT");
+            config.set("sasl.mechanism", "OAUTHBEARER");
+        }
+        AuthConfig::SaslSslOAuthBearer { ca_cert, .. } => {
+            config.set("security.protocol", "SASL_SSL");
+            config.set("sasl.mechanism", "OAUTHBEARER");
+            if let Some(ca) = ca_cert {
+                config.set("ssl.ca.location", ca);
+            }
+        }
+    }
+
+    // User properties go last so they can override anything above
+    for (key, value) in &cluster.properties {
+        if !ROLE_PREFIXES.iter().any(|prefix| key.starts_with(prefix)) {
+            config.set(key, value);
+        }
+    }
+    for (key, value) in &cluster.properties {
+        if let Some(key) = key.strip_prefix(role_prefix(role)) {
+            config.set(key, value);
+        }
     }
 
     config
 }
 
-fn extract_metadata(metadata: &Metadata) -> (KafkaResponse, Vec<TopicInfo>) {
+/// Hands the current OAUTHBEARER token straight to a client. The admin
+/// client never serves its main queue, and the consumer only does while
+/// consuming, so neither would see librdkafka's refresh request in time.
+fn push_token<C: ClientContext>(client: &Client<C>, tokens: &TokenSource) -> Result<(), String> {
+    let token = tokens.token().map_err(|e| format!("OAuth token request failed: {}", e))?;
+    native::set_oauth_token(client, &token).map_err(|e| format!("Setting OAuth token failed: {}", e))
+}
+
+/// Creates the admin client, browser consumer and producer for a cluster,
+/// all reporting into `health` and sending their statistics to `resp_tx`.
+/// Fails unless the brokers answer a metadata request.
+fn connect(
+    cluster: &ClusterConfig,
+    health: &HealthMonitor,
+    resp_tx: &mpsc::UnboundedSender<KafkaResponse>,
+    tokens: Option<&Arc<TokenSource>>,
+) -> Result<(EyeAdmin, EyeConsumer, EyeProducer), String> {
+    let context = |role| EyeContext::new(role, health.clone(), resp_tx.clone(), tokens.cloned());
+    let adm: EyeAdmin = build_client_config(cluster, ClientRole::Admin)
+        .create_with_context(context(ClientRole::Admin))
+        .map_err(|e| format!("Config error: {}", e))?;
+    if let Some(tokens) = tokens {
+        push_token(adm.inner(), tokens)?;
+    }
+    adm.inner()
+        .fetch_metadata(None, Duration::from_secs(10))
+        .map_err(|e| format!("Cannot reach brokers: {}", e))?;
+
+    let cons: Result<EyeConsumer, _> = build_client_config(cluster, ClientRole::Consumer)
+        .set("group.id", "kafka-eye-browser")
+        .set("enable.auto.commit", "false")
+        .set("auto.offset.reset", "latest")
+        .create_with_context(context(ClientRole::Consumer));
+    let prod: Result<EyeProducer, _> =
+        build_client_config(cluster, ClientRole::Producer).create_with_context(context(ClientRole::Producer));
+
+    match (cons, prod) {
+        (Ok(c), Ok(p)) => {
+            if let Some(tokens) = tokens {
+                push_token(c.client(), tokens)?;
+            }
+            Ok((adm, c, p))
+        }
+        (Err(e), _) | (_, Err(e)) => Err(format!("Failed to create client: {}", e)),
+    }
+}
+
+pub fn kafka_message(msg: &BorrowedMessage) -> KafkaMessage {
+    use rdkafka::Message;
+    let key = msg.key().map(|k| String::from_utf8_lossy(k).to_string());
+    let value = msg.payload().map(|v| String::from_utf8_lossy(v).to_string());
+    let (timestamp, timestamp_type) = match msg.timestamp() {
+        rdkafka::Timestamp::CreateTime(ts) => (Some(ts), Some(TimestampType::CreateTime)),
+        rdkafka::Timestamp::LogAppendTime(ts) => (Some(ts), Some(TimestampType::LogAppendTime)),
+        _ => (None, None),
+    };
+    let mut headers_size = 0;
+    let headers = if let Some(hdrs) = msg.headers() {
+        (0..hdrs.count())
+            .filter_map(|i| {
+                hdrs.get_as::<[u8]>(i).ok().map(|h| {
+                    headers_size += h.key.len() + h.value.map_or(0, |v| v.len());
+                    (
+                        h.key.to_string(),
+                        h.value.map(|v| String::from_utf8_lossy(v).to_string()),
+                    )
+                })
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    KafkaMessage {
+        partition: msg.partition(),
+        offset: msg.offset(),
+        key,
+        value,
+        timestamp,
+        timestamp_type,
+        headers,
+        key_size: msg.key_len(),
+        value_size: msg.payload_len(),
+        headers_size,
+        leader_epoch: native::leader_epoch(msg),
+    }
+}
+
+/// A consumer for one-off reads, separate from the browser consumer.
+/// It has its own group and no statistics, so it stays out of Client Stats.
+fn scan_consumer(
+    cluster: &ClusterConfig,
+    tokens: Option<&Arc<TokenSource>>,
+    resp_tx: &mpsc::UnboundedSender<KafkaResponse>,
+) -> Result<EyeConsumer, String> {
+    let context = EyeContext::new(ClientRole::Consumer, HealthMonitor::default(), resp_tx.clone(), tokens.cloned());
+    let cons: EyeConsumer = build_client_config(cluster, ClientRole::Consumer)
+        .set("group.id", "kafka-eye-search")
+        .set("enable.auto.commit", "false")
+        .set("enable.partition.eof", "true")
+        .set("statistics.interval.ms", "0")
+        .create_with_context(context)
+        .map_err(|e| format!("Failed to create client: {}", e))?;
+    if let Some(tokens) = tokens {
+        push_token(cons.client(), tokens)?;
+    }
+    Ok(cons)
+}
+
+/// Starts a search on its own consumer and thread. Setting the returned
+/// flag cancels it.
+fn start_search(
+    cluster: &ClusterConfig,
+    tokens: Option<&Arc<TokenSource>>,
+    id: u64,
+    topic: String,
+    filter: Filter,
+    range: SearchRange,
+    resp_tx: &mpsc::UnboundedSender<KafkaResponse>,
+) -> Result<Arc<AtomicBool>, String> {
+    let cons = scan_consumer(cluster, tokens, resp_tx)?;
+    let cancel = Arc::new(AtomicBool::new(false));
+    let flag = cancel.clone();
+    let resp_tx = resp_tx.clone();
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = search::run(&cons, id, &topic, &filter, &range, &flag, &resp_tx) {
+            let _ = resp_tx.send(KafkaResponse::SearchFinished {
+                id,
+                scanned: 0,
+                matched: 0,
+                stopped: Some(e),
+            });
+        }
+    });
+    Ok(cancel)
+}
+
+/// Builds the table of a compacted topic on its own consumer and thread.
+/// Setting the returned flag cancels it.
+fn start_table(
+    cluster: &ClusterConfig,
+    tokens: Option<&Arc<TokenSource>>,
+    id: u64,
+    topic: String,
+    resp_tx: &mpsc::UnboundedSender<KafkaResponse>,
+) -> Result<Arc<AtomicBool>, String> {
+    let cons = scan_consumer(cluster, tokens, resp_tx)?;
+    let cancel = Arc::new(AtomicBool::new(false));
+    let flag = cancel.clone();
+    let resp_tx = resp_tx.clone();
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = compacted::run(&cons, id, &topic, &flag, &resp_tx) {
+            let _ = resp_tx.send(KafkaResponse::TableFinished {
+                id,
+                rows: Vec::new(),
+                summary: TableSummary {
+                    stopped: Some(e),
+                    ..Default::default()
+                },
+            });
+        }
+    });
+    Ok(cancel)
+}
+
+/// Next offset and cached high watermark of every assigned partition
+fn consumer_positions(cons: &EyeConsumer, topic: &str, positions: &HashMap<i32, i64>) -> Vec<PartitionPosition> {
+    let assignment = cons.assignment().unwrap_or_default();
+    assignment
+        .elements_for_topic(topic)
+        .iter()
+        .map(|e| PartitionPosition {
+            partition: e.partition(),
+            position: positions.get(&e.partition()).copied(),
+            high: native::cached_watermarks(cons.client(), topic, e.partition()).map(|(_, high)| high),
+        })
+        .collect()
+}
+
+/// Assigns every partition of `topic` to the browser consumer. Partitions
+/// found in `resume_at` continue from that offset; the rest start at
+/// `offset_mode`.
+fn assign_partitions(
+    cons: &EyeConsumer,
+    topic: &str,
+    offset_mode: &OffsetMode,
+    resume_at: &HashMap<i32, i64>,
+) -> Result<(), String> {
+    let meta = cons
+        .fetch_metadata(Some(topic), Duration::from_secs(10))
+        .map_err(|e| format!("Failed to fetch metadata: {}", e))?;
+    let topic_meta = meta.topics().first().ok_or_else(|| format!("Topic '{}' not found", topic))?;
+    let mut tpl = TopicPartitionList::new();
+    for p in topic_meta.partitions() {
+        let offset = match (resume_at.get(&p.id()), offset_mode) {
+            (Some(next), _) => rdkafka::Offset::Offset(*next),
+            (None, OffsetMode::Earliest) => rdkafka::Offset::Beginning,
+            (None, OffsetMode::Latest) => rdkafka::Offset::End,
+            (None, OffsetMode::Specific(o)) => rdkafka::Offset::Offset(*o),
+            (None, OffsetMode::Timestamp(_ts)) => rdkafka::Offset::End,
+        };
+        tpl.add_partition_offset(topic, p.id(), offset).ok();
+    }
+    cons.assign(&tpl).map_err(|e| format!("Failed to assign partitions: {}", e))
+}
+
+fn reconnect_backoff(attempt: u32) -> u64 {
+    2u64.saturating_pow(attempt.saturating_sub(1)).min(MAX_RECONNECT_BACKOFF_SECS)
+}
+
+/// Asks librdkafka for the current controller. Returns None while it is unknown.
+fn fetch_controller_id<C: ClientContext>(client: &Client<C>) -> Option<i32> {
+    let id = unsafe { rdkafka::bindings::rd_kafka_controllerid(client.native_ptr(), 10_000) };
+    if id >= 0 {
+        Some(id)
+    } else {
+        None
+    }
+}
+
+fn extract_brokers(metadata: &Metadata, controller_id: Option<i32>) -> Vec<BrokerInfo> {
+    let mut leader_counts: HashMap<i32, usize> = HashMap::new();
+    let mut replica_counts: HashMap<i32, usize> = HashMap::new();
+    for topic in metadata.topics() {
+        for p in topic.partitions() {
+            *leader_counts.entry(p.leader()).or_default() += 1;
+            for r in p.replicas() {
+                *replica_counts.entry(*r).or_default() += 1;
+            }
+        }
+    }
+
+    let mut brokers: Vec<BrokerInfo> = metadata
+        .brokers()
+        .iter()
+        .map(|b| BrokerInfo {
+            id: b.id(),
+            host: b.host().to_string(),
+            port: b.port(),
+            is_controller: controller_id == Some(b.id()),
+            leader_count: leader_counts.get(&b.id()).copied().unwrap_or(0),
+            replica_count: replica_counts.get(&b.id()).copied().unwrap_or(0),
+        })
+        .collect();
+    brokers.sort_by_key(|b| b.id);
+    brokers
+}
+
+fn extract_partitions(topic: &MetadataTopic) -> Vec<PartitionInfo> {
+    topic
+        .partitions()
+        .iter()
+        .map(|p| PartitionInfo {
+            id: p.id(),
+            leader: p.leader(),
+            replicas: p.replicas().to_vec(),
+            isr: p.isr().to_vec(),
+        })
+        .collect()
+}
+
+fn extract_topic(topic: &MetadataTopic) -> TopicInfo {
+    let partitions = extract_partitions(topic);
+    let replication_factor = partitions.first().map(|p| p.replicas.len()).unwrap_or(0);
+    TopicInfo {
+        name: topic.name().to_string(),
+        partitions: partitions.len(),
+        replication_factor,
+        partition_details: partitions,
+    }
+}
+
+fn reassignment_progress(metadata: &Metadata, plan: &ReassignmentPlan) -> Vec<MoveProgress> {
+    plan.moves
+        .iter()
+        .filter_map(|m| {
+            let topic = metadata.topics().iter().find(|t| t.name() == m.topic)?;
+            let partition = extract_partitions(topic).into_iter().find(|p| p.id == m.partition)?;
+            Some(MoveProgress {
+                topic: m.topic.clone(),
+                partition: m.partition,
+                status: reassign::move_status(m, &partition),
+            })
+        })
+        .collect()
+}
+
+/// Writes the outcome of a mutating command to the audit log, once.
+fn record_audit(
+    audit: &mut Option<AuditEntry>,
+    result: &str,
+    message: impl Into<String>,
+    resp_tx: &mpsc::UnboundedSender<KafkaResponse>,
+) {
+    if let Some(entry) = audit.take() {
+        if let Err(e) = entry.finish(result, message) {
+            let _ = resp_tx.send(KafkaResponse::Error(format!("Audit log write failed: {:#}", e)));
+        }
+    }
+}
+
+fn send_acl_list<C: ClientContext>(client: &Client<C>, resp_tx: &mpsc::UnboundedSender<KafkaResponse>) {
+    match native::describe_acls(client, Duration::from_secs(10)) {
+        Ok(bindings) => {
+            let _ = resp_tx.send(KafkaResponse::AclList(bindings));
+        }
+        Err(e) => {
+            let _ = resp_tx.send(KafkaResponse::Error(format!("Fetch ACLs failed: {}", e)));
+        }
+    }
+}
+
+fn extract_metadata(
+    metadata: &Metadata,
+    cluster_id: Option<String>,
+    controller_id: Option<i32>,
+) -> (KafkaResponse, Vec<TopicInfo>) {
     let brokers_online: Vec<i32> = metadata.brokers().iter().map(|b| b.id()).collect();
     let broker_count = brokers_online.len();
 
@@ -169,35 +749,15 @@ fn extract_metadata(metadata: &Metadata) -> (KafkaResponse, Vec<TopicInfo>) {
             continue;
         }
         topic_count += 1;
-        let partitions: Vec<PartitionInfo> = topic
-            .partitions()
-            .iter()
-            .map(|p| {
-                partition_count += 1;
-                PartitionInfo {
-                    id: p.id(),
-                    leader: p.leader(),
-                    replicas: p.replicas().to_vec(),
-                    isr: p.isr().to_vec(),
-                }
-            })
-            .collect();
-
-        let replication_factor = partitions.first().map(|p| p.replicas.len()).unwrap_or(0);
-
-        topics.push(TopicInfo {
-            name: topic.name().to_string(),
-            partitions: partitions.len(),
-            replication_factor,
-            partition_details: partitions,
-        });
+        let info = extract_topic(topic);
+        partition_count += info.partitions;
+        topics.push(info);
     }
 
     topics.sort_by(|a, b| a.name.cmp(&b.name));
 
-    let controller_id = metadata.brokers().iter().find(|b| b.id() >= 0).map(|b| b.id());
-
     let resp = KafkaResponse::MetadataUpdate {
+        cluster_id,
         controller_id,
         broker_count,
         brokers_online,
@@ -214,61 +774,135 @@ pub fn spawn_kafka_backend() -> (mpsc::UnboundedSender<KafkaCommand>, mpsc::Unbo
     let (resp_tx, resp_rx) = mpsc::unbounded_channel::<KafkaResponse>();
 
     tokio::spawn(async move {
-        let mut admin: Option<AdminClient<DefaultClientContext>> = None;
-        let mut consumer: Option<BaseConsumer> = None;
-        let mut producer: Option<FutureProducer> = None;
-        let mut _current_config: Option<ClusterConfig> = None;
+        // Shared with the tasks that finish slow admin requests off the loop
+        let mut admin: Option<Arc<EyeAdmin>> = None;
+        let mut consumer: Option<EyeConsumer> = None;
+        let mut producer: Option<EyeProducer> = None;
+        let mut current_config: Option<ClusterConfig> = None;
         let mut consuming = false;
-        let mut _consume_topic: Option<String> = None;
+        let mut consume_from: Option<(String, OffsetMode)> = None;
+        // Next offset to read per partition, so consumption survives a reconnect
+        let mut positions: HashMap<i32, i64> = HashMap::new();
+        let mut last_position_report = Instant::now();
+        let mut search_cancel: Option<Arc<AtomicBool>> = None;
+        let mut table_cancel: Option<Arc<AtomicBool>> = None;
+
+        let mut health = HealthMonitor::default();
+        let mut tokens: Option<Arc<TokenSource>> = None;
+        let mut pushed_token_expiry: i64 = 0;
+        let mut token_error_reported = false;
+        let mut status = ConnectionStatus::Connected;
+        let mut last_health_check = Instant::now();
+        let mut reconnect_attempt: u32 = 0;
+        let mut next_reconnect: Option<Instant> = None;
 
         loop {
+            if let Some(cluster) = current_config.clone() {
+                if last_health_check.elapsed() >= HEALTH_CHECK_INTERVAL {
+                    last_health_check = Instant::now();
+                    if let (None, Some(source)) = (next_reconnect, &tokens) {
+                        // Hand renewed tokens to the clients that don't poll for them
+                        match source.token() {
+                            Ok(token) if token.expires_at_ms != pushed_token_expiry => {
+                                pushed_token_expiry = token.expires_at_ms;
+                                token_error_reported = false;
+                                let clients = admin.as_ref().map(|adm| adm.inner())
+                                    .into_iter()
+                                    .chain(consumer.as_ref().map(|cons| cons.client()));
+                                for client in clients {
+                                    if let Err(e) = native::set_oauth_token(client, &token) {
+                                        let _ = resp_tx.send(KafkaResponse::Error(format!("Setting OAuth token failed: {}", e)));
+                                    }
+                                }
+                            }
+                            Ok(_) => {}
+                            Err(e) if !token_error_reported => {
+                                token_error_reported = true;
+                                let _ = resp_tx.send(KafkaResponse::Error(format!("OAuth token refresh failed: {}", e)));
+                            }
+                            Err(_) => {}
+                        }
+                    }
+                    let new_status = match next_reconnect {
+                        Some(at) if Instant::now() < at => status.clone(),
+                        Some(_) => {
+                            health = HealthMonitor::default();
+                            match connect(&cluster, &health, &resp_tx, tokens.as_ref()) {
+                                Ok((adm, cons, prod)) => {
+                                    if let (true, Some((topic, offset_mode))) = (consuming, &consume_from) {
+                                        if let Err(e) = assign_partitions(&cons, topic, offset_mode, &positions) {
+                                            let _ = resp_tx.send(KafkaResponse::Error(format!(
+                                                "Resuming consumption failed: {}",
+                                                e
+                                            )));
+                                            consuming = false;
+                                        }
+                                    }
+                                    admin = Some(Arc::new(adm));
+                                    consumer = Some(cons);
+                                    producer = Some(prod);
+                                    reconnect_attempt = 0;
+                                    next_reconnect = None;
+                                    ConnectionStatus::Connected
+                                }
+                                Err(_) => {
+                                    reconnect_attempt += 1;
+                                    let retry_in_secs = reconnect_backoff(reconnect_attempt);
+                                    next_reconnect = Some(Instant::now() + Duration::from_secs(retry_in_secs));
+                                    ConnectionStatus::Reconnecting { attempt: reconnect_attempt, retry_in_secs }
+                                }
+                            }
+                        }
+                        None => {
+                            // Only probe the brokers once librdkafka says they are all gone
+                            let reachable = !health.all_brokers_down()
+                                || admin.as_ref().is_some_and(|adm| {
+                                    adm.inner().fetch_metadata(None, Duration::from_secs(5)).is_ok()
+                                });
+                            if reachable {
+                                health.recovered();
+                                match health.recent_error(DEGRADED_WINDOW) {
+                                    Some(reason) => ConnectionStatus::Degraded(reason),
+                                    None => ConnectionStatus::Connected,
+                                }
+                            } else {
+                                // The old handles are dead weight; rebuild them from scratch
+                                admin = None;
+                                consumer = None;
+                                producer = None;
+                                reconnect_attempt = 1;
+                                next_reconnect = Some(Instant::now());
+                                ConnectionStatus::Reconnecting { attempt: 1, retry_in_secs: 0 }
+                            }
+                        }
+                    };
+                    if new_status != status {
+                        status = new_status;
+                        let _ = resp_tx.send(KafkaResponse::ConnectionStatus(status.clone()));
+                    }
+                }
+            }
+
             // If consuming, poll for messages with a short timeout
             if consuming {
                 if let Some(ref cons) = consumer {
                     match cons.poll(Duration::from_millis(100)) {
                         Some(Ok(msg)) => {
                             use rdkafka::Message;
-                            let key = msg.key().map(|k| String::from_utf8_lossy(k).to_string());
-                            let value = msg
-                                .payload()
-                                .map(|v| String::from_utf8_lossy(v).to_string())
-                                .unwrap_or_default();
-                            let timestamp = match msg.timestamp() {
-                                rdkafka::Timestamp::CreateTime(ts) => Some(ts),
-                                rdkafka::Timestamp::LogAppendTime(ts) => Some(ts),
-                                _ => None,
-                            };
-                            let headers = if let Some(hdrs) = msg.headers() {
-                                (0..hdrs.count())
-                                    .filter_map(|i| {
-                                        hdrs.get_as::<[u8]>(i).ok().map(|h| {
-                                            (
-                                                h.key.to_string(),
-                                                String::from_utf8_lossy(h.value.unwrap_or(b""))
-                                                    .to_string(),
-                                            )
-                                        })
-                                    })
-                                    .collect()
-                            } else {
-                                Vec::new()
-                            };
-
-                            let kafka_msg = KafkaMessage {
-                                partition: msg.partition(),
-                                offset: msg.offset(),
-                                key,
-                                value,
-                                timestamp,
-                                headers,
-                            };
-                            let _ = resp_tx.send(KafkaResponse::Messages(vec![kafka_msg]));
+                            positions.insert(msg.partition(), msg.offset() + 1);
+                            let _ = resp_tx.send(KafkaResponse::Messages(vec![kafka_message(&msg)]));
                         }
                         Some(Err(e)) => {
                             let _ = resp_tx.send(KafkaResponse::Error(format!("Consumer error: {}", e)));
                         }
                         None => {}
                     }
+                    if last_position_report.elapsed() >= POSITION_REPORT_INTERVAL {
+                        last_position_report = Instant::now();
+                        if let Some((ref topic, _)) = consume_from {
+                            let _ = resp_tx.send(KafkaResponse::ConsumerPositions(consumer_positions(cons, topic, &positions)));
+                        }
+                    }
                 }
             }
 
@@ -282,73 +916,105 @@ pub fn spawn_kafka_backend() -> (mpsc::UnboundedSender<KafkaCommand>, mpsc::Unbo
                     }
                 }
             } else {
-                cmd_rx.recv().await
+                // Wake up periodically so the health check above keeps running
+                match tokio::time::timeout(HEALTH_CHECK_INTERVAL, cmd_rx.recv()).await {
+                    Ok(cmd) => cmd,
+                    Err(_) => continue,
+                }
             };
 
             let Some(cmd) = cmd else { break };
 
+            // Changes sent while disconnected are recorded too, as failed
+            let cluster_name = current_config.as_ref().map_or("(not connected)", |c| c.name.as_str());
+            let mut audit = AuditEntry::begin(cluster_name, &cmd);
+            if let (Some(mutation), Some(cluster)) = (cmd.mutation(), current_config.as_ref()) {
+                if cluster.read_only {
+                    let message = format!("Refused to {}: cluster '{}' is read-only", mutation, cluster.name);
+                    record_audit(&mut audit, "rejected", message.clone(), &resp_tx);
+                    let _ = resp_tx.send(cmd.failure(message));
+                    continue;
+                }
+            }
+            // The clients are all present or all gone, and every change needs a cluster
+            if (cmd.needs_client() && admin.is_none()) || (cmd.mutation().is_some() && current_config.is_none()) {
+                let message = if current_config.is_some() { "not connected (reconnecting)" } else { NOT_CONNECTED };
+                record_audit(&mut audit, "failed", message, &resp_tx);
+                let _ = resp_tx.send(cmd.failure(message.to_string()));
+                continue;
+            }
+
             match cmd {
-                KafkaCommand::Connect(cluster) => {
-                    match build_client_config(&cluster).create::<AdminClient<DefaultClientContext>>() {
-                        Ok(adm) => {
-                            // Test connection by fetching metadata
-                            match adm.inner().fetch_metadata(None, Duration::from_secs(10)) {
-                                Ok(_meta) => {
-                                    let cons: Result<BaseConsumer, _> = build_client_config(&cluster)
-                                        .set("group.id", "kafka-eye-browser")
-                                        .set("enable.auto.commit", "false")
-                                        .set("auto.offset.reset", "latest")
-                                        .create();
-
-                                    let prod: Result<FutureProducer, _> =
-                                        build_client_config(&cluster).create();
-
-                                    match (cons, prod) {
-                                        (Ok(c), Ok(p)) => {
-                                            admin = Some(adm);
-                                            consumer = Some(c);
-                                            producer = Some(p);
-                                            _current_config = Some(cluster.clone());
-                                            let _ = resp_tx.send(KafkaResponse::Connected(cluster.name));
-                                        }
-                                        (Err(e), _) | (_, Err(e)) => {
-                                            let _ = resp_tx.send(KafkaResponse::ConnectionFailed(
-                                                format!("Failed to create client: {}", e),
-                                            ));
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    let _ = resp_tx.send(KafkaResponse::ConnectionFailed(format!(
-                                        "Cannot reach brokers: {}",
-                                        e
-                                    )));
-                                }
-                            }
+                KafkaCommand::Connect { cluster, password } => {
+                    // Secrets are resolved here and only kept in memory
+                    let cluster = match cluster.resolve_secrets(password.as_deref()) {
+                        Ok(cluster) => cluster,
+                        Err(e) => {
+                            let _ = resp_tx.send(KafkaResponse::ConnectionFailed(format!("{:#}", e)));
+                            continue;
+                        }
+                    };
+                    health = HealthMonitor::default();
+                    tokens = TokenSource::from_auth(&cluster.auth).map(Arc::new);
+                    pushed_token_expiry = 0;
+                    match connect(&cluster, &health, &resp_tx, tokens.as_ref()) {
+                        Ok((adm, cons, prod)) => {
+                            admin = Some(Arc::new(adm));
+                            consumer = Some(cons);
+                            producer = Some(prod);
+                            current_config = Some(cluster.clone());
+                            status = ConnectionStatus::Connected;
+                            reconnect_attempt = 0;
+                            next_reconnect = None;
+                            let _ = resp_tx.send(KafkaResponse::Connected(cluster.name));
                         }
                         Err(e) => {
-                            let _ = resp_tx.send(KafkaResponse::ConnectionFailed(format!(
-                                "Config error: {}",
-                                e
-                            )));
+                            let _ = resp_tx.send(KafkaResponse::ConnectionFailed(e));
                         }
                     }
                 }
 
                 KafkaCommand::Disconnect => {
+                    for cancel in [search_cancel.take(), table_cancel.take()].into_iter().flatten() {
+                        cancel.store(true, Ordering::Relaxed);
+                    }
                     admin = None;
                     consumer = None;
                     producer = None;
-                    _current_config = None;
+                    current_config = None;
                     consuming = false;
-                    _consume_topic = None;
+                    consume_from = None;
+                    positions.clear();
+                    next_reconnect = None;
                     let _ = resp_tx.send(KafkaResponse::Disconnected);
                 }
 
-                KafkaCommand::TestConnection(cluster) => {
+                KafkaCommand::TestConnection { cluster, password } => {
                     let name = cluster.name.clone();
-                    match build_client_config(&cluster).create::<AdminClient<DefaultClientContext>>() {
+                    let cluster = match cluster.resolve_secrets(password.as_deref()) {
+                        Ok(cluster) => cluster,
+                        Err(e) => {
+                            let _ = resp_tx.send(KafkaResponse::ConnectionTestResult {
+                                cluster_name: name,
+                                success: false,
+                                message: format!("{:#}", e),
+                            });
+                            continue;
+                        }
+                    };
+                    match build_client_config(&cluster, ClientRole::Admin).create::<AdminClient<DefaultClientContext>>() {
                         Ok(adm) => {
+                            // OAUTHBEARER clusters need a token before the first request
+                            if let Some(source) = TokenSource::from_auth(&cluster.auth) {
+                                if let Err(e) = push_token(adm.inner(), &source) {
+                                    let _ = resp_tx.send(KafkaResponse::ConnectionTestResult {
+                                        cluster_name: name,
+                                        success: false,
+                                        message: e,
+                                    });
+                                    continue;
+                                }
+                            }
                             match adm.inner().fetch_metadata(None, Duration::from_secs(10)) {
                                 Ok(meta) => {
                                     let _ = resp_tx.send(KafkaResponse::ConnectionTestResult {
@@ -384,7 +1050,9 @@ pub fn spawn_kafka_backend() -> (mpsc::UnboundedSender<KafkaCommand>, mpsc::Unbo
                     if let Some(ref adm) = admin {
                         match adm.inner().fetch_metadata(None, Duration::from_secs(10)) {
                             Ok(meta) => {
-                                let (resp, _) = extract_metadata(&meta);
+                                let cluster_id = adm.inner().fetch_cluster_id(Duration::from_secs(10));
+                                let controller_id = fetch_controller_id(adm.inner());
+                                let (resp, _) = extract_metadata(&meta, cluster_id, controller_id);
                                 let _ = resp_tx.send(resp);
                             }
                             Err(e) => {
@@ -397,11 +1065,71 @@ pub fn spawn_kafka_backend() -> (mpsc::UnboundedSender<KafkaCommand>, mpsc::Unbo
                     }
                 }
 
+                KafkaCommand::FetchBrokers => {
+                    if let Some(ref adm) = admin {
+                        match adm.inner().fetch_metadata(None, Duration::from_secs(10)) {
+                            Ok(meta) => {
+                                let controller_id = fetch_controller_id(adm.inner());
+                                let brokers = extract_brokers(&meta, controller_id);
+                                let _ = resp_tx.send(KafkaResponse::BrokerList(brokers));
+                            }
+                            Err(e) => {
+                                let _ = resp_tx.send(KafkaResponse::Error(format!(
+                                    "Broker fetch failed: {}",
+                                    e
+                                )));
+                            }
+                        }
+                    }
+                }
+
+                KafkaCommand::FetchBrokerConfig(broker_id) => {
+                    if let Some(ref adm) = admin {
+                        let opts = AdminOptions::new().request_timeout(Some(Duration::from_secs(10)));
+                        match adm.describe_configs(&[ResourceSpecifier::Broker(broker_id)], &opts).await {
+                            Ok(results) => {
+                                for result in results {
+                                    match result {
+                                        Ok(resource) => {
+                                            let mut entries: Vec<ConfigEntryInfo> = resource
+                                                .entries
+                                                .into_iter()
+                                                .map(|e| ConfigEntryInfo {
+                                                    name: e.name,
+                                                    value: e.value,
+                                                    source: format!("{:?}", e.source),
+                                                    is_default: e.is_default,
+                                                    is_sensitive: e.is_sensitive,
+                                                    is_read_only: e.is_read_only,
+                                                })
+                                                .collect();
+                                            entries.sort_by(|a, b| a.name.cmp(&b.name));
+                                            let _ = resp_tx.send(KafkaResponse::BrokerConfig { broker_id, entries });
+                                        }
+                                        Err(e) => {
+                                            let _ = resp_tx.send(KafkaResponse::Error(format!(
+                                                "Describe broker config failed: {:?}",
+                                                e
+                                            )));
+                                        }
+                                    }
+                                }
+                            }
+                            Err(e) => {
+                                let _ = resp_tx.send(KafkaResponse::Error(format!(
+                                    "Describe broker config failed: {}",
+                                    e
+                                )));
+                            }
+                        }
+                    }
+                }
+
                 KafkaCommand::FetchTopics => {
                     if let Some(ref adm) = admin {
                         match adm.inner().fetch_metadata(None, Duration::from_secs(10)) {
                             Ok(meta) => {
-                                let (_, topics) = extract_metadata(&meta);
+                                let (_, topics) = extract_metadata(&meta, None, None);
                                 let _ = resp_tx.send(KafkaResponse::TopicList(topics));
                             }
                             Err(e) => {
@@ -422,19 +1150,9 @@ pub fn spawn_kafka_backend() -> (mpsc::UnboundedSender<KafkaCommand>, mpsc::Unbo
                         {
                             Ok(meta) => {
                                 if let Some(topic) = meta.topics().first() {
-                                    let partitions: Vec<PartitionInfo> = topic
-                                        .partitions()
-                                        .iter()
-                                        .map(|p| PartitionInfo {
-                                            id: p.id(),
-                                            leader: p.leader(),
-                                            replicas: p.replicas().to_vec(),
-                                            isr: p.isr().to_vec(),
-                                        })
-                                        .collect();
                                     let _ = resp_tx.send(KafkaResponse::TopicDetail {
                                         name: topic_name,
-                                        partitions,
+                                        partitions: extract_partitions(topic),
                                     });
                                 }
                             }
@@ -466,16 +1184,19 @@ pub fn spawn_kafka_backend() -> (mpsc::UnboundedSender<KafkaCommand>, mpsc::Unbo
                                 let mut success = true;
                                 for result in &results {
                                     if let Err((_, e)) = result {
+                                        record_audit(&mut audit, "failed", format!("{:?}", e), &resp_tx);
                                         let _ = resp_tx
                                             .send(KafkaResponse::Error(format!("Create topic failed: {:?}", e)));
                                         success = false;
                                     }
                                 }
                                 if success {
+                                    record_audit(&mut audit, "ok", "", &resp_tx);
                                     let _ = resp_tx.send(KafkaResponse::TopicCreated(name));
                                 }
                             }
                             Err(e) => {
+                                record_audit(&mut audit, "failed", e.to_string(), &resp_tx);
                                 let _ = resp_tx.send(KafkaResponse::Error(format!(
                                     "Create topic failed: {}",
                                     e
@@ -493,16 +1214,19 @@ pub fn spawn_kafka_backend() -> (mpsc::UnboundedSender<KafkaCommand>, mpsc::Unbo
                                 let mut success = true;
                                 for result in &results {
                                     if let Err((_, e)) = result {
+                                        record_audit(&mut audit, "failed", format!("{:?}", e), &resp_tx);
                                         let _ = resp_tx
                                             .send(KafkaResponse::Error(format!("Delete topic failed: {:?}", e)));
                                         success = false;
                                     }
                                 }
                                 if success {
+                                    record_audit(&mut audit, "ok", "", &resp_tx);
                                     let _ = resp_tx.send(KafkaResponse::TopicDeleted(name));
                                 }
                             }
                             Err(e) => {
+                                record_audit(&mut audit, "failed", e.to_string(), &resp_tx);
                                 let _ = resp_tx.send(KafkaResponse::Error(format!(
                                     "Delete topic failed: {}",
                                     e
@@ -512,37 +1236,185 @@ pub fn spawn_kafka_backend() -> (mpsc::UnboundedSender<KafkaCommand>, mpsc::Unbo
                     }
                 }
 
-                KafkaCommand::StartConsuming { topic, offset_mode } => {
-                    if let Some(ref cons) = consumer {
-                        // Unsubscribe first
-                        cons.unsubscribe();
+                KafkaCommand::ElectPreferredLeaders { topic, partitions } => {
+                    if let Some(ref adm) = admin {
+                        let targets: Vec<(String, i32)> =
+                            partitions.iter().map(|p| (topic.clone(), *p)).collect();
+                        match native::elect_preferred_leaders(adm.inner(), &targets, Duration::from_secs(30)) {
+                            Ok(results) => {
+                                // The call succeeds as a whole even when every partition fails
+                                let failed: Vec<String> = results
+                                    .iter()
+                                    .filter_map(|r| r.outcome.as_ref().err().map(|e| format!("{}-{}: {}", r.topic, r.partition, e)))
+                                    .collect();
+                                let result = match failed.len() {
+                                    0 => "ok",
+                                    n if n == results.len() => "failed",
+                                    _ => "partial",
+                                };
+                                record_audit(&mut audit, result, failed.join("; "), &resp_tx);
+                                let _ = resp_tx.send(KafkaResponse::LeaderElectionResult {
+                                    topic: topic.clone(),
+                                    results,
+                                });
+                            }
+                            Err(e) => {
+                                record_audit(&mut audit, "failed", e.to_string(), &resp_tx);
+                                let _ = resp_tx.send(election_failed(&topic, &partitions, &e.to_string()));
+                            }
+                        }
 
-                        let meta = cons.fetch_metadata(Some(&topic), Duration::from_secs(10));
-                        if let Ok(meta) = meta {
+                        // Leadership has moved, so refresh the partition list either way
+                        if let Ok(meta) = adm.inner().fetch_metadata(Some(&topic), Duration::from_secs(10)) {
                             if let Some(topic_meta) = meta.topics().first() {
-                                let mut tpl = TopicPartitionList::new();
-                                for p in topic_meta.partitions() {
-                                    let offset = match &offset_mode {
-                                        OffsetMode::Earliest => rdkafka::Offset::Beginning,
-                                        OffsetMode::Latest => rdkafka::Offset::End,
-                                        OffsetMode::Specific(o) => rdkafka::Offset::Offset(*o),
-                                        OffsetMode::Timestamp(_ts) => rdkafka::Offset::End,
-                                    };
-                                    tpl.add_partition_offset(&topic, p.id(), offset).ok();
-                                }
-                                match cons.assign(&tpl) {
-                                    Ok(()) => {
-                                        consuming = true;
-                                        _consume_topic = Some(topic);
+                                let _ = resp_tx.send(KafkaResponse::TopicDetail {
+                                    name: topic,
+                                    partitions: extract_partitions(topic_meta),
+                                });
+                            }
+                        }
+                    }
+                }
+
+                KafkaCommand::FetchAcls => {
+                    if let Some(ref adm) = admin {
+                        send_acl_list(adm.inner(), &resp_tx);
+                    }
+                }
+
+                KafkaCommand::CreateAcl(binding) => {
+                    if let Some(ref adm) = admin {
+                        match native::create_acl(adm.inner(), &binding, Duration::from_secs(30)) {
+                            Ok(()) => {
+                                record_audit(&mut audit, "ok", "", &resp_tx);
+                                let _ = resp_tx.send(KafkaResponse::AclCreated(binding));
+                                send_acl_list(adm.inner(), &resp_tx);
+                            }
+                            Err(e) => {
+                                record_audit(&mut audit, "failed", e.to_string(), &resp_tx);
+                                let _ = resp_tx.send(KafkaResponse::Error(format!(
+                                    "Create ACL failed: {}",
+                                    e
+                                )));
+                            }
+                        }
+                    }
+                }
+
+                KafkaCommand::DeleteAcl(binding) => {
+                    if let Some(ref adm) = admin {
+                        match native::delete_acl(adm.inner(), &binding, Duration::from_secs(30)) {
+                            Ok(count) => {
+                                record_audit(&mut audit, "ok", format!("{} ACL(s) deleted", count), &resp_tx);
+                                let _ = resp_tx.send(KafkaResponse::AclDeleted { binding, count });
+                                send_acl_list(adm.inner(), &resp_tx);
+                            }
+                            Err(e) => {
+                                record_audit(&mut audit, "failed", e.to_string(), &resp_tx);
+                                let _ = resp_tx.send(KafkaResponse::Error(format!(
+                                    "Delete ACL failed: {}",
+                                    e
+                                )));
+                            }
+                        }
+                    }
+                }
+
+                KafkaCommand::PlanReassignment(goal) => {
+                    if let Some(ref adm) = admin {
+                        match adm.inner().fetch_metadata(None, Duration::from_secs(10)) {
+                            Ok(meta) => {
+                                // Internal topics have replicas too and must move with the rest
+                                let topics: Vec<TopicInfo> = meta.topics().iter().map(extract_topic).collect();
+                                let brokers: Vec<i32> = meta.brokers().iter().map(|b| b.id()).collect();
+                                match reassign::build_plan(&goal, &topics, &brokers) {
+                                    Ok(plan) => {
+                                        let _ = resp_tx.send(KafkaResponse::ReassignmentPlanned(plan));
                                     }
                                     Err(e) => {
                                         let _ = resp_tx.send(KafkaResponse::Error(format!(
-                                            "Failed to assign partitions: {}",
+                                            "Reassignment planning failed: {}",
                                             e
                                         )));
                                     }
                                 }
                             }
+                            Err(e) => {
+                                let _ = resp_tx.send(KafkaResponse::Error(format!(
+                                    "Metadata fetch failed: {}",
+                                    e
+                                )));
+                            }
+                        }
+                    }
+                }
+
+                KafkaCommand::SubmitReassignment(plan) => {
+                    if let Some(ref cluster) = current_config {
+                        // The external tool takes seconds, so it runs off the loop
+                        // and the audit entry goes with it
+                        let mut audit = audit.take();
+                        let cluster = cluster.clone();
+                        let admin = admin.clone();
+                        let resp_tx = resp_tx.clone();
+                        tokio::spawn(async move {
+                            match reassign::submit(&cluster, &plan).await {
+                                Ok(output) => {
+                                    record_audit(&mut audit, "ok", "", &resp_tx);
+                                    let _ = resp_tx.send(KafkaResponse::ReassignmentSubmitted(output));
+                                    if let Some(adm) = admin {
+                                        let _ = tokio::task::spawn_blocking(move || {
+                                            if let Ok(meta) = adm.inner().fetch_metadata(None, Duration::from_secs(10)) {
+                                                let progress = reassignment_progress(&meta, &plan);
+                                                let _ = resp_tx.send(KafkaResponse::ReassignmentProgress(progress));
+                                            }
+                                        })
+                                        .await;
+                                    }
+                                }
+                                Err(e) => {
+                                    record_audit(&mut audit, "failed", e.to_string(), &resp_tx);
+                                    let _ = resp_tx.send(KafkaResponse::Error(format!(
+                                        "Reassignment submit failed: {}",
+                                        e
+                                    )));
+                                }
+                            }
+                        });
+                    }
+                }
+
+                KafkaCommand::TrackReassignment(plan) => {
+                    if let Some(ref adm) = admin {
+                        match adm.inner().fetch_metadata(None, Duration::from_secs(10)) {
+                            Ok(meta) => {
+                                let progress = reassignment_progress(&meta, &plan);
+                                let _ = resp_tx.send(KafkaResponse::ReassignmentProgress(progress));
+                            }
+                            Err(e) => {
+                                let _ = resp_tx.send(KafkaResponse::Error(format!(
+                                    "Reassignment progress check failed: {}",
+                                    e
+                                )));
+                            }
+                        }
+                    }
+                }
+
+                KafkaCommand::StartConsuming { topic, offset_mode } => {
+                    if let Some(ref cons) = consumer {
+                        // Unsubscribe first
+                        cons.unsubscribe();
+                        positions.clear();
+
+                        match assign_partitions(cons, &topic, &offset_mode, &positions) {
+                            Ok(()) => {
+                                consuming = true;
+                                consume_from = Some((topic, offset_mode));
+                            }
+                            Err(e) => {
+                                let _ = resp_tx.send(KafkaResponse::Error(e));
+                            }
                         }
                     }
                 }
@@ -552,7 +1424,188 @@ pub fn spawn_kafka_backend() -> (mpsc::UnboundedSender<KafkaCommand>, mpsc::Unbo
                         cons.unsubscribe();
                     }
                     consuming = false;
-                    _consume_topic = None;
+                    consume_from = None;
+                }
+
+                KafkaCommand::StartSearch { id, topic, filter, range } => {
+                    if let Some(cancel) = search_cancel.take() {
+                        cancel.store(true, Ordering::Relaxed);
+                    }
+                    if let Some(ref cluster) = current_config {
+                        match start_search(cluster, tokens.as_ref(), id, topic, filter, range, &resp_tx) {
+                            Ok(cancel) => search_cancel = Some(cancel),
+                            Err(e) => {
+                                let _ = resp_tx.send(KafkaResponse::SearchFinished {
+                                    id,
+                                    scanned: 0,
+                                    matched: 0,
+                                    stopped: Some(e),
+                                });
+                            }
+                        }
+                    } else {
+                        let _ = resp_tx.send(KafkaResponse::SearchFinished {
+                            id,
+                            scanned: 0,
+                            matched: 0,
+                            stopped: Some(NOT_CONNECTED.to_string()),
+                        });
+                    }
+                }
+
+                KafkaCommand::FetchPage { topic, anchor } => {
+                    if let Some(ref cluster) = current_config {
+                        match scan_consumer(cluster, tokens.as_ref(), &resp_tx) {
+                            Ok(cons) => {
+                                let resp_tx = resp_tx.clone();
+                                tokio::task::spawn_blocking(move || {
+                                    match page::fetch(&cons, &topic, &anchor, page::PAGE_SIZE) {
+                                        Ok(page) => {
+                                            let _ = resp_tx.send(KafkaResponse::Page { topic, page });
+                                        }
+                                        Err(e) => {
+                                            let _ = resp_tx.send(KafkaResponse::Error(format!("Fetching page failed: {}", e)));
+                                        }
+                                    }
+                                });
+                            }
+                            Err(e) => {
+                                let _ = resp_tx.send(KafkaResponse::Error(format!("Fetching page failed: {}", e)));
+                            }
+                        }
+                    } else {
+                        let _ = resp_tx.send(KafkaResponse::Error(format!("Fetching page failed: {}", NOT_CONNECTED)));
+                    }
+                }
+
+                KafkaCommand::FetchTimeline { topic, from, to, bucket } => {
+                    if let Some(ref cluster) = current_config {
+                        match scan_consumer(cluster, tokens.as_ref(), &resp_tx) {
+                            Ok(cons) => {
+                                let resp_tx = resp_tx.clone();
+                                tokio::task::spawn_blocking(move || {
+                                    let timeline = timeline::fetch(&cons, &topic, from, to, bucket);
+                                    let _ = resp_tx.send(KafkaResponse::Timeline { topic, timeline });
+                                });
+                            }
+                            Err(e) => {
+                                let _ = resp_tx.send(KafkaResponse::Timeline { topic, timeline: Err(e) });
+                            }
+                        }
+                    } else {
+                        let _ = resp_tx.send(KafkaResponse::Timeline {
+                            topic,
+                            timeline: Err(NOT_CONNECTED.to_string()),
+                        });
+                    }
+                }
+
+                KafkaCommand::AnalyzeTopic { topic, sample } => {
+                    if let Some(ref cluster) = current_config {
+                        match scan_consumer(cluster, tokens.as_ref(), &resp_tx) {
+                            Ok(cons) => {
+                                let resp_tx = resp_tx.clone();
+                                tokio::task::spawn_blocking(move || {
+                                    let analysis = analysis::run(&cons, &topic, sample);
+                                    let _ = resp_tx.send(KafkaResponse::Analysis { topic, sample, analysis });
+                                });
+                            }
+                            Err(e) => {
+                                let _ = resp_tx.send(KafkaResponse::Analysis { topic, sample, analysis: Err(e) });
+                            }
+                        }
+                    } else {
+                        let _ = resp_tx.send(KafkaResponse::Analysis {
+                            topic,
+                            sample,
+                            analysis: Err(NOT_CONNECTED.to_string()),
+                        });
+                    }
+                }
+
+                KafkaCommand::FetchRecord { topic, partition, offset, context } => {
+                    if let Some(ref cluster) = current_config {
+                        match scan_consumer(cluster, tokens.as_ref(), &resp_tx) {
+                            Ok(cons) => {
+                                let resp_tx = resp_tx.clone();
+                                tokio::task::spawn_blocking(move || {
+                                    match search::fetch_around(&cons, &topic, partition, offset, context) {
+                                        Ok(records) => {
+                                            let _ = resp_tx.send(KafkaResponse::Record { topic, partition, offset, records });
+                                        }
+                                        Err(e) => {
+                                            let _ = resp_tx.send(KafkaResponse::Error(format!("Fetching record failed: {}", e)));
+                                        }
+                                    }
+                                });
+                            }
+                            Err(e) => {
+                                let _ = resp_tx.send(KafkaResponse::Error(format!("Fetching record failed: {}", e)));
+                            }
+                        }
+                    } else {
+                        let _ = resp_tx.send(KafkaResponse::Error(format!("Fetching record failed: {}", NOT_CONNECTED)));
+                    }
+                }
+
+                KafkaCommand::CancelSearch => {
+                    if let Some(cancel) = search_cancel.take() {
+                        cancel.store(true, Ordering::Relaxed);
+                    }
+                }
+
+                KafkaCommand::StartTable { id, topic } => {
+                    if let Some(cancel) = table_cancel.take() {
+                        cancel.store(true, Ordering::Relaxed);
+                    }
+                    if let Some(ref adm) = admin {
+                        let adm = adm.clone();
+                        let topic = topic.clone();
+                        let resp_tx = resp_tx.clone();
+                        tokio::spawn(async move {
+                            let opts = AdminOptions::new().request_timeout(Some(Duration::from_secs(10)));
+                            // Only a warning, so a topic the user can't describe is still read
+                            if let Ok(results) = adm.describe_configs(&[ResourceSpecifier::Topic(&topic)], &opts).await {
+                                let policy = results
+                                    .into_iter()
+                                    .flatten()
+                                    .find_map(|resource| resource.get("cleanup.policy").and_then(|e| e.value.clone()));
+                                if let Some(policy) = policy {
+                                    let _ = resp_tx.send(KafkaResponse::TableCleanupPolicy { id, policy });
+                                }
+                            }
+                        });
+                    }
+                    if let Some(ref cluster) = current_config {
+                        match start_table(cluster, tokens.as_ref(), id, topic, &resp_tx) {
+                            Ok(cancel) => table_cancel = Some(cancel),
+                            Err(e) => {
+                                let _ = resp_tx.send(KafkaResponse::TableFinished {
+                                    id,
+                                    rows: Vec::new(),
+                                    summary: TableSummary {
+                                        stopped: Some(e),
+                                        ..Default::default()
+                                    },
+                                });
+                            }
+                        }
+                    } else {
+                        let _ = resp_tx.send(KafkaResponse::TableFinished {
+                            id,
+                            rows: Vec::new(),
+                            summary: TableSummary {
+                                stopped: Some(NOT_CONNECTED.to_string()),
+                                ..Default::default()
+                            },
+                        });
+                    }
+                }
+
+                KafkaCommand::CancelTable => {
+                    if let Some(cancel) = table_cancel.take() {
+                        cancel.store(true, Ordering::Relaxed);
+                    }
                 }
 
                 KafkaCommand::FetchConsumerGroups => {
@@ -624,24 +1677,31 @@ pub fn spawn_kafka_backend() -> (mpsc::UnboundedSender<KafkaCommand>, mpsc::Unbo
                     headers,
                 } => {
                     if let Some(ref prod) = producer {
-                        let mut record = FutureRecord::to(&topic).payload(&value);
-                        let key_str;
+                        let mut record = FutureRecord::<String, String>::to(&topic);
+                        if let Some(ref v) = value {
+                            record = record.payload(v);
+                        }
                         if let Some(ref k) = key {
-                            key_str = k.clone();
-                            record = record.key(&key_str);
+                            record = record.key(k);
                         }
 
                         let mut owned_headers = rdkafka::message::OwnedHeaders::new();
                         for (k, v) in &headers {
                             owned_headers = owned_headers.insert(rdkafka::message::Header {
                                 key: k,
-                                value: Some(v.as_bytes()),
+                                value: v.as_deref().map(str::as_bytes),
                             });
                         }
                         record = record.headers(owned_headers);
 
                         match prod.send(record, Duration::from_secs(5)).await {
                             Ok((partition, offset)) => {
+                                record_audit(
+                                    &mut audit,
+                                    "ok",
+                                    format!("partition {} offset {}", partition, offset),
+                                    &resp_tx,
+                                );
                                 let _ = resp_tx.send(KafkaResponse::MessageProduced {
                                     topic,
                                     partition,
@@ -649,6 +1709,7 @@ pub fn spawn_kafka_backend() -> (mpsc::UnboundedSender<KafkaCommand>, mpsc::Unbo
                                 });
                             }
                             Err((e, _)) => {
+                                record_audit(&mut audit, "failed", e.to_string(), &resp_tx);
                                 let _ = resp_tx.send(KafkaResponse::Error(format!(
                                     "Produce failed: {}",
                                     e
@@ -660,8 +1721,58 @@ pub fn spawn_kafka_backend() -> (mpsc::UnboundedSender<KafkaCommand>, mpsc::Unbo
                     }
                 }
             }
+
+            // A mutating command that reached no client never ran
+            record_audit(&mut audit, "failed", "not connected", &resp_tx);
         }
     });
 
     (cmd_tx, resp_rx)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompted_cluster() -> ClusterConfig {
+        toml::from_str(
+            r#"name = "dev"
+brokers = "localhost:9092"
+auth = { type = "SaslPlain", username = "eye", password = "prompt:" }
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn prompted_passwords_reach_the_client_unchanged() {
+        let cluster = prompted_cluster();
+        for typed in ["cmd:touch /tmp/kafka-eye-pwned", "env:HOME", "file:~/.ssh/id_rsa", "prompt:", ""] {
+            let resolved = cluster.resolve_secrets(Some(typed)).unwrap();
+            let config = build_client_config(&resolved, ClientRole::Admin);
+            assert_eq!(config.get("sasl.password"), Some(typed));
+        }
+        assert!(!std::path::Path::new("/tmp/kafka-eye-pwned").exists());
+    }
+
+    #[test]
+    fn election_failures_answer_the_election() {
+        let election = KafkaCommand::ElectPreferredLeaders {
+            topic: "orders".to_string(),
+            partitions: vec![0, 2],
+        };
+        let KafkaResponse::LeaderElectionResult { topic, results } = election.failure("not connected".to_string()) else {
+            panic!("expected an election result");
+        };
+        assert_eq!(topic, "orders");
+        assert_eq!(results.iter().map(|r| r.partition).collect::<Vec<_>>(), [0, 2]);
+        assert!(results.iter().all(|r| r.outcome == Err("not connected".to_string())));
+        assert!(matches!(KafkaCommand::FetchBrokers.failure("x".to_string()), KafkaResponse::Error(_)));
+    }
+
+    #[test]
+    fn unprompted_clusters_still_resolve_the_stored_secret() {
+        let err = prompted_cluster().resolve_secrets(None).unwrap_err();
+        assert!(format!("{:#}", err).contains("password was not entered"));
+    }
+}
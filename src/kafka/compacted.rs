@@ -0,0 +1,116 @@
+//! Materialized view of a compacted topic: the latest record of every key,
+//! read from the log start up to the high watermark when it starts. Keys
+//! whose latest record is a tombstone are dropped, as compaction would.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
+use std::sync::atomic::AtomicBool;
+
+use rdkafka::consumer::{BaseConsumer, ConsumerContext};
+use tokio::sync::mpsc;
+
+use crate::app::KafkaMessage;
+use crate::kafka::client::KafkaResponse;
+use crate::kafka::search::{self, PartitionScan, ScanVisitor, SearchBound, SearchRange};
+
+/// Stop reading after this many live keys; the table is reported as truncated
+pub const MAX_TABLE_KEYS: usize = 200_000;
+
+/// Stop reading once the live records hold this many bytes of key, value and headers
+pub const MAX_TABLE_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Counts of what was read besides the live keys
+#[derive(Debug, Clone, Default)]
+pub struct TableSummary {
+    pub scanned: u64,
+    /// Keys removed by a tombstone that was their latest record
+    pub deleted: usize,
+    /// Records without a key, which compaction can't keep
+    pub null_keys: u64,
+    /// Why the read ended before the high watermark
+    pub stopped: Option<String>,
+}
+
+struct Table<'a> {
+    id: u64,
+    latest: HashMap<String, KafkaMessage>,
+    deleted: HashSet<String>,
+    null_keys: u64,
+    /// Total size of the records in `latest`
+    bytes: u64,
+    resp_tx: &'a mpsc::UnboundedSender<KafkaResponse>,
+}
+
+impl ScanVisitor for Table<'_> {
+    fn visit(&mut self, message: KafkaMessage) -> ControlFlow<String, bool> {
+        let Some(key) = message.key.clone() else {
+            self.null_keys += 1;
+            return ControlFlow::Continue(false);
+        };
+        // A key stays in one partition, so records arrive in offset order
+        let replaced = if message.is_tombstone() {
+            self.deleted.insert(key.clone());
+            self.latest.remove(&key)
+        } else {
+            if self.latest.len() >= MAX_TABLE_KEYS && !self.latest.contains_key(&key) {
+                return ControlFlow::Break(format!("stopped at {} keys", MAX_TABLE_KEYS));
+            }
+            if self.bytes >= MAX_TABLE_BYTES {
+                return ControlFlow::Break(format!("stopped at {} MiB of records", MAX_TABLE_BYTES / (1024 * 1024)));
+            }
+            self.deleted.remove(&key);
+            self.bytes += message.total_size() as u64;
+            self.latest.insert(key, message)
+        };
+        if let Some(old) = replaced {
+            self.bytes -= old.total_size() as u64;
+        }
+        ControlFlow::Continue(true)
+    }
+
+    fn progress(&mut self, scans: &[PartitionScan]) {
+        let _ = self.resp_tx.send(KafkaResponse::TableProgress {
+            id: self.id,
+            scans: scans.to_vec(),
+            keys: self.latest.len(),
+        });
+    }
+}
+
+/// Builds the table and sends it, sorted by key, once the read ends or
+/// `MAX_TABLE_KEYS` or `MAX_TABLE_BYTES` is reached.
+pub fn run<C: ConsumerContext>(
+    consumer: &BaseConsumer<C>,
+    id: u64,
+    topic: &str,
+    cancel: &AtomicBool,
+    resp_tx: &mpsc::UnboundedSender<KafkaResponse>,
+) -> Result<(), String> {
+    let range = SearchRange {
+        from: SearchBound::Edge,
+        to: SearchBound::Edge,
+    };
+    let mut table = Table {
+        id,
+        latest: HashMap::new(),
+        deleted: HashSet::new(),
+        null_keys: 0,
+        bytes: 0,
+        resp_tx,
+    };
+    let (scans, stopped) = search::scan(consumer, topic, &range, cancel, &mut table, resp_tx)?;
+
+    let mut rows: Vec<KafkaMessage> = table.latest.into_values().collect();
+    rows.sort_by(|a, b| a.key.cmp(&b.key));
+    let _ = resp_tx.send(KafkaResponse::TableFinished {
+        id,
+        rows,
+        summary: TableSummary {
+            scanned: scans.iter().map(|s| s.scanned() as u64).sum(),
+            deleted: table.deleted.len(),
+            null_keys: table.null_keys,
+            stopped,
+        },
+    });
+    Ok(())
+}
@@ -0,0 +1,151 @@
+//! Client context shared by the admin client, consumer and producer. It
+//! turns librdkafka's error callbacks into a health signal that the backend
+//! loop checks to decide when the connection is degraded or lost, forwards
+//! the periodic statistics reports to the UI and answers librdkafka's
+//! OAUTHBEARER token refresh requests.
+
+use crate::app::{BrokerStats, ClientRole, ClientStats, PartitionStats};
+use crate::kafka::client::KafkaResponse;
+use crate::kafka::oauth::TokenSource;
+use rdkafka::client::OAuthToken;
+use rdkafka::consumer::ConsumerContext;
+use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+use rdkafka::statistics::Statistics;
+use rdkafka::ClientContext;
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+#[derive(Debug, Default)]
+struct Health {
+    all_brokers_down: bool,
+    last_error: Option<(Instant, String)>,
+}
+
+/// Handle on the health of one set of clients; cheap to clone.
+#[derive(Debug, Clone, Default)]
+pub struct HealthMonitor(Arc<Mutex<Health>>);
+
+impl HealthMonitor {
+    /// True once librdkafka has reported that no broker is reachable.
+    pub fn all_brokers_down(&self) -> bool {
+        self.0.lock().map(|h| h.all_brokers_down).unwrap_or(false)
+    }
+
+    /// The last client error, if one was reported within `window`.
+    pub fn recent_error(&self, window: Duration) -> Option<String> {
+        let health = self.0.lock().ok()?;
+        match &health.last_error {
+            Some((at, reason)) if at.elapsed() < window => Some(reason.clone()),
+            _ => None,
+        }
+    }
+
+    /// Clears the all-brokers-down flag after the cluster answered again.
+    pub fn recovered(&self) {
+        if let Ok(mut health) = self.0.lock() {
+            health.all_brokers_down = false;
+        }
+    }
+}
+
+pub struct EyeContext {
+    role: ClientRole,
+    health: HealthMonitor,
+    resp_tx: mpsc::UnboundedSender<KafkaResponse>,
+    tokens: Option<Arc<TokenSource>>,
+}
+
+impl EyeContext {
+    pub fn new(
+        role: ClientRole,
+        health: HealthMonitor,
+        resp_tx: mpsc::UnboundedSender<KafkaResponse>,
+        tokens: Option<Arc<TokenSource>>,
+    ) -> Self {
+        Self { role, health, resp_tx, tokens }
+    }
+}
+
+impl ClientContext for EyeContext {
+    const ENABLE_REFRESH_OAUTH_TOKEN: bool = true;
+
+    fn error(&self, error: KafkaError, reason: &str) {
+        if let Ok(mut health) = self.health.0.lock() {
+            if error.rdkafka_error_code() == Some(RDKafkaErrorCode::AllBrokersDown) {
+                health.all_brokers_down = true;
+            }
+            health.last_error = Some((Instant::now(), reason.to_string()));
+        }
+    }
+
+    fn stats(&self, statistics: Statistics) {
+        let _ = self.resp_tx.send(KafkaResponse::ClientStats(client_stats(self.role, &statistics)));
+    }
+
+    fn generate_oauth_token(&self, _oauthbearer_config: Option<&str>) -> Result<OAuthToken, Box<dyn Error>> {
+        let tokens = self.tokens.as_ref().ok_or("OAUTHBEARER is not configured for this cluster")?;
+        let token = tokens.token().map_err(|e| e.to_string())?;
+        Ok(OAuthToken {
+            token: token.value,
+            principal_name: token.principal,
+            lifetime_ms: token.expires_at_ms,
+        })
+    }
+}
+
+impl ConsumerContext for EyeContext {}
+
+fn client_stats(role: ClientRole, statistics: &Statistics) -> ClientStats {
+    // Bootstrap and logical (coordinator) entries have no node id
+    let mut brokers: Vec<BrokerStats> = statistics
+        .brokers
+        .values()
+        .filter(|b| b.nodeid >= 0)
+        .map(|b| BrokerStats {
+            node_id: b.nodeid,
+            name: b.nodename.clone(),
+            state: b.state.clone(),
+            rtt_avg: b.rtt.as_ref().map(|w| w.avg),
+            rtt_p99: b.rtt.as_ref().map(|w| w.p99),
+            outbuf_cnt: b.outbuf_cnt,
+            waitresp_cnt: b.waitresp_cnt,
+            tx_bytes: b.txbytes,
+            rx_bytes: b.rxbytes,
+            req_timeouts: b.req_timeouts,
+        })
+        .collect();
+    brokers.sort_by_key(|b| b.node_id);
+
+    // Partition -1 is librdkafka's placeholder for unassigned messages
+    let mut partitions: Vec<PartitionStats> = statistics
+        .topics
+        .values()
+        .flat_map(|t| t.partitions.values().map(move |p| (t, p)))
+        .filter(|(_, p)| p.partition >= 0 && (p.desired || p.msgq_cnt > 0 || p.xmit_msgq_cnt > 0))
+        .map(|(t, p)| PartitionStats {
+            topic: t.topic.clone(),
+            partition: p.partition,
+            leader: p.leader,
+            fetch_state: p.fetch_state.clone(),
+            fetchq_cnt: p.fetchq_cnt,
+            next_offset: p.next_offset,
+            consumer_lag: p.consumer_lag,
+            msgq_cnt: p.msgq_cnt,
+            xmit_msgq_cnt: p.xmit_msgq_cnt,
+        })
+        .collect();
+    partitions.sort_by(|a, b| (&a.topic, a.partition).cmp(&(&b.topic, b.partition)));
+
+    ClientStats {
+        role,
+        received_at: chrono::Local::now(),
+        queued_msgs: statistics.msg_cnt,
+        queued_bytes: statistics.msg_size,
+        tx_bytes: statistics.tx_bytes,
+        rx_bytes: statistics.rx_bytes,
+        brokers,
+        partitions,
+    }
+}
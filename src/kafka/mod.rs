@@ -1 +1,10 @@
+pub mod analysis;
 pub mod client;
+pub mod compacted;
+mod context;
+mod native;
+mod oauth;
+pub mod page;
+pub mod reassign;
+pub mod search;
+pub mod timeline;
@@ -0,0 +1,432 @@
+//! Admin operations that librdkafka supports but the rdkafka crate does not
+//! wrap. Each call runs on its own temporary queue and blocks until the
+//! result event arrives, like the metadata fetches in the backend loop.
+
+use crate::app::{AclBinding, AclOperation, AclPatternType, AclPermission, AclResourceType, ElectionResult};
+use crate::kafka::oauth::Token;
+use anyhow::{anyhow, Result};
+use rdkafka::bindings as rdsys;
+use rdkafka::client::Client;
+use rdkafka::message::BorrowedMessage;
+use rdkafka::types::{RDKafkaAdminOp, RDKafkaRespErr};
+use rdkafka::ClientContext;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::time::Duration;
+
+struct Queue(*mut rdsys::rd_kafka_queue_t);
+
+impl Drop for Queue {
+    fn drop(&mut self) {
+        unsafe { rdsys::rd_kafka_queue_destroy(self.0) }
+    }
+}
+
+struct Options(*mut rdsys::rd_kafka_AdminOptions_t);
+
+impl Drop for Options {
+    fn drop(&mut self) {
+        unsafe { rdsys::rd_kafka_AdminOptions_destroy(self.0) }
+    }
+}
+
+struct Event(*mut rdsys::rd_kafka_event_t);
+
+impl Drop for Event {
+    fn drop(&mut self) {
+        unsafe { rdsys::rd_kafka_event_destroy(self.0) }
+    }
+}
+
+struct PartitionList(*mut rdsys::rd_kafka_topic_partition_list_t);
+
+impl Drop for PartitionList {
+    fn drop(&mut self) {
+        unsafe { rdsys::rd_kafka_topic_partition_list_destroy(self.0) }
+    }
+}
+
+/// Bindings created for a request; librdkafka copies them, so they are
+/// destroyed once the request has been enqueued.
+struct Bindings(Vec<*mut rdsys::rd_kafka_AclBinding_t>);
+
+impl Drop for Bindings {
+    fn drop(&mut self) {
+        unsafe { rdsys::rd_kafka_AclBinding_destroy_array(self.0.as_mut_ptr(), self.0.len()) }
+    }
+}
+
+unsafe fn cstr_to_string(ptr: *const c_char) -> String {
+    if ptr.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(ptr).to_string_lossy().into_owned()
+    }
+}
+
+fn admin_options<C: ClientContext>(client: &Client<C>, op: RDKafkaAdminOp, timeout: Duration) -> Options {
+    let ms = timeout.as_millis() as i32;
+    let mut errstr = [0 as c_char; 512];
+    unsafe {
+        let opts = Options(rdsys::rd_kafka_AdminOptions_new(client.native_ptr(), op));
+        rdsys::rd_kafka_AdminOptions_set_request_timeout(opts.0, ms, errstr.as_mut_ptr(), errstr.len());
+        // Not every operation accepts an operation timeout; failures here are harmless.
+        rdsys::rd_kafka_AdminOptions_set_operation_timeout(opts.0, ms, errstr.as_mut_ptr(), errstr.len());
+        opts
+    }
+}
+
+fn wait_for_result(queue: &Queue, timeout: Duration) -> Result<Event> {
+    // Leave the broker its full timeout before giving up on the local queue.
+    let wait_ms = (timeout + Duration::from_secs(5)).as_millis() as i32;
+    let event = unsafe { rdsys::rd_kafka_queue_poll(queue.0, wait_ms) };
+    if event.is_null() {
+        return Err(anyhow!("timed out waiting for admin result"));
+    }
+    let event = Event(event);
+    let err = unsafe { rdsys::rd_kafka_event_error(event.0) };
+    if err != RDKafkaRespErr::RD_KAFKA_RESP_ERR_NO_ERROR {
+        let msg = unsafe { cstr_to_string(rdsys::rd_kafka_event_error_string(event.0)) };
+        return Err(anyhow!("{:?}: {}", err, msg));
+    }
+    Ok(event)
+}
+
+/// Sets the OAUTHBEARER token a client authenticates with.
+pub fn set_oauth_token<C: ClientContext>(client: &Client<C>, token: &Token) -> Result<()> {
+    let value = CString::new(token.value.as_str())?;
+    let principal = CString::new(token.principal.as_str())?;
+    let mut errstr = [0 as c_char; 512];
+    let err = unsafe {
+        rdsys::rd_kafka_oauthbearer_set_token(
+            client.native_ptr(),
+            value.as_ptr(),
+            token.expires_at_ms,
+            principal.as_ptr(),
+            std::ptr::null_mut(),
+            0,
+            errstr.as_mut_ptr(),
+            errstr.len(),
+        )
+    };
+    if err != RDKafkaRespErr::RD_KAFKA_RESP_ERR_NO_ERROR {
+        return Err(anyhow!("{:?}: {}", err, unsafe { cstr_to_string(errstr.as_ptr()) }));
+    }
+    Ok(())
+}
+
+/// Triggers a preferred-replica election for the given partitions.
+pub fn elect_preferred_leaders<C: ClientContext>(
+    client: &Client<C>,
+    partitions: &[(String, i32)],
+    timeout: Duration,
+) -> Result<Vec<ElectionResult>> {
+    let rk = client.native_ptr();
+    unsafe {
+        let list = PartitionList(rdsys::rd_kafka_topic_partition_list_new(partitions.len() as i32));
+        for (topic, partition) in partitions {
+            let topic = CString::new(topic.as_str())?;
+            rdsys::rd_kafka_topic_partition_list_add(list.0, topic.as_ptr(), *partition);
+        }
+
+        let request = rdsys::rd_kafka_ElectLeaders_new(
+            rdsys::rd_kafka_ElectionType_t::RD_KAFKA_ELECTION_TYPE_PREFERRED,
+            list.0,
+        );
+        let opts = admin_options(client, RDKafkaAdminOp::RD_KAFKA_ADMIN_OP_ELECTLEADERS, timeout);
+        let queue = Queue(rdsys::rd_kafka_queue_new(rk));
+        rdsys::rd_kafka_ElectLeaders(rk, request, opts.0, queue.0);
+        rdsys::rd_kafka_ElectLeaders_destroy(request);
+
+        let event = wait_for_result(&queue, timeout)?;
+        let result = rdsys::rd_kafka_event_ElectLeaders_result(event.0);
+        if result.is_null() {
+            return Err(anyhow!("unexpected admin result event"));
+        }
+
+        let mut count = 0;
+        let items = rdsys::rd_kafka_ElectLeaders_result_partitions(result, &mut count);
+        let mut results = Vec::with_capacity(count);
+        for i in 0..count {
+            let item = *items.add(i);
+            let tp = &*rdsys::rd_kafka_topic_partition_result_partition(item);
+            let error = rdsys::rd_kafka_topic_partition_result_error(item);
+            let (code, message) = if error.is_null() {
+                (RDKafkaRespErr::RD_KAFKA_RESP_ERR_NO_ERROR, String::new())
+            } else {
+                (rdsys::rd_kafka_error_code(error), cstr_to_string(rdsys::rd_kafka_error_string(error)))
+            };
+            let outcome = match code {
+                RDKafkaRespErr::RD_KAFKA_RESP_ERR_NO_ERROR => Ok("Leader elected".to_string()),
+                RDKafkaRespErr::RD_KAFKA_RESP_ERR_ELECTION_NOT_NEEDED => {
+                    Ok("Already on preferred leader".to_string())
+                }
+                _ => Err(message),
+            };
+            results.push(ElectionResult {
+                topic: cstr_to_string(tp.topic),
+                partition: tp.partition,
+                outcome,
+            });
+        }
+        results.sort_by_key(|r| r.partition);
+        Ok(results)
+    }
+}
+
+fn resource_type_to_native(t: AclResourceType) -> rdsys::rd_kafka_ResourceType_t {
+    use rdsys::rd_kafka_ResourceType_t::*;
+    match t {
+        AclResourceType::Topic => RD_KAFKA_RESOURCE_TOPIC,
+        AclResourceType::Group => RD_KAFKA_RESOURCE_GROUP,
+        // librdkafka calls Kafka's CLUSTER resource type "broker"
+        AclResourceType::Cluster => RD_KAFKA_RESOURCE_BROKER,
+        AclResourceType::TransactionalId => RD_KAFKA_RESOURCE_TRANSACTIONAL_ID,
+        AclResourceType::Unknown => RD_KAFKA_RESOURCE_UNKNOWN,
+    }
+}
+
+fn resource_type_from_native(t: rdsys::rd_kafka_ResourceType_t) -> AclResourceType {
+    use rdsys::rd_kafka_ResourceType_t::*;
+    match t {
+        RD_KAFKA_RESOURCE_TOPIC => AclResourceType::Topic,
+        RD_KAFKA_RESOURCE_GROUP => AclResourceType::Group,
+        RD_KAFKA_RESOURCE_BROKER => AclResourceType::Cluster,
+        RD_KAFKA_RESOURCE_TRANSACTIONAL_ID => AclResourceType::TransactionalId,
+        _ => AclResourceType::Unknown,
+    }
+}
+
+fn pattern_type_to_native(t: AclPatternType) -> rdsys::rd_kafka_ResourcePatternType_t {
+    use rdsys::rd_kafka_ResourcePatternType_t::*;
+    match t {
+        AclPatternType::Literal => RD_KAFKA_RESOURCE_PATTERN_LITERAL,
+        AclPatternType::Prefixed => RD_KAFKA_RESOURCE_PATTERN_PREFIXED,
+        AclPatternType::Unknown => RD_KAFKA_RESOURCE_PATTERN_UNKNOWN,
+    }
+}
+
+fn pattern_type_from_native(t: rdsys::rd_kafka_ResourcePatternType_t) -> AclPatternType {
+    use rdsys::rd_kafka_ResourcePatternType_t::*;
+    match t {
+        RD_KAFKA_RESOURCE_PATTERN_LITERAL => AclPatternType::Literal,
+        RD_KAFKA_RESOURCE_PATTERN_PREFIXED => AclPatternType::Prefixed,
+        _ => AclPatternType::Unknown,
+    }
+}
+
+fn operation_to_native(op: AclOperation) -> rdsys::rd_kafka_AclOperation_t {
+    use rdsys::rd_kafka_AclOperation_t::*;
+    match op {
+        AclOperation::All => RD_KAFKA_ACL_OPERATION_ALL,
+        AclOperation::Read => RD_KAFKA_ACL_OPERATION_READ,
+        AclOperation::Write => RD_KAFKA_ACL_OPERATION_WRITE,
+        AclOperation::Create => RD_KAFKA_ACL_OPERATION_CREATE,
+        AclOperation::Delete => RD_KAFKA_ACL_OPERATION_DELETE,
+        AclOperation::Alter => RD_KAFKA_ACL_OPERATION_ALTER,
+        AclOperation::Describe => RD_KAFKA_ACL_OPERATION_DESCRIBE,
+        AclOperation::ClusterAction => RD_KAFKA_ACL_OPERATION_CLUSTER_ACTION,
+        AclOperation::DescribeConfigs => RD_KAFKA_ACL_OPERATION_DESCRIBE_CONFIGS,
+        AclOperation::AlterConfigs => RD_KAFKA_ACL_OPERATION_ALTER_CONFIGS,
+        AclOperation::IdempotentWrite => RD_KAFKA_ACL_OPERATION_IDEMPOTENT_WRITE,
+        AclOperation::Unknown => RD_KAFKA_ACL_OPERATION_UNKNOWN,
+    }
+}
+
+fn operation_from_native(op: rdsys::rd_kafka_AclOperation_t) -> AclOperation {
+    use rdsys::rd_kafka_AclOperation_t::*;
+    match op {
+        RD_KAFKA_ACL_OPERATION_ALL => AclOperation::All,
+        RD_KAFKA_ACL_OPERATION_READ => AclOperation::Read,
+        RD_KAFKA_ACL_OPERATION_WRITE => AclOperation::Write,
+        RD_KAFKA_ACL_OPERATION_CREATE => AclOperation::Create,
+        RD_KAFKA_ACL_OPERATION_DELETE => AclOperation::Delete,
+        RD_KAFKA_ACL_OPERATION_ALTER => AclOperation::Alter,
+        RD_KAFKA_ACL_OPERATION_DESCRIBE => AclOperation::Describe,
+        RD_KAFKA_ACL_OPERATION_CLUSTER_ACTION => AclOperation::ClusterAction,
+        RD_KAFKA_ACL_OPERATION_DESCRIBE_CONFIGS => AclOperation::DescribeConfigs,
+        RD_KAFKA_ACL_OPERATION_ALTER_CONFIGS => AclOperation::AlterConfigs,
+        RD_KAFKA_ACL_OPERATION_IDEMPOTENT_WRITE => AclOperation::IdempotentWrite,
+        _ => AclOperation::Unknown,
+    }
+}
+
+fn permission_to_native(p: AclPermission) -> rdsys::rd_kafka_AclPermissionType_t {
+    use rdsys::rd_kafka_AclPermissionType_t::*;
+    match p {
+        AclPermission::Allow => RD_KAFKA_ACL_PERMISSION_TYPE_ALLOW,
+        AclPermission::Deny => RD_KAFKA_ACL_PERMISSION_TYPE_DENY,
+        AclPermission::Unknown => RD_KAFKA_ACL_PERMISSION_TYPE_UNKNOWN,
+    }
+}
+
+fn permission_from_native(p: rdsys::rd_kafka_AclPermissionType_t) -> AclPermission {
+    use rdsys::rd_kafka_AclPermissionType_t::*;
+    match p {
+        RD_KAFKA_ACL_PERMISSION_TYPE_ALLOW => AclPermission::Allow,
+        RD_KAFKA_ACL_PERMISSION_TYPE_DENY => AclPermission::Deny,
+        _ => AclPermission::Unknown,
+    }
+}
+
+/// Builds a native binding; with `filter` set it is an exact-match filter
+/// usable with DeleteAcls instead.
+fn new_binding(binding: &AclBinding, filter: bool) -> Result<*mut rdsys::rd_kafka_AclBinding_t> {
+    let name = CString::new(binding.resource_name.as_str())?;
+    let principal = CString::new(binding.principal.as_str())?;
+    let host = CString::new(binding.host.as_str())?;
+    let mut errstr = [0 as c_char; 512];
+    let ctor = if filter {
+        rdsys::rd_kafka_AclBindingFilter_new
+    } else {
+        rdsys::rd_kafka_AclBinding_new
+    };
+    let ptr = unsafe {
+        ctor(
+            resource_type_to_native(binding.resource_type),
+            name.as_ptr(),
+            pattern_type_to_native(binding.pattern_type),
+            principal.as_ptr(),
+            host.as_ptr(),
+            operation_to_native(binding.operation),
+            permission_to_native(binding.permission),
+            errstr.as_mut_ptr(),
+            errstr.len(),
+        )
+    };
+    if ptr.is_null() {
+        return Err(anyhow!("invalid ACL binding: {}", unsafe { cstr_to_string(errstr.as_ptr()) }));
+    }
+    Ok(ptr)
+}
+
+unsafe fn binding_from_native(acl: *const rdsys::rd_kafka_AclBinding_t) -> AclBinding {
+    AclBinding {
+        resource_type: resource_type_from_native(rdsys::rd_kafka_AclBinding_restype(acl)),
+        resource_name: cstr_to_string(rdsys::rd_kafka_AclBinding_name(acl)),
+        pattern_type: pattern_type_from_native(rdsys::rd_kafka_AclBinding_resource_pattern_type(acl)),
+        principal: cstr_to_string(rdsys::rd_kafka_AclBinding_principal(acl)),
+        host: cstr_to_string(rdsys::rd_kafka_AclBinding_host(acl)),
+        operation: operation_from_native(rdsys::rd_kafka_AclBinding_operation(acl)),
+        permission: permission_from_native(rdsys::rd_kafka_AclBinding_permission_type(acl)),
+    }
+}
+
+/// Lists every ACL binding in the cluster.
+pub fn describe_acls<C: ClientContext>(client: &Client<C>, timeout: Duration) -> Result<Vec<AclBinding>> {
+    let rk = client.native_ptr();
+    unsafe {
+        let mut errstr = [0 as c_char; 512];
+        let filter = rdsys::rd_kafka_AclBindingFilter_new(
+            rdsys::rd_kafka_ResourceType_t::RD_KAFKA_RESOURCE_ANY,
+            std::ptr::null(),
+            rdsys::rd_kafka_ResourcePatternType_t::RD_KAFKA_RESOURCE_PATTERN_ANY,
+            std::ptr::null(),
+            std::ptr::null(),
+            rdsys::rd_kafka_AclOperation_t::RD_KAFKA_ACL_OPERATION_ANY,
+            rdsys::rd_kafka_AclPermissionType_t::RD_KAFKA_ACL_PERMISSION_TYPE_ANY,
+            errstr.as_mut_ptr(),
+            errstr.len(),
+        );
+        if filter.is_null() {
+            return Err(anyhow!("invalid ACL filter: {}", cstr_to_string(errstr.as_ptr())));
+        }
+        let filter = Bindings(vec![filter]);
+
+        let opts = admin_options(client, RDKafkaAdminOp::RD_KAFKA_ADMIN_OP_DESCRIBEACLS, timeout);
+        let queue = Queue(rdsys::rd_kafka_queue_new(rk));
+        rdsys::rd_kafka_DescribeAcls(rk, filter.0[0], opts.0, queue.0);
+
+        let event = wait_for_result(&queue, timeout)?;
+        let result = rdsys::rd_kafka_event_DescribeAcls_result(event.0);
+        if result.is_null() {
+            return Err(anyhow!("unexpected admin result event"));
+        }
+
+        let mut count = 0;
+        let acls = rdsys::rd_kafka_DescribeAcls_result_acls(result, &mut count);
+        let mut bindings: Vec<AclBinding> = (0..count).map(|i| binding_from_native(*acls.add(i))).collect();
+        bindings.sort_by(|a, b| {
+            (a.resource_type.label(), &a.resource_name, &a.principal)
+                .cmp(&(b.resource_type.label(), &b.resource_name, &b.principal))
+        });
+        Ok(bindings)
+    }
+}
+
+/// Creates a single ACL binding.
+pub fn create_acl<C: ClientContext>(client: &Client<C>, binding: &AclBinding, timeout: Duration) -> Result<()> {
+    let rk = client.native_ptr();
+    let mut bindings = Bindings(vec![new_binding(binding, false)?]);
+    unsafe {
+        let opts = admin_options(client, RDKafkaAdminOp::RD_KAFKA_ADMIN_OP_CREATEACLS, timeout);
+        let queue = Queue(rdsys::rd_kafka_queue_new(rk));
+        rdsys::rd_kafka_CreateAcls(rk, bindings.0.as_mut_ptr(), bindings.0.len(), opts.0, queue.0);
+
+        let event = wait_for_result(&queue, timeout)?;
+        let result = rdsys::rd_kafka_event_CreateAcls_result(event.0);
+        if result.is_null() {
+            return Err(anyhow!("unexpected admin result event"));
+        }
+
+        let mut count = 0;
+        let items = rdsys::rd_kafka_CreateAcls_result_acls(result, &mut count);
+        for i in 0..count {
+            let error = rdsys::rd_kafka_acl_result_error(*items.add(i));
+            if !error.is_null() {
+                return Err(anyhow!("{}", cstr_to_string(rdsys::rd_kafka_error_string(error))));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Deletes the bindings exactly matching `binding`; returns how many went.
+pub fn delete_acl<C: ClientContext>(client: &Client<C>, binding: &AclBinding, timeout: Duration) -> Result<usize> {
+    let rk = client.native_ptr();
+    let mut filters = Bindings(vec![new_binding(binding, true)?]);
+    unsafe {
+        let opts = admin_options(client, RDKafkaAdminOp::RD_KAFKA_ADMIN_OP_DELETEACLS, timeout);
+        let queue = Queue(rdsys::rd_kafka_queue_new(rk));
+        rdsys::rd_kafka_DeleteAcls(rk, filters.0.as_mut_ptr(), filters.0.len(), opts.0, queue.0);
+
+        let event = wait_for_result(&queue, timeout)?;
+        let result = rdsys::rd_kafka_event_DeleteAcls_result(event.0);
+        if result.is_null() {
+            return Err(anyhow!("unexpected admin result event"));
+        }
+
+        let mut count = 0;
+        let responses = rdsys::rd_kafka_DeleteAcls_result_responses(result, &mut count);
+        let mut deleted = 0;
+        for i in 0..count {
+            let response = *responses.add(i);
+            let error = rdsys::rd_kafka_DeleteAcls_result_response_error(response);
+            if !error.is_null() {
+                return Err(anyhow!("{}", cstr_to_string(rdsys::rd_kafka_error_string(error))));
+            }
+            let mut matched = 0;
+            rdsys::rd_kafka_DeleteAcls_result_response_matching_acls(response, &mut matched);
+            deleted += matched;
+        }
+        Ok(deleted)
+    }
+}
+
+/// Leader epoch of a consumed record; `None` when the broker is too old to
+/// report one.
+pub fn leader_epoch(msg: &BorrowedMessage) -> Option<i32> {
+    let epoch = unsafe { rdsys::rd_kafka_message_leader_epoch(msg.ptr()) };
+    (epoch >= 0).then_some(epoch)
+}
+
+/// Low and high watermark from the client's last fetch response for the
+/// partition, without asking the broker. `None` until it has fetched.
+pub fn cached_watermarks<C: ClientContext>(client: &Client<C>, topic: &str, partition: i32) -> Option<(i64, i64)> {
+    let topic = CString::new(topic).ok()?;
+    let (mut low, mut high) = (-1, -1);
+    let err = unsafe {
+        rdsys::rd_kafka_get_watermark_offsets(client.native_ptr(), topic.as_ptr(), partition, &mut low, &mut high)
+    };
+    (err == RDKafkaRespErr::RD_KAFKA_RESP_ERR_NO_ERROR && high >= 0).then_some((low, high))
+}
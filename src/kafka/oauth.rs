@@ -0,0 +1,269 @@
+//! SASL/OAUTHBEARER tokens. Tokens come either from an OAuth 2.0 token
+//! endpoint using the client-credentials grant, or, for local development,
+//! from an unsecured JWT minted here for a fixed principal.
+
+use crate::config::AuthConfig;
+use anyhow::{anyhow, bail, Context, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::Deserialize;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Tokens are renewed once less than this much of their lifetime is left
+const REFRESH_MARGIN_MS: i64 = 60_000;
+const UNSECURED_LIFETIME_SECS: i64 = 3600;
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub value: String,
+    pub principal: String,
+    /// Expiry in milliseconds since the Unix epoch
+    pub expires_at_ms: i64,
+}
+
+#[derive(Debug, Clone)]
+enum Grant {
+    ClientCredentials {
+        token_endpoint: String,
+        client_id: String,
+        client_secret: String,
+        scope: Option<String>,
+    },
+    Unsecured {
+        principal: String,
+    },
+}
+
+/// Hands out a valid token, fetching a new one when the cached one is
+/// close to expiry. Shared by all clients of a connection.
+#[derive(Debug)]
+pub struct TokenSource {
+    grant: Grant,
+    cached: Mutex<Option<Token>>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: Option<String>,
+}
+
+impl TokenSource {
+    /// Returns a token source when the cluster authenticates with OAUTHBEARER.
+    pub fn from_auth(auth: &AuthConfig) -> Option<Self> {
+        let grant = match auth {
+            AuthConfig::SaslOAuthBearer { token_endpoint, client_id, client_secret, scope }
+            | AuthConfig::SaslSslOAuthBearer { token_endpoint, client_id, client_secret, scope, .. } => {
+                Grant::ClientCredentials {
+                    token_endpoint: token_endpoint.clone(),
+                    client_id: client_id.clone(),
+                    client_secret: client_secret.clone(),
+                    scope: scope.clone(),
+                }
+            }
+            AuthConfig::SaslOAuthBearerUnsecured { principal } => Grant::Unsecured {
+                principal: principal.clone(),
+            },
+            _ => return None,
+        };
+        Some(Self {
+            grant,
+            cached: Mutex::new(None),
+        })
+    }
+
+    pub fn token(&self) -> Result<Token> {
+        let mut cached = self.cached.lock().map_err(|_| anyhow!("token cache poisoned"))?;
+        if let Some(token) = cached.as_ref() {
+            if token.expires_at_ms - now_ms() > REFRESH_MARGIN_MS {
+                return Ok(token.clone());
+            }
+        }
+        let token = match &self.grant {
+            Grant::ClientCredentials { token_endpoint, client_id, client_secret, scope } => {
+                fetch_token(token_endpoint, client_id, client_secret, scope.as_deref())?
+            }
+            Grant::Unsecured { principal } => unsecured_token(principal),
+        };
+        *cached = Some(token.clone());
+        Ok(token)
+    }
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+fn fetch_token(endpoint: &str, client_id: &str, client_secret: &str, scope: Option<&str>) -> Result<Token> {
+    let mut form = vec![
+        ("grant_type", "client_credentials"),
+        ("client_id", client_id),
+        ("client_secret", client_secret),
+    ];
+    if let Some(scope) = scope {
+        form.push(("scope", scope));
+    }
+    let response = match ureq::post(endpoint).timeout(Duration::from_secs(10)).send_form(&form) {
+        Ok(response) => response,
+        Err(ureq::Error::Status(code, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            bail!("token endpoint returned {}: {}", code, body.trim());
+        }
+        Err(e) => return Err(anyhow!("token request failed: {}", e)),
+    };
+    let body: TokenResponse =
+        serde_json::from_reader(response.into_reader()).context("invalid token endpoint response")?;
+
+    // Kafka needs a principal; JWTs carry it in `sub`, opaque tokens fall back to the client id
+    let principal = jwt_subject(&body.access_token).unwrap_or_else(|| client_id.to_string());
+    Ok(Token {
+        principal,
+        expires_at_ms: now_ms() + body.expires_in.unwrap_or(3600) * 1000,
+        value: body.access_token,
+    })
+}
+
+fn jwt_subject(token: &str) -> Option<String> {
+    let payload = token.split('.').nth(1)?;
+    let bytes = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+    serde_json::from_slice::<Claims>(&bytes).ok()?.sub
+}
+
+/// Builds an `alg: none` JWT, which brokers accept only with
+/// `OAuthBearerUnsecuredValidatorCallbackHandler` (the broker default).
+fn unsecured_token(principal: &str) -> Token {
+    let issued_at = chrono::Utc::now().timestamp();
+    let expires_at = issued_at + UNSECURED_LIFETIME_SECS;
+    let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"none"}"#);
+    let claims = serde_json::json!({ "sub": principal, "iat": issued_at, "exp": expires_at });
+    let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+    Token {
+        value: format!("{}.{}.", header, payload),
+        principal: principal.to_string(),
+        expires_at_ms: expires_at * 1000,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    /// Serves one canned HTTP response on a local port and hands back the
+    /// request body it received.
+    fn serve_once(status: &'static str, body: String) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/token", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut request = vec![0; length];
+            reader.read_exact(&mut request).unwrap();
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (url, handle)
+    }
+
+    fn jwt(claims: serde_json::Value) -> String {
+        format!(
+            "{}.{}.signature",
+            URL_SAFE_NO_PAD.encode(r#"{"alg":"RS256"}"#),
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        )
+    }
+
+    #[test]
+    fn client_credentials_grant() {
+        let access_token = jwt(serde_json::json!({ "sub": "svc-orders" }));
+        let body = serde_json::json!({ "access_token": access_token, "expires_in": 120 }).to_string();
+        let (url, server) = serve_once("200 OK", body);
+
+        let before = now_ms();
+        let token = fetch_token(&url, "eye", "s3cret", Some("kafka")).unwrap();
+        let form: Vec<String> = server.join().unwrap().split('&').map(String::from).collect();
+
+        assert!(form.contains(&"grant_type=client_credentials".to_string()), "{:?}", form);
+        assert!(form.contains(&"client_id=eye".to_string()), "{:?}", form);
+        assert!(form.contains(&"client_secret=s3cret".to_string()), "{:?}", form);
+        assert!(form.contains(&"scope=kafka".to_string()), "{:?}", form);
+        assert_eq!(token.value, access_token);
+        assert_eq!(token.principal, "svc-orders");
+        assert!(token.expires_at_ms >= before + 120_000 && token.expires_at_ms <= now_ms() + 120_000);
+    }
+
+    #[test]
+    fn opaque_tokens_use_the_client_id_and_default_lifetime() {
+        let body = serde_json::json!({ "access_token": "opaque" }).to_string();
+        let (url, server) = serve_once("200 OK", body);
+
+        let before = now_ms();
+        let token = fetch_token(&url, "eye", "s3cret", None).unwrap();
+        let form = server.join().unwrap();
+
+        assert!(!form.contains("scope="), "{}", form);
+        assert_eq!(token.principal, "eye");
+        assert!(token.expires_at_ms >= before + 3_600_000 && token.expires_at_ms <= now_ms() + 3_600_000);
+    }
+
+    #[test]
+    fn error_status_is_reported_with_the_body() {
+        let (url, server) = serve_once("401 Unauthorized", r#"{"error":"invalid_client"}"#.to_string());
+        let error = fetch_token(&url, "eye", "wrong", None).unwrap_err().to_string();
+        server.join().unwrap();
+        assert!(error.contains("401"), "{}", error);
+        assert!(error.contains("invalid_client"), "{}", error);
+    }
+
+    #[test]
+    fn token_source_reuses_a_fresh_token() {
+        let body = serde_json::json!({ "access_token": "opaque", "expires_in": 600 }).to_string();
+        let (url, server) = serve_once("200 OK", body);
+        let source = TokenSource::from_auth(&AuthConfig::SaslOAuthBearer {
+            token_endpoint: url,
+            client_id: "eye".to_string(),
+            client_secret: "s3cret".to_string(),
+            scope: None,
+        })
+        .unwrap();
+        let first = source.token().unwrap();
+        server.join().unwrap();
+        // The server only answers once, so this must come from the cache
+        let second = source.token().unwrap();
+        assert_eq!(first.value, second.value);
+    }
+
+    #[test]
+    fn unsecured_tokens_carry_the_principal() {
+        let token = unsecured_token("alice");
+        assert_eq!(token.principal, "alice");
+        assert_eq!(jwt_subject(&token.value).as_deref(), Some("alice"));
+    }
+}
@@ -0,0 +1,272 @@
+//! Paged reads for the message browser. A page is up to `PAGE_SIZE`
+//! records of all partitions merged by timestamp, taking the head of each
+//! partition in turn so every partition gives a contiguous run of offsets.
+//! Partitions are read in chunks with short-lived assignments that stop at
+//! the chunk boundary, so little is read past the page and no offsets are
+//! committed. The next and previous pages seek by offset per partition from
+//! the edges of the page shown.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
+use rdkafka::error::KafkaError;
+use rdkafka::{Offset, TopicPartitionList};
+
+use crate::app::KafkaMessage;
+use crate::kafka::client::kafka_message;
+use crate::kafka::search::{self, SearchBound};
+
+pub const PAGE_SIZE: usize = 100;
+
+/// How long a page read waits for its records
+pub const READ_DEADLINE: Duration = Duration::from_secs(10);
+
+/// Where a page starts or ends
+#[derive(Debug, Clone)]
+pub enum PageAnchor {
+    /// The first page at or after a bound; `Edge` is the log start
+    From(SearchBound),
+    /// The last page before the high watermark
+    Last,
+    /// The page following one, from its next offset per partition
+    After(HashMap<i32, i64>),
+    /// The page preceding one, before its first offset per partition
+    Before(HashMap<i32, i64>),
+}
+
+/// Position of a page in one partition. `start..end` are the offsets the
+/// page covers, empty when none of its records are on the page.
+#[derive(Debug, Clone)]
+pub struct PagePartition {
+    pub partition: i32,
+    pub low: i64,
+    pub high: i64,
+    pub start: i64,
+    pub end: i64,
+    /// Records of this partition on the page
+    pub count: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Page {
+    /// Oldest first
+    pub records: Vec<KafkaMessage>,
+    pub partitions: Vec<PagePartition>,
+}
+
+impl Page {
+    /// Where the next page starts
+    pub fn after(&self) -> PageAnchor {
+        PageAnchor::After(self.partitions.iter().map(|p| (p.partition, p.end)).collect())
+    }
+
+    /// Where the previous page ends
+    pub fn before(&self) -> PageAnchor {
+        PageAnchor::Before(self.partitions.iter().map(|p| (p.partition, p.start)).collect())
+    }
+
+    pub fn at_start(&self) -> bool {
+        self.partitions.iter().all(|p| p.start <= p.low)
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.partitions.iter().all(|p| p.end >= p.high)
+    }
+}
+
+/// Reads one page.
+pub fn fetch<C: ConsumerContext>(
+    consumer: &BaseConsumer<C>,
+    topic: &str,
+    anchor: &PageAnchor,
+    size: usize,
+) -> Result<Page, String> {
+    let (partitions, watermarks) = search::partitions_with_watermarks(consumer, topic)?;
+
+    // Where every partition starts, reading up from it or down from it
+    let forward = matches!(anchor, PageAnchor::From(_) | PageAnchor::After(_));
+    let edges: Vec<i64> = match anchor {
+        PageAnchor::From(bound) => search::resolve(consumer, topic, &partitions, &watermarks, bound, true)?,
+        PageAnchor::Last => watermarks.iter().map(|&(_, high)| high).collect(),
+        PageAnchor::After(offsets) | PageAnchor::Before(offsets) => partitions
+            .iter()
+            .zip(&watermarks)
+            .map(|(p, &(low, high))| offsets.get(p).copied().unwrap_or(if forward { low } else { high }).clamp(low, high))
+            .collect(),
+    };
+    let mut streams: Vec<Stream> = partitions
+        .iter()
+        .zip(watermarks.iter().zip(&edges))
+        .map(|(&partition, (&(low, high), &edge))| Stream {
+            partition,
+            next: edge,
+            limit: if forward { high } else { low },
+            forward,
+            buffer: VecDeque::new(),
+        })
+        .collect();
+
+    // Merge the heads of the partitions, so each one gives a contiguous run of
+    // offsets. Every partition starts with its share of the page and is read
+    // further only when its head is needed.
+    let share = size.div_ceil(partitions.len().max(1));
+    let all: Vec<usize> = (0..streams.len()).collect();
+    fill(consumer, topic, &mut streams, &all, share)?;
+    let mut records = Vec::with_capacity(size);
+    while records.len() < size {
+        let empty: Vec<usize> = (0..streams.len()).filter(|&i| streams[i].needs_read()).collect();
+        if !empty.is_empty() {
+            fill(consumer, topic, &mut streams, &empty, size - records.len())?;
+            continue;
+        }
+        let heads = streams
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| s.buffer.front().map(|m| (i, (m.timestamp.unwrap_or(0), m.partition, m.offset))));
+        let head = if forward { heads.min_by_key(|&(_, k)| k) } else { heads.max_by_key(|&(_, k)| k) };
+        let Some((i, _)) = head else { break };
+        records.extend(streams[i].buffer.pop_front());
+    }
+    if !forward {
+        records.reverse();
+    }
+
+    let partitions = partitions
+        .iter()
+        .zip(watermarks.iter().zip(&edges))
+        .map(|(&partition, (&(low, high), &edge))| {
+            let offsets = || records.iter().filter(|m| m.partition == partition).map(|m| m.offset);
+            let (start, end) = match (offsets().min(), offsets().max()) {
+                (Some(first), Some(last)) => (first, last + 1),
+                _ => (edge, edge),
+            };
+            PagePartition {
+                partition,
+                low,
+                high,
+                start,
+                end,
+                count: offsets().count(),
+            }
+        })
+        .collect();
+    Ok(Page { records, partitions })
+}
+
+/// Records of one partition in the order a page takes them: up from `next`
+/// to `limit` going forward, down from `next` to `limit` going back
+struct Stream {
+    partition: i32,
+    next: i64,
+    limit: i64,
+    forward: bool,
+    /// Read and not yet on the page, next first
+    buffer: VecDeque<KafkaMessage>,
+}
+
+impl Stream {
+    fn needs_read(&self) -> bool {
+        self.buffer.is_empty() && self.next != self.limit
+    }
+
+    /// The next `count` offsets
+    fn chunk(&self, count: usize) -> (i64, i64) {
+        if self.forward {
+            (self.next, (self.next + count as i64).min(self.limit))
+        } else {
+            ((self.next - count as i64).max(self.limit), self.next)
+        }
+    }
+}
+
+/// Reads the next `count` offsets of the chosen streams into their buffers
+fn fill<C: ConsumerContext>(
+    consumer: &BaseConsumer<C>,
+    topic: &str,
+    streams: &mut [Stream],
+    chosen: &[usize],
+    count: usize,
+) -> Result<(), String> {
+    let partitions: Vec<i32> = chosen.iter().map(|&i| streams[i].partition).collect();
+    let ranges: Vec<(i64, i64)> = chosen.iter().map(|&i| streams[i].chunk(count)).collect();
+    let RangeRead { records: mut read, unfinished } = read_ranges(consumer, topic, &partitions, &ranges, READ_DEADLINE)?;
+    if !unfinished.is_empty() {
+        let unfinished: Vec<String> = unfinished.iter().map(|p| p.to_string()).collect();
+        return Err(format!("Timed out reading partition(s) {}", unfinished.join(", ")));
+    }
+    read.sort_by_key(|m| m.offset);
+    for (&i, &(start, end)) in chosen.iter().zip(&ranges) {
+        let stream = &mut streams[i];
+        let records = read.iter().filter(|m| m.partition == stream.partition).cloned();
+        if stream.forward {
+            stream.buffer.extend(records);
+            stream.next = end;
+        } else {
+            stream.buffer.extend(records.rev());
+            stream.next = start;
+        }
+    }
+    Ok(())
+}
+
+/// Records read by `read_ranges`
+pub struct RangeRead {
+    pub records: Vec<KafkaMessage>,
+    /// Partitions not read to the end of their range before the deadline
+    pub unfinished: Vec<i32>,
+}
+
+/// Reads `start..end` of every partition, for at most `deadline`.
+pub fn read_ranges<C: ConsumerContext>(
+    consumer: &BaseConsumer<C>,
+    topic: &str,
+    partitions: &[i32],
+    ranges: &[(i64, i64)],
+    deadline: Duration,
+) -> Result<RangeRead, String> {
+    let mut pending: HashMap<i32, i64> = HashMap::new();
+    let mut tpl = TopicPartitionList::new();
+    for (&partition, &(start, end)) in partitions.iter().zip(ranges) {
+        if start < end {
+            tpl.add_partition_offset(topic, partition, Offset::Offset(start))
+                .map_err(|e| format!("Failed to assign partition {}: {}", partition, e))?;
+            pending.insert(partition, end);
+        }
+    }
+    if pending.is_empty() {
+        return Ok(RangeRead {
+            records: Vec::new(),
+            unfinished: Vec::new(),
+        });
+    }
+    consumer.assign(&tpl).map_err(|e| format!("Failed to assign partitions: {}", e))?;
+
+    let deadline = Instant::now() + deadline;
+    let mut records = Vec::new();
+    while !pending.is_empty() && Instant::now() < deadline {
+        match consumer.poll(Duration::from_millis(100)) {
+            Some(Ok(msg)) => {
+                use rdkafka::Message;
+                let Some(&end) = pending.get(&msg.partition()) else { continue };
+                if msg.offset() < end {
+                    records.push(kafka_message(&msg));
+                }
+                if msg.offset() + 1 >= end {
+                    pending.remove(&msg.partition());
+                    search::pause(consumer, topic, msg.partition());
+                }
+            }
+            // Compaction and transaction markers can leave the last offsets empty
+            Some(Err(KafkaError::PartitionEOF(partition))) => {
+                pending.remove(&partition);
+            }
+            Some(Err(e)) => return Err(e.to_string()),
+            None => {}
+        }
+    }
+    let _ = consumer.unassign();
+    let mut unfinished: Vec<i32> = pending.into_keys().collect();
+    unfinished.sort();
+    Ok(RangeRead { records, unfinished })
+}
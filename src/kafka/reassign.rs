@@ -0,0 +1,590 @@
+//! Partition reassignment planning.
+//!
+//! Plans are computed from the replica lists in `PartitionInfo` and exported
+//! in the JSON format understood by `kafka-reassign-partitions`. librdkafka
+//! has no AlterPartitionReassignments API, so submission goes through that
+//! tool and progress is tracked by comparing metadata against the plan.
+
+use crate::app::{PartitionInfo, TopicInfo};
+use crate::config::{AuthConfig, ClusterConfig};
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReassignmentGoal {
+    /// Move every replica off the given broker
+    DrainBroker(i32),
+    /// Grow or shrink the replica list of every partition of a topic
+    ChangeReplicationFactor {
+        topic: String,
+        replication_factor: usize,
+    },
+    /// Spread replicas and preferred leaders evenly across brokers
+    Rebalance,
+}
+
+impl ReassignmentGoal {
+    pub fn describe(&self) -> String {
+        match self {
+            ReassignmentGoal::DrainBroker(id) => format!("Move all replicas off broker {}", id),
+            ReassignmentGoal::ChangeReplicationFactor { topic, replication_factor } => {
+                format!("Set replication factor of '{}' to {}", topic, replication_factor)
+            }
+            ReassignmentGoal::Rebalance => "Rebalance leaders and replicas across brokers".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartitionMove {
+    pub topic: String,
+    pub partition: i32,
+    pub current: Vec<i32>,
+    pub target: Vec<i32>,
+}
+
+impl PartitionMove {
+    pub fn added(&self) -> Vec<i32> {
+        self.target.iter().filter(|b| !self.current.contains(b)).copied().collect()
+    }
+
+    pub fn removed(&self) -> Vec<i32> {
+        self.current.iter().filter(|b| !self.target.contains(b)).copied().collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReassignmentPlan {
+    pub goal: ReassignmentGoal,
+    pub moves: Vec<PartitionMove>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveStatus {
+    Pending,
+    InProgress,
+    Done,
+}
+
+#[derive(Debug, Clone)]
+pub struct MoveProgress {
+    pub topic: String,
+    pub partition: i32,
+    pub status: MoveStatus,
+}
+
+/// Replica and preferred-leader counts per broker, updated as moves are planned.
+struct Load {
+    replicas: HashMap<i32, usize>,
+    leaders: HashMap<i32, usize>,
+}
+
+impl Load {
+    fn new(topics: &[TopicInfo], brokers: &[i32]) -> Self {
+        let mut replicas: HashMap<i32, usize> = brokers.iter().map(|b| (*b, 0)).collect();
+        let mut leaders = replicas.clone();
+        for p in topics.iter().flat_map(|t| &t.partition_details) {
+            for r in &p.replicas {
+                *replicas.entry(*r).or_default() += 1;
+            }
+            if let Some(first) = p.replicas.first() {
+                *leaders.entry(*first).or_default() += 1;
+            }
+        }
+        Self { replicas, leaders }
+    }
+
+    fn replicas(&self, broker: i32) -> usize {
+        self.replicas.get(&broker).copied().unwrap_or(0)
+    }
+
+    fn leaders(&self, broker: i32) -> usize {
+        self.leaders.get(&broker).copied().unwrap_or(0)
+    }
+
+    /// Records that position `pos` of a replica list changed from `from` to `to`.
+    fn replace(&mut self, pos: usize, from: i32, to: i32) {
+        *self.replicas.entry(from).or_default() -= 1;
+        *self.replicas.entry(to).or_default() += 1;
+        if pos == 0 {
+            *self.leaders.entry(from).or_default() -= 1;
+            *self.leaders.entry(to).or_default() += 1;
+        }
+    }
+
+    /// Least loaded broker that is not already in `replicas`. Leader load
+    /// decides first when the pick becomes a preferred leader.
+    fn least_loaded(&self, brokers: &[i32], replicas: &[i32], as_leader: bool) -> Option<i32> {
+        brokers
+            .iter()
+            .filter(|b| !replicas.contains(b))
+            .min_by_key(|b| {
+                let leaders = if as_leader { self.leaders(**b) } else { 0 };
+                (leaders, self.replicas(**b), **b)
+            })
+            .copied()
+    }
+}
+
+pub fn build_plan(goal: &ReassignmentGoal, topics: &[TopicInfo], brokers: &[i32]) -> Result<ReassignmentPlan> {
+    let mut brokers = brokers.to_vec();
+    brokers.sort();
+    let mut load = Load::new(topics, &brokers);
+
+    let mut partitions: Vec<(&str, &PartitionInfo)> = topics
+        .iter()
+        .flat_map(|t| t.partition_details.iter().map(move |p| (t.name.as_str(), p)))
+        .collect();
+    partitions.sort_by(|a, b| a.0.cmp(b.0).then(a.1.id.cmp(&b.1.id)));
+
+    let mut targets: Vec<Vec<i32>> = partitions.iter().map(|(_, p)| p.replicas.clone()).collect();
+
+    match goal {
+        ReassignmentGoal::DrainBroker(drained) => {
+            let eligible: Vec<i32> = brokers.iter().filter(|b| *b != drained).copied().collect();
+            for ((topic, p), target) in partitions.iter().zip(targets.iter_mut()) {
+                let Some(pos) = target.iter().position(|r| r == drained) else { continue };
+                let Some(replacement) = load.least_loaded(&eligible, target, pos == 0) else {
+                    bail!(
+                        "not enough brokers to move {}-{} off broker {}",
+                        topic, p.id, drained
+                    );
+                };
+                load.replace(pos, *drained, replacement);
+                target[pos] = replacement;
+            }
+        }
+        ReassignmentGoal::ChangeReplicationFactor { topic: name, replication_factor } => {
+            let rf = *replication_factor;
+            if rf == 0 || rf > brokers.len() {
+                bail!("replication factor must be between 1 and {}", brokers.len());
+            }
+            if !topics.iter().any(|t| &t.name == name) {
+                bail!("topic '{}' not found", name);
+            }
+            for ((topic, p), target) in partitions.iter().zip(targets.iter_mut()) {
+                if topic != name {
+                    continue;
+                }
+                while target.len() < rf {
+                    let Some(b) = load.least_loaded(&brokers, target, target.is_empty()) else {
+                        bail!("not enough brokers to give {}-{} {} replicas", topic, p.id, rf);
+                    };
+                    *load.replicas.entry(b).or_default() += 1;
+                    target.push(b);
+                }
+                while target.len() > rf {
+                    // Keep the preferred leader; drop the busiest follower
+                    let (idx, _) = target
+                        .iter()
+                        .enumerate()
+                        .skip(1)
+                        .max_by_key(|(_, b)| (load.replicas(**b), **b))
+                        .expect("follower exists while len > rf >= 1");
+                    let b = target.remove(idx);
+                    *load.replicas.entry(b).or_default() -= 1;
+                }
+            }
+        }
+        ReassignmentGoal::Rebalance => {
+            if brokers.is_empty() {
+                bail!("no brokers available");
+            }
+            let n = brokers.len();
+            let total: usize = targets.iter().map(|t| t.len()).sum();
+            let (floor, ceil) = (total / n, total.div_ceil(n));
+
+            for ((topic, p), target) in partitions.iter().zip(targets.iter_mut()) {
+                for pos in 0..target.len() {
+                    let current = target[pos];
+                    let known = brokers.contains(&current);
+                    let underloaded = brokers.iter().any(|b| load.replicas(*b) < floor);
+                    let overloaded = !known
+                        || load.replicas(current) > ceil
+                        || (load.replicas(current) > floor && underloaded);
+                    if !overloaded {
+                        continue;
+                    }
+                    match load.least_loaded(&brokers, target, false) {
+                        Some(b) if !known || load.replicas(b) + 1 < load.replicas(current) => {
+                            load.replace(pos, current, b);
+                            target[pos] = b;
+                        }
+                        None if !known => {
+                            bail!("not enough brokers to move {}-{} off missing broker {}", topic, p.id, current);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            let partition_count = targets.len();
+            let (lfloor, lceil) = (partition_count / n, partition_count.div_ceil(n));
+            for target in targets.iter_mut() {
+                let Some(&leader) = target.first() else { continue };
+                let underloaded = brokers.iter().any(|b| load.leaders(*b) < lfloor);
+                if load.leaders(leader) <= lceil && !(load.leaders(leader) > lfloor && underloaded) {
+                    continue;
+                }
+                let best = target
+                    .iter()
+                    .enumerate()
+                    .skip(1)
+                    .filter(|(_, b)| brokers.contains(b))
+                    .min_by_key(|(_, b)| (load.leaders(**b), **b))
+                    .map(|(i, b)| (i, *b));
+                if let Some((idx, b)) = best {
+                    if load.leaders(b) + 1 < load.leaders(leader) {
+                        *load.leaders.entry(leader).or_default() -= 1;
+                        *load.leaders.entry(b).or_default() += 1;
+                        target.swap(0, idx);
+                    }
+                }
+            }
+        }
+    }
+
+    let moves = partitions
+        .iter()
+        .zip(targets)
+        .filter(|((_, p), target)| &p.replicas != target)
+        .map(|((topic, p), target)| PartitionMove {
+            topic: topic.to_string(),
+            partition: p.id,
+            current: p.replicas.clone(),
+            target,
+        })
+        .collect();
+
+    Ok(ReassignmentPlan { goal: goal.clone(), moves })
+}
+
+/// Renders the plan in the `kafka-reassign-partitions` JSON format.
+pub fn to_json(plan: &ReassignmentPlan) -> String {
+    let partitions: Vec<serde_json::Value> = plan
+        .moves
+        .iter()
+        .map(|m| {
+            serde_json::json!({
+                "topic": m.topic,
+                "partition": m.partition,
+                "replicas": m.target,
+                "log_dirs": vec!["any"; m.target.len()],
+            })
+        })
+        .collect();
+    let doc = serde_json::json!({ "version": 1, "partitions": partitions });
+    serde_json::to_string_pretty(&doc).unwrap_or_default()
+}
+
+/// Writes the plan JSON into the current directory and returns its path.
+pub fn export(plan: &ReassignmentPlan) -> Result<PathBuf> {
+    let name = format!("reassignment-{}.json", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+    let path = std::env::current_dir().context("failed to resolve current directory")?.join(name);
+    std::fs::write(&path, to_json(plan)).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+pub fn move_status(planned: &PartitionMove, partition: &PartitionInfo) -> MoveStatus {
+    let sorted = |v: &[i32]| {
+        let mut v = v.to_vec();
+        v.sort();
+        v
+    };
+    let replicas = sorted(&partition.replicas);
+    if replicas == sorted(&planned.target) && planned.target.iter().all(|r| partition.isr.contains(r)) {
+        MoveStatus::Done
+    } else if replicas == sorted(&planned.current) {
+        MoveStatus::Pending
+    } else {
+        MoveStatus::InProgress
+    }
+}
+
+/// Locates `kafka-reassign-partitions` on PATH or under `$KAFKA_HOME/bin`.
+pub fn find_tool() -> Option<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|p| std::env::split_paths(&p).collect())
+        .unwrap_or_default();
+    if let Some(home) = std::env::var_os("KAFKA_HOME") {
+        dirs.push(PathBuf::from(home).join("bin"));
+    }
+    let names = ["kafka-reassign-partitions.sh", "kafka-reassign-partitions"];
+    dirs.iter()
+        .flat_map(|d| names.iter().map(move |n| d.join(n)))
+        .find(|p| p.is_file())
+}
+
+/// Java client properties matching the cluster's auth settings, for `--command-config`.
+pub fn command_config(cluster: &ClusterConfig) -> Result<String> {
+    fn jaas(module: &str, username: &str, password: &str) -> String {
+        format!(
+            "sasl.jaas.config={} required username=\"{}\" password=\"{}\";\n",
+            module,
+            username.replace('"', "\\\""),
+            password.replace('"', "\\\"")
+        )
+    }
+    fn pem(path: &str) -> Result<String> {
+        let contents = std::fs::read_to_string(path).with_context(|| format!("failed to read {}", path))?;
+        Ok(contents.trim_end().replace('\n', "\\n"))
+    }
+    fn oauth(token_endpoint: &str, client_id: &str, client_secret: &str, scope: Option<&str>) -> String {
+        let scope = scope.map(|s| format!(" scope=\"{}\"", s.replace('"', "\\\""))).unwrap_or_default();
+        format!(
+            "sasl.login.callback.handler.class={}\nsasl.oauthbearer.token.endpoint.url={}\nsasl.jaas.config={} required clientId=\"{}\" clientSecret=\"{}\"{};\n",
+            OAUTH_HANDLER,
+            token_endpoint,
+            OAUTH,
+            client_id.replace('"', "\\\""),
+            client_secret.replace('"', "\\\""),
+            scope
+        )
+    }
+    const PLAIN: &str = "org.apache.kafka.common.security.plain.PlainLoginModule";
+    const SCRAM: &str = "org.apache.kafka.common.security.scram.ScramLoginModule";
+    const OAUTH: &str = "org.apache.kafka.common.security.oauthbearer.OAuthBearerLoginModule";
+    const OAUTH_HANDLER: &str = "org.apache.kafka.common.security.oauthbearer.OAuthBearerLoginCallbackHandler";
+
+    let mut props = String::new();
+    let (protocol, mechanism, credentials, ca) = match &cluster.auth {
+        AuthConfig::None => return Ok(props),
+        AuthConfig::SaslPlain { username, password } => ("SASL_PLAINTEXT", Some("PLAIN"), Some(jaas(PLAIN, username, password)), None),
+        AuthConfig::SaslScram256 { username, password } => ("SASL_PLAINTEXT", Some("SCRAM-SHA-256"), Some(jaas(SCRAM, username, password)), None),
+        AuthConfig::SaslScram512 { username, password } => ("SASL_PLAINTEXT", Some("SCRAM-SHA-512"), Some(jaas(SCRAM, username, password)), None),
+        AuthConfig::SaslSslPlain { username, password, ca_cert } => ("SASL_SSL", Some("PLAIN"), Some(jaas(PLAIN, username, password)), ca_cert.as_ref()),
+        AuthConfig::SaslSslScram256 { username, password, ca_cert } => ("SASL_SSL", Some("SCRAM-SHA-256"), Some(jaas(SCRAM, username, password)), ca_cert.as_ref()),
+        AuthConfig::SaslSslScram512 { username, password, ca_cert } => ("SASL_SSL", Some("SCRAM-SHA-512"), Some(jaas(SCRAM, username, password)), ca_cert.as_ref()),
+        AuthConfig::SaslOAuthBearer { token_endpoint, client_id, client_secret, scope } => {
+            ("SASL_PLAINTEXT", Some("OAUTHBEARER"), Some(oauth(token_endpoint, client_id, client_secret, scope.as_deref())), None)
+        }
+        AuthConfig::SaslSslOAuthBearer { token_endpoint, client_id, client_secret, scope, ca_cert } => {
+            ("SASL_SSL", Some("OAUTHBEARER"), Some(oauth(token_endpoint, client_id, client_secret, scope.as_deref())), ca_cert.as_ref())
+        }
+        AuthConfig::SaslOAuthBearerUnsecured { principal } => {
+            let jaas = format!("sasl.jaas.config={} required unsecuredLoginStringClaim_sub=\"{}\";\n", OAUTH, principal.replace('"', "\\\""));
+            ("SASL_PLAINTEXT", Some("OAUTHBEARER"), Some(jaas), None)
+        }
+        AuthConfig::Ssl { ca_cert, client_cert, client_key } => {
+            if let (Some(cert), Some(key)) = (client_cert, client_key) {
+                props.push_str("ssl.keystore.type=PEM\n");
+                props.push_str(&format!("ssl.keystore.certificate.chain={}\n", pem(cert)?));
+                props.push_str(&format!("ssl.keystore.key={}\n", pem(key)?));
+            }
+            ("SSL", None, None, ca_cert.as_ref())
+        }
+    };
+    props.push_str(&format!("security.protocol={}\n", protocol));
+    if let Some(mechanism) = mechanism {
+        props.push_str(&format!("sasl.mechanism={}\n", mechanism));
+    }
+    if let Some(credentials) = credentials {
+        props.push_str(&credentials);
+    }
+    if let Some(ca) = ca {
+        props.push_str("ssl.truststore.type=PEM\n");
+        props.push_str(&format!("ssl.truststore.location={}\n", ca));
+    }
+    Ok(props)
+}
+
+/// Runs `kafka-reassign-partitions --execute` for the plan and returns its output.
+pub async fn submit(cluster: &ClusterConfig, plan: &ReassignmentPlan) -> Result<String> {
+    let Some(tool) = find_tool() else {
+        bail!("kafka-reassign-partitions not found on PATH or in $KAFKA_HOME/bin");
+    };
+    let props = command_config(cluster)?;
+    let dir = std::env::temp_dir();
+    let id = uuid::Uuid::new_v4();
+    let plan_path = dir.join(format!("kafka-eye-reassign-{}.json", id));
+    let props_path = dir.join(format!("kafka-eye-reassign-{}.properties", id));
+    // The properties file may hold credentials, so never leave it behind
+    let _files = RemoveOnDrop(vec![plan_path.clone(), props_path.clone()]);
+    write_private(&plan_path, &to_json(plan)).context("failed to write reassignment JSON")?;
+    write_private(&props_path, &props).context("failed to write client properties")?;
+
+    let output = tokio::process::Command::new(&tool)
+        .arg("--bootstrap-server")
+        .arg(&cluster.brokers)
+        .arg("--command-config")
+        .arg(&props_path)
+        .arg("--reassignment-json-file")
+        .arg(&plan_path)
+        .arg("--execute")
+        .output()
+        .await;
+
+    let output = output.with_context(|| format!("failed to run {}", tool.display()))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if !output.status.success() {
+        bail!("{}", if stderr.is_empty() { stdout } else { stderr });
+    }
+    Ok(stdout.lines().last().unwrap_or("Reassignment started").to_string())
+}
+
+/// Creates a new file readable by the current user only
+fn write_private(path: &std::path::Path, contents: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(contents.as_bytes())
+}
+
+/// Deletes the files when dropped, whichever way `submit` returns
+struct RemoveOnDrop(Vec<PathBuf>);
+
+impl Drop for RemoveOnDrop {
+    fn drop(&mut self) {
+        for path in &self.0 {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn topic(name: &str, assignments: &[&[i32]]) -> TopicInfo {
+        TopicInfo {
+            name: name.to_string(),
+            partitions: assignments.len(),
+            replication_factor: assignments.first().map_or(0, |r| r.len()),
+            partition_details: assignments
+                .iter()
+                .enumerate()
+                .map(|(i, replicas)| PartitionInfo {
+                    id: i as i32,
+                    leader: replicas[0],
+                    replicas: replicas.to_vec(),
+                    isr: replicas.to_vec(),
+                })
+                .collect(),
+        }
+    }
+
+    /// The replica list of every partition after the plan
+    fn after(plan: &ReassignmentPlan, topics: &[TopicInfo]) -> Vec<Vec<i32>> {
+        topics
+            .iter()
+            .flat_map(|t| &t.partition_details)
+            .map(|p| {
+                plan.moves
+                    .iter()
+                    .find(|m| m.partition == p.id)
+                    .map_or_else(|| p.replicas.clone(), |m| m.target.clone())
+            })
+            .collect()
+    }
+
+    fn assert_distinct(replicas: &[i32]) {
+        let mut sorted = replicas.to_vec();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), replicas.len(), "duplicate replica in {:?}", replicas);
+    }
+
+    #[test]
+    fn drain_moves_every_replica_off_the_broker() {
+        let topics = vec![topic("orders", &[&[1, 2], &[2, 3], &[3, 1], &[1, 3]])];
+        let plan = build_plan(&ReassignmentGoal::DrainBroker(1), &topics, &[1, 2, 3, 4]).unwrap();
+        assert_eq!(plan.moves.len(), 3);
+        for replicas in after(&plan, &topics) {
+            assert!(!replicas.contains(&1), "{:?} still on broker 1", replicas);
+            assert_distinct(&replicas);
+        }
+    }
+
+    #[test]
+    fn drain_without_a_spare_broker_fails() {
+        let topics = vec![topic("orders", &[&[1, 2], &[2, 1]])];
+        assert!(build_plan(&ReassignmentGoal::DrainBroker(1), &topics, &[1, 2]).is_err());
+    }
+
+    #[test]
+    fn growing_the_replication_factor_keeps_the_leader() {
+        let topics = vec![topic("orders", &[&[1], &[2], &[3]]), topic("other", &[&[1]])];
+        let goal = ReassignmentGoal::ChangeReplicationFactor {
+            topic: "orders".to_string(),
+            replication_factor: 3,
+        };
+        let plan = build_plan(&goal, &topics, &[1, 2, 3, 4]).unwrap();
+        assert_eq!(plan.moves.len(), 3);
+        for m in &plan.moves {
+            assert_eq!(m.topic, "orders");
+            assert_eq!(m.target.len(), 3);
+            assert_eq!(m.target[0], m.current[0]);
+            assert_distinct(&m.target);
+        }
+    }
+
+    #[test]
+    fn shrinking_the_replication_factor_keeps_the_leader() {
+        let topics = vec![topic("orders", &[&[1, 2, 3], &[2, 3, 1], &[3, 1, 2]])];
+        let goal = ReassignmentGoal::ChangeReplicationFactor {
+            topic: "orders".to_string(),
+            replication_factor: 1,
+        };
+        let plan = build_plan(&goal, &topics, &[1, 2, 3]).unwrap();
+        for m in &plan.moves {
+            assert_eq!(m.target, vec![m.current[0]]);
+        }
+    }
+
+    #[test]
+    fn impossible_replication_factors_fail() {
+        let topics = vec![topic("orders", &[&[1], &[2]])];
+        let goal = |rf| ReassignmentGoal::ChangeReplicationFactor {
+            topic: "orders".to_string(),
+            replication_factor: rf,
+        };
+        assert!(build_plan(&goal(0), &topics, &[1, 2]).is_err());
+        assert!(build_plan(&goal(3), &topics, &[1, 2]).is_err());
+        let missing = ReassignmentGoal::ChangeReplicationFactor {
+            topic: "missing".to_string(),
+            replication_factor: 1,
+        };
+        assert!(build_plan(&missing, &topics, &[1, 2]).is_err());
+    }
+
+    #[test]
+    fn rebalance_spreads_replicas_without_duplicates() {
+        let topics = vec![topic("orders", &[&[1, 2], &[1, 2], &[2, 1], &[1, 2], &[2, 1], &[1, 2]])];
+        let plan = build_plan(&ReassignmentGoal::Rebalance, &topics, &[1, 2, 3]).unwrap();
+        let replicas = after(&plan, &topics);
+        let mut counts: HashMap<i32, usize> = HashMap::new();
+        for r in &replicas {
+            assert_distinct(r);
+            for b in r {
+                *counts.entry(*b).or_default() += 1;
+            }
+        }
+        for b in [1, 2, 3] {
+            assert_eq!(counts.get(&b), Some(&4), "broker {} in {:?}", b, counts);
+        }
+    }
+
+    #[test]
+    fn rebalance_moves_replicas_off_missing_brokers() {
+        let topics = vec![topic("orders", &[&[9, 1], &[1, 2]])];
+        let plan = build_plan(&ReassignmentGoal::Rebalance, &topics, &[1, 2, 3]).unwrap();
+        for replicas in after(&plan, &topics) {
+            assert!(!replicas.contains(&9));
+            assert_distinct(&replicas);
+        }
+    }
+
+    #[test]
+    fn rebalance_without_room_for_a_missing_broker_fails() {
+        let topics = vec![topic("orders", &[&[9, 1]])];
+        assert!(build_plan(&ReassignmentGoal::Rebalance, &topics, &[1]).is_err());
+        assert!(build_plan(&ReassignmentGoal::Rebalance, &topics, &[]).is_err());
+    }
+}
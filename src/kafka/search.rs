@@ -0,0 +1,353 @@
+//! Reads of a topic outside the message browser, each on a dedicated
+//! consumer. `scan` reads every partition from the start to the end of a
+//! range and hands each record to a `ScanVisitor`. A search applies the
+//! filter and streams back only the matches. `fetch_around` reads a single
+//! record and its neighbours. These reads, like the ones built on them in
+//! `page`, `timeline`, `analysis` and `compacted`, block on the consumer, so
+//! the client runs them with `spawn_blocking`.
+
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
+use rdkafka::error::KafkaError;
+use rdkafka::{Offset, TopicPartitionList};
+use tokio::sync::mpsc;
+
+use crate::app::KafkaMessage;
+use crate::filter::Filter;
+use crate::kafka::client::{kafka_message, KafkaResponse};
+use crate::kafka::page;
+
+/// Stop collecting after this many matches; the scan is reported as truncated
+pub const MAX_MATCHES: usize = 10_000;
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// One end of a search range
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchBound {
+    /// The log start for `from`, the high watermark when the search starts for `to`
+    Edge,
+    Offset(i64),
+    /// Milliseconds since the epoch
+    Timestamp(i64),
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchRange {
+    pub from: SearchBound,
+    pub to: SearchBound,
+}
+
+impl SearchRange {
+    pub fn describe(&self) -> String {
+        let bound = |b: &SearchBound, edge: &str| match b {
+            SearchBound::Edge => edge.to_string(),
+            SearchBound::Offset(o) => format!("offset {}", o),
+            SearchBound::Timestamp(ts) => chrono::DateTime::from_timestamp_millis(*ts)
+                .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_else(|| ts.to_string()),
+        };
+        format!("{} → {}", bound(&self.from, "earliest"), bound(&self.to, "latest"))
+    }
+}
+
+/// Progress of the scan of one partition; `end` is exclusive
+#[derive(Debug, Clone)]
+pub struct PartitionScan {
+    pub partition: i32,
+    pub start: i64,
+    pub end: i64,
+    pub position: i64,
+    pub matches: usize,
+}
+
+impl PartitionScan {
+    pub fn done(&self) -> bool {
+        self.position >= self.end
+    }
+
+    pub fn total(&self) -> i64 {
+        (self.end - self.start).max(0)
+    }
+
+    pub fn scanned(&self) -> i64 {
+        (self.position.min(self.end) - self.start).max(0)
+    }
+
+    /// Fraction in 0.0..=1.0
+    pub fn progress(&self) -> f64 {
+        if self.total() == 0 {
+            1.0
+        } else {
+            self.scanned() as f64 / self.total() as f64
+        }
+    }
+}
+
+/// What a scan does with each record it reads
+pub trait ScanVisitor {
+    /// `Continue(true)` counts the record as a match of its partition,
+    /// `Break` stops the scan with the reason.
+    fn visit(&mut self, message: KafkaMessage) -> ControlFlow<String, bool>;
+
+    /// Called periodically while scanning and once at the end
+    fn progress(&mut self, scans: &[PartitionScan]);
+}
+
+/// Reads every partition of `topic` from the start to the end of `range`,
+/// handing each record to `visitor`, until `cancel` is set or the visitor
+/// stops. Returns the final per-partition progress and why it stopped
+/// early, if it did.
+pub fn scan<C: ConsumerContext>(
+    consumer: &BaseConsumer<C>,
+    topic: &str,
+    range: &SearchRange,
+    cancel: &AtomicBool,
+    visitor: &mut impl ScanVisitor,
+    resp_tx: &mpsc::UnboundedSender<KafkaResponse>,
+) -> Result<(Vec<PartitionScan>, Option<String>), String> {
+    let (partitions, watermarks) = partitions_with_watermarks(consumer, topic)?;
+    let from = resolve(consumer, topic, &partitions, &watermarks, &range.from, true)?;
+    let to = resolve(consumer, topic, &partitions, &watermarks, &range.to, false)?;
+    let mut scans: Vec<PartitionScan> = partitions
+        .iter()
+        .zip(from.iter().zip(to.iter()))
+        .map(|(&partition, (&start, &end))| PartitionScan {
+            partition,
+            start,
+            end: end.max(start),
+            position: start,
+            matches: 0,
+        })
+        .collect();
+
+    let mut tpl = TopicPartitionList::new();
+    for scan in scans.iter().filter(|s| !s.done()) {
+        tpl.add_partition_offset(topic, scan.partition, Offset::Offset(scan.start))
+            .map_err(|e| format!("Failed to assign partition {}: {}", scan.partition, e))?;
+    }
+    consumer.assign(&tpl).map_err(|e| format!("Failed to assign partitions: {}", e))?;
+
+    visitor.progress(&scans);
+    let mut last_report = Instant::now();
+    let mut stopped = None;
+    while !scans.iter().all(|s| s.done()) {
+        if cancel.load(Ordering::Relaxed) {
+            stopped = Some("cancelled".to_string());
+            break;
+        }
+
+        match consumer.poll(Duration::from_millis(100)) {
+            Some(Ok(msg)) => {
+                use rdkafka::Message;
+                if let Some(scan) = scans.iter_mut().find(|s| s.partition == msg.partition()) {
+                    if msg.offset() < scan.end {
+                        scan.position = msg.offset() + 1;
+                        match visitor.visit(kafka_message(&msg)) {
+                            ControlFlow::Continue(true) => scan.matches += 1,
+                            ControlFlow::Continue(false) => {}
+                            ControlFlow::Break(reason) => {
+                                stopped = Some(reason);
+                                break;
+                            }
+                        }
+                    } else {
+                        scan.position = scan.end;
+                    }
+                    if scan.done() {
+                        pause(consumer, topic, scan.partition);
+                    }
+                }
+            }
+            // The end of the log can come before the end of the range
+            // when the last offsets were compacted away or are markers
+            Some(Err(KafkaError::PartitionEOF(partition))) => {
+                if let Some(scan) = scans.iter_mut().find(|s| s.partition == partition) {
+                    scan.position = scan.end;
+                    pause(consumer, topic, partition);
+                }
+            }
+            Some(Err(e)) => {
+                let _ = resp_tx.send(KafkaResponse::Error(format!("Scan consumer error: {}", e)));
+            }
+            None => {}
+        }
+
+        if last_report.elapsed() >= PROGRESS_INTERVAL {
+            last_report = Instant::now();
+            visitor.progress(&scans);
+        }
+    }
+    visitor.progress(&scans);
+    Ok((scans, stopped))
+}
+
+/// Streams the records matching a filter back to the UI
+struct Search<'a> {
+    id: u64,
+    filter: &'a Filter,
+    matches: Vec<KafkaMessage>,
+    matched: usize,
+    resp_tx: &'a mpsc::UnboundedSender<KafkaResponse>,
+}
+
+impl ScanVisitor for Search<'_> {
+    fn visit(&mut self, message: KafkaMessage) -> ControlFlow<String, bool> {
+        if self.matched >= MAX_MATCHES {
+            return ControlFlow::Break(format!("stopped after {} matches", MAX_MATCHES));
+        }
+        if !self.filter.matches(&message) {
+            return ControlFlow::Continue(false);
+        }
+        self.matched += 1;
+        self.matches.push(message);
+        ControlFlow::Continue(true)
+    }
+
+    fn progress(&mut self, scans: &[PartitionScan]) {
+        if !self.matches.is_empty() {
+            let _ = self.resp_tx.send(KafkaResponse::SearchMatches {
+                id: self.id,
+                matches: std::mem::take(&mut self.matches),
+            });
+        }
+        let _ = self.resp_tx.send(KafkaResponse::SearchProgress { id: self.id, scans: scans.to_vec() });
+    }
+}
+
+/// Runs the search to the end of the range, until `cancel` is set or
+/// `MAX_MATCHES` is reached.
+pub fn run<C: ConsumerContext>(
+    consumer: &BaseConsumer<C>,
+    id: u64,
+    topic: &str,
+    filter: &Filter,
+    range: &SearchRange,
+    cancel: &AtomicBool,
+    resp_tx: &mpsc::UnboundedSender<KafkaResponse>,
+) -> Result<(), String> {
+    let mut search = Search {
+        id,
+        filter,
+        matches: Vec::new(),
+        matched: 0,
+        resp_tx,
+    };
+    let (scans, stopped) = scan(consumer, topic, range, cancel, &mut search, resp_tx)?;
+    let _ = resp_tx.send(KafkaResponse::SearchFinished {
+        id,
+        scanned: scans.iter().map(|s| s.scanned() as u64).sum(),
+        matched: search.matched,
+        stopped,
+    });
+    Ok(())
+}
+
+/// The record at `offset` and up to `context` records before and after it,
+/// read with a short-lived assignment of that one partition.
+pub fn fetch_around<C: ConsumerContext>(
+    consumer: &BaseConsumer<C>,
+    topic: &str,
+    partition: i32,
+    offset: i64,
+    context: i64,
+) -> Result<Vec<KafkaMessage>, String> {
+    let (low, high) = consumer
+        .fetch_watermarks(topic, partition, Duration::from_secs(10))
+        .map_err(|e| format!("Failed to fetch watermarks of partition {}: {}", partition, e))?;
+    if offset < low || offset >= high {
+        return Err(format!(
+            "offset {} is outside partition {} (offsets {} to {})",
+            offset,
+            partition,
+            low,
+            high - 1
+        ));
+    }
+    let start = (offset - context).max(low);
+    let end = (offset + context + 1).min(high);
+
+    let read = page::read_ranges(consumer, topic, &[partition], &[(start, end)], page::READ_DEADLINE)?;
+    if !read.unfinished.is_empty() {
+        return Err(format!("timed out reading partition {} around offset {}", partition, offset));
+    }
+    if read.records.is_empty() {
+        return Err(format!("no records read from partition {} around offset {}", partition, offset));
+    }
+    Ok(read.records)
+}
+
+pub fn pause<C: ConsumerContext>(consumer: &BaseConsumer<C>, topic: &str, partition: i32) {
+    let mut tpl = TopicPartitionList::new();
+    tpl.add_partition(topic, partition);
+    let _ = consumer.pause(&tpl);
+}
+
+/// Low and high watermark of every partition, in partition order
+pub type Watermarks = Vec<(i64, i64)>;
+
+/// The partitions of `topic` in order, with their low and high watermarks.
+pub fn partitions_with_watermarks<C: ConsumerContext>(
+    consumer: &BaseConsumer<C>,
+    topic: &str,
+) -> Result<(Vec<i32>, Watermarks), String> {
+    let timeout = Duration::from_secs(10);
+    let meta = consumer
+        .fetch_metadata(Some(topic), timeout)
+        .map_err(|e| format!("Failed to fetch metadata: {}", e))?;
+    let topic_meta = meta.topics().first().ok_or_else(|| format!("Topic '{}' not found", topic))?;
+    let mut partitions: Vec<i32> = topic_meta.partitions().iter().map(|p| p.id()).collect();
+    partitions.sort();
+
+    let mut watermarks = Vec::new();
+    for &p in &partitions {
+        let marks = consumer
+            .fetch_watermarks(topic, p, timeout)
+            .map_err(|e| format!("Failed to fetch watermarks of partition {}: {}", p, e))?;
+        watermarks.push(marks);
+    }
+    Ok((partitions, watermarks))
+}
+
+/// Offset of a bound in every partition, clamped to the watermarks.
+pub fn resolve<C: ConsumerContext>(
+    consumer: &BaseConsumer<C>,
+    topic: &str,
+    partitions: &[i32],
+    watermarks: &[(i64, i64)],
+    bound: &SearchBound,
+    is_start: bool,
+) -> Result<Vec<i64>, String> {
+    match bound {
+        SearchBound::Edge => Ok(watermarks.iter().map(|&(low, high)| if is_start { low } else { high }).collect()),
+        // The end offset is inclusive in the dialog, exclusive here
+        SearchBound::Offset(offset) => Ok(watermarks
+            .iter()
+            .map(|&(low, high)| if is_start { *offset } else { offset + 1 }.clamp(low, high))
+            .collect()),
+        SearchBound::Timestamp(ts) => {
+            let mut tpl = TopicPartitionList::new();
+            for &p in partitions {
+                tpl.add_partition_offset(topic, p, Offset::Offset(*ts))
+                    .map_err(|e| format!("Failed to look up offsets: {}", e))?;
+            }
+            let found = consumer
+                .offsets_for_times(tpl, Duration::from_secs(10))
+                .map_err(|e| format!("Failed to look up offsets for time: {}", e))?;
+            Ok(partitions
+                .iter()
+                .zip(watermarks)
+                .map(|(&p, &(low, high))| {
+                    // No message at or after the time means the partition's end
+                    match found.find_partition(topic, p).map(|e| e.offset()) {
+                        Some(Offset::Offset(o)) => o.clamp(low, high),
+                        _ => high,
+                    }
+                })
+                .collect())
+        }
+    }
+}
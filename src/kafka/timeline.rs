@@ -0,0 +1,134 @@
+//! Message counts per time bucket. The offsets of every bucket boundary are
+//! looked up with offsets-for-times, and a bucket's count is the distance
+//! between its edges, so nothing is consumed. Offsets freed by compaction
+//! and transaction markers are counted like records.
+
+use std::time::Duration;
+
+use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
+use rdkafka::{Offset, TopicPartitionList};
+
+use crate::kafka::search;
+
+/// Each bucket is one lookup, so wide windows need hour buckets
+pub const MAX_BUCKETS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BucketSize {
+    Minute,
+    Hour,
+}
+
+impl BucketSize {
+    pub fn millis(&self) -> i64 {
+        match self {
+            BucketSize::Minute => 60_000,
+            BucketSize::Hour => 3_600_000,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BucketSize::Minute => "minute",
+            BucketSize::Hour => "hour",
+        }
+    }
+
+    pub fn toggle(&self) -> Self {
+        match self {
+            BucketSize::Minute => BucketSize::Hour,
+            BucketSize::Hour => BucketSize::Minute,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Timeline {
+    /// Start of the first bucket, in milliseconds since the epoch
+    pub from: i64,
+    pub bucket: BucketSize,
+    pub partitions: Vec<i32>,
+    /// Per bucket, the count of every partition in `partitions` order
+    pub counts: Vec<Vec<u64>>,
+}
+
+impl Timeline {
+    pub fn bucket_start(&self, index: usize) -> i64 {
+        self.from + index as i64 * self.bucket.millis()
+    }
+
+    /// Count of one bucket, for one partition or all of them
+    pub fn count(&self, index: usize, partition: Option<i32>) -> u64 {
+        let Some(counts) = self.counts.get(index) else { return 0 };
+        match partition {
+            None => counts.iter().sum(),
+            Some(p) => self
+                .partitions
+                .iter()
+                .position(|&id| id == p)
+                .map(|i| counts[i])
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// Counts the messages of `topic` between two times, in whole buckets.
+pub fn fetch<C: ConsumerContext>(
+    consumer: &BaseConsumer<C>,
+    topic: &str,
+    from: i64,
+    to: i64,
+    bucket: BucketSize,
+) -> Result<Timeline, String> {
+    let size = bucket.millis();
+    let from = from.div_euclid(size) * size;
+    let buckets = (to - from + size - 1).div_euclid(size).max(1) as usize;
+    if buckets > MAX_BUCKETS {
+        return Err(format!(
+            "{} {} buckets is too many, the limit is {}",
+            buckets,
+            bucket.label(),
+            MAX_BUCKETS
+        ));
+    }
+
+    let (partitions, watermarks) = search::partitions_with_watermarks(consumer, topic)?;
+
+    // Offset of every boundary in every partition
+    let mut edges: Vec<Vec<i64>> = Vec::with_capacity(buckets + 1);
+    for i in 0..=buckets {
+        let ts = from + i as i64 * size;
+        let mut tpl = TopicPartitionList::new();
+        for &p in &partitions {
+            tpl.add_partition_offset(topic, p, Offset::Offset(ts))
+                .map_err(|e| format!("Failed to look up offsets: {}", e))?;
+        }
+        let found = consumer
+            .offsets_for_times(tpl, Duration::from_secs(10))
+            .map_err(|e| format!("Failed to look up offsets for time: {}", e))?;
+        edges.push(
+            partitions
+                .iter()
+                .zip(&watermarks)
+                .map(|(&p, &(low, high))| {
+                    // No message at or after the time means the partition's end
+                    match found.find_partition(topic, p).map(|e| e.offset()) {
+                        Some(Offset::Offset(o)) => o.clamp(low, high),
+                        _ => high,
+                    }
+                })
+                .collect(),
+        );
+    }
+
+    let counts = edges
+        .windows(2)
+        .map(|pair| pair[0].iter().zip(&pair[1]).map(|(start, end)| (end - start).max(0) as u64).collect())
+        .collect();
+    Ok(Timeline {
+        from,
+        bucket,
+        partitions,
+        counts,
+    })
+}
@@ -1,25 +1,74 @@
 mod app;
+mod audit;
 mod config;
+mod crypto;
 mod event;
+mod filter;
+mod import;
 mod kafka;
+mod secrets;
 mod tui;
 mod ui;
 
 use app::*;
-use config::AppConfig;
-use crossterm::event::{KeyCode, KeyEvent};
+use clap::{Parser, Subcommand};
+use config::{AppConfig, ClusterConfig, ConfigError};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use event::Event;
+use kafka::analysis;
 use kafka::client::{KafkaCommand, KafkaResponse};
+use kafka::page::PageAnchor;
+use kafka::reassign::{self, MoveStatus, ReassignmentGoal};
+use kafka::search::SearchBound;
 use std::collections::HashMap;
 use tokio::sync::mpsc;
 
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+}
+
+#[derive(Subcommand)]
+enum CliCommand {
+    /// Encrypt config.toml with a master passphrase, or change the passphrase
+    EncryptConfig,
+    /// Write config.toml back as plaintext
+    DecryptConfig,
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let config = AppConfig::load()?;
-    let mut app = App::new(config);
+    let cli = Cli::parse();
+    match cli.command {
+        Some(CliCommand::EncryptConfig) => return config::encrypt_config(),
+        Some(CliCommand::DecryptConfig) => return config::decrypt_config(),
+        None => {}
+    }
+
+    // Anything but a problem with the file itself (e.g. a wrong passphrase)
+    // still ends the program before the terminal is taken over
+    let loaded = match AppConfig::load() {
+        Ok(config) => Ok(config),
+        Err(e) => Err(e.downcast::<ConfigError>()?),
+    };
     let mut terminal = tui::init()?;
     let mut events = event::EventHandler::new(100);
 
+    let config = match loaded {
+        Ok(config) => config,
+        Err(error) => match config_error_screen(&mut terminal, &mut events, error).await {
+            Ok(Some(config)) => config,
+            Ok(None) => return tui::restore(&mut terminal),
+            Err(e) => {
+                tui::restore(&mut terminal)?;
+                return Err(e);
+            }
+        },
+    };
+    let mut app = App::new(config);
+
     let (kafka_tx, mut kafka_rx) = kafka::client::spawn_kafka_backend();
 
     let result = run_app(&mut terminal, &mut app, &mut events, &kafka_tx, &mut kafka_rx).await;
@@ -28,6 +77,37 @@ async fn main() -> anyhow::Result<()> {
     result
 }
 
+/// Shown instead of the app when config.toml is broken. Returns the config
+/// once it loads (or the user continues despite validation issues), or
+/// `None` to quit.
+async fn config_error_screen(
+    terminal: &mut tui::Terminal,
+    events: &mut event::EventHandler,
+    mut error: ConfigError,
+) -> anyhow::Result<Option<AppConfig>> {
+    let mut note = None;
+    loop {
+        terminal.draw(|frame| ui::render_config_error(&error, note, frame))?;
+        let Event::Key(key) = events.next().await? else { continue };
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(None),
+            KeyCode::Char('c') if error.config.is_some() => return Ok(error.config.take().map(|c| *c)),
+            // Reloading would need the passphrase prompt, which can't run inside the TUI
+            KeyCode::Char('r') if error.encrypted => {
+                note = Some("The file is encrypted: run `kafka-eye decrypt-config`, fix it and restart.");
+            }
+            KeyCode::Char('r') => match AppConfig::load() {
+                Ok(config) => return Ok(Some(config)),
+                Err(e) => match e.downcast::<ConfigError>() {
+                    Ok(reloaded) => error = reloaded,
+                    Err(e) => return Err(e),
+                },
+            },
+            _ => {}
+        }
+    }
+}
+
 async fn run_app(
     terminal: &mut tui::Terminal,
     app: &mut App,
@@ -47,7 +127,8 @@ async fn run_app(
         match events.next().await? {
             Event::Key(key) => handle_key_event(app, key, kafka_tx),
             Event::Tick => {
-                // Periodic tasks could go here
+                app.messages.stats.roll(std::time::Instant::now());
+                auto_refresh(app, kafka_tx);
             }
             Event::Resize(_, _) => {
                 // Terminal will auto-resize on next draw
@@ -58,22 +139,80 @@ async fn run_app(
     Ok(())
 }
 
+/// Re-fetches the current view once its refresh interval has passed.
+/// Paused while a dialog is open so input isn't disturbed.
+fn auto_refresh(app: &mut App, kafka_tx: &mpsc::UnboundedSender<KafkaCommand>) {
+    if app.active_cluster.is_none() || app.dialog.is_some() {
+        return;
+    }
+    let interval = match app.refresh_interval() {
+        Some(interval) => interval,
+        None => return,
+    };
+    if let Some(last) = app.last_refresh {
+        if last.elapsed() < interval {
+            return;
+        }
+    }
+    let command = match &app.route {
+        Route::Dashboard => KafkaCommand::FetchMetadata,
+        Route::Brokers => KafkaCommand::FetchBrokers,
+        Route::Topics => KafkaCommand::FetchTopics,
+        Route::TopicDetail(name) => KafkaCommand::FetchTopicDetail(name.clone()),
+        Route::ConsumerGroups => KafkaCommand::FetchConsumerGroups,
+        Route::ConsumerGroupDetail(name) => KafkaCommand::FetchConsumerGroupDetail(name.clone()),
+        Route::Acls => KafkaCommand::FetchAcls,
+        _ => return,
+    };
+    app.last_refresh = Some(std::time::Instant::now());
+    app.auto_refreshing = true;
+    let _ = kafka_tx.send(command);
+}
+
 fn handle_kafka_response(app: &mut App, response: KafkaResponse) {
     match response {
         KafkaResponse::Connected(name) => {
             app.log_info(&format!("Connected to cluster: {}", name));
             app.route = Route::Dashboard;
             app.focus = Focus::Sidebar;
+            app.connection = ConnectionStatus::Connected;
+            // The first tick fetches the dashboard
+            app.last_refresh = None;
+            app.last_updated = None;
         }
         KafkaResponse::Disconnected => {
             app.log_info("Disconnected from cluster");
             app.route = Route::ClusterSelect;
             app.active_cluster = None;
+            app.client_stats = ClientStatsState::default();
         }
         KafkaResponse::ConnectionFailed(msg) => {
             app.log_error(&format!("Connection failed: {}", msg));
         }
+        KafkaResponse::ConnectionStatus(status) => {
+            match &status {
+                ConnectionStatus::Connected => {
+                    app.log_info("Connection healthy");
+                    // Anything on screen may be stale after an outage
+                    app.last_refresh = None;
+                }
+                ConnectionStatus::Degraded(reason) => {
+                    app.log_error(&format!("Connection degraded: {}", reason));
+                }
+                ConnectionStatus::Reconnecting { attempt, retry_in_secs } => {
+                    app.log_error(&format!(
+                        "Brokers unreachable, reconnecting (attempt {}, next try in {}s)",
+                        attempt, retry_in_secs
+                    ));
+                }
+            }
+            app.connection = status;
+        }
+        KafkaResponse::ClientStats(stats) => {
+            app.client_stats.update(stats);
+        }
         KafkaResponse::MetadataUpdate {
+            cluster_id,
             controller_id,
             broker_count,
             brokers_online,
@@ -81,6 +220,7 @@ fn handle_kafka_response(app: &mut App, response: KafkaResponse) {
             partition_count,
         } => {
             app.dashboard = DashboardState {
+                cluster_id,
                 controller_id,
                 broker_count,
                 brokers_online,
@@ -88,21 +228,51 @@ fn handle_kafka_response(app: &mut App, response: KafkaResponse) {
                 partition_count,
                 loading: false,
             };
-            app.log_info("Metadata refreshed");
+            app.data_loaded("Metadata refreshed");
+        }
+        KafkaResponse::BrokerList(brokers) => {
+            app.brokers.brokers = brokers;
+            app.brokers.loading = false;
+            if app.brokers.selected >= app.brokers.brokers.len() {
+                app.brokers.selected = 0;
+            }
+            let message = format!("Loaded {} brokers", app.brokers.brokers.len());
+            app.data_loaded(&message);
+        }
+        KafkaResponse::BrokerConfig { broker_id, entries } => {
+            app.broker_detail = BrokerDetailState {
+                broker_id,
+                config: entries,
+                selected: 0,
+                loading: false,
+            };
+            app.log_info(&format!("Loaded config for broker {}", broker_id));
         }
         KafkaResponse::TopicList(topics) => {
             app.topics.topics = topics;
             app.topics.loading = false;
-            app.log_info(&format!("Loaded {} topics", app.topics.topics.len()));
+            let message = format!("Loaded {} topics", app.topics.topics.len());
+            app.data_loaded(&message);
         }
         KafkaResponse::TopicDetail { name, partitions } => {
+            // A refresh of the same topic keeps the cursor and marks in place
+            let (selected_partition, marked_partitions) = if app.topic_detail.topic_name == name {
+                let marked = app.topic_detail.marked_partitions.iter()
+                    .copied()
+                    .filter(|id| partitions.iter().any(|p| p.id == *id))
+                    .collect();
+                (app.topic_detail.selected_partition.min(partitions.len().saturating_sub(1)), marked)
+            } else {
+                (0, Vec::new())
+            };
             app.topic_detail = TopicDetailState {
                 topic_name: name.clone(),
                 partitions,
                 config: Vec::new(),
-                selected_partition: 0,
+                selected_partition,
+                marked_partitions,
             };
-            app.log_info(&format!("Loaded detail for topic: {}", name));
+            app.data_loaded(&format!("Loaded detail for topic: {}", name));
         }
         KafkaResponse::TopicCreated(name) => {
             app.log_info(&format!("Topic '{}' created successfully", name));
@@ -112,8 +282,67 @@ fn handle_kafka_response(app: &mut App, response: KafkaResponse) {
             app.log_info(&format!("Topic '{}' deleted successfully", name));
             app.dialog = None;
         }
+        KafkaResponse::LeaderElectionResult { topic, results } => {
+            let failed = results.iter().filter(|r| r.outcome.is_err()).count();
+            if failed == 0 {
+                app.log_info(&format!("Preferred leader election finished for '{}'", topic));
+            } else if let (true, Some(Err(e))) = (failed == results.len(), results.first().map(|r| &r.outcome)) {
+                app.log_error(&format!("Preferred leader election failed for '{}': {}", topic, e));
+            } else {
+                app.log_error(&format!("Preferred leader election failed for {} partition(s) of '{}'", failed, topic));
+            }
+            if let Some(Dialog::ElectLeaders(ref mut d)) = app.dialog {
+                d.running = false;
+                d.results = Some(results);
+            }
+        }
+        KafkaResponse::ReassignmentPlanned(plan) => {
+            if plan.moves.is_empty() {
+                app.log_info("Reassignment plan is empty: nothing to move");
+            } else {
+                app.log_info(&format!("Planned {} partition move(s)", plan.moves.len()));
+            }
+            app.reassignment = ReassignmentState {
+                plan: Some(plan),
+                ..Default::default()
+            };
+        }
+        KafkaResponse::ReassignmentSubmitted(output) => {
+            app.reassignment.submitted = true;
+            app.log_info(&format!("Reassignment submitted: {}", output));
+        }
+        KafkaResponse::ReassignmentProgress(progress) => {
+            let done = progress.iter().filter(|p| p.status == MoveStatus::Done).count();
+            app.log_info(&format!("Reassignment progress: {}/{} partitions done", done, progress.len()));
+            app.reassignment.progress = progress;
+        }
+        KafkaResponse::AclList(bindings) => {
+            app.acls.loading = false;
+            app.acls.bindings = bindings;
+            let len = app.acls.filtered_bindings().len();
+            if app.acls.selected >= len {
+                app.acls.selected = len.saturating_sub(1);
+            }
+            let message = format!("Loaded {} ACL bindings", app.acls.bindings.len());
+            app.data_loaded(&message);
+        }
+        KafkaResponse::AclCreated(binding) => {
+            app.log_info(&format!("ACL created: {}", binding.describe()));
+        }
+        KafkaResponse::AclDeleted { binding, count } => {
+            if count == 0 {
+                app.log_error(&format!("No ACL matched: {}", binding.describe()));
+            } else {
+                app.log_info(&format!("Deleted {} ACL(s): {}", count, binding.describe()));
+            }
+        }
         KafkaResponse::Messages(msgs) => {
+            // Late records from before page mode was entered
+            if app.messages.paged {
+                return;
+            }
             for msg in msgs {
+                app.messages.stats.record(&msg);
                 app.messages.messages.push(msg);
             }
             // Keep only last 1000 messages
@@ -128,16 +357,152 @@ fn handle_kafka_response(app: &mut App, response: KafkaResponse) {
                 }
             }
         }
+        KafkaResponse::Analysis { topic, sample, analysis } => {
+            if topic != app.analysis.topic || sample != app.analysis.sample {
+                return;
+            }
+            app.analysis.loading = false;
+            match analysis {
+                Ok(analysis) => {
+                    app.log_info(&format!(
+                        "Analysed {}: {} record(s) sampled, {} distinct key(s), skew {:.2}",
+                        topic, analysis.sampled, analysis.distinct_keys, analysis.skew
+                    ));
+                    if analysis.truncated() > 0 {
+                        app.log(
+                            LogLevel::Warn,
+                            &format!("{} partition(s) of {} were only partly sampled before the read deadline", analysis.truncated(), topic),
+                        );
+                    }
+                    app.analysis.selected = 0;
+                    app.analysis.analysis = Some(analysis);
+                }
+                Err(e) => {
+                    app.log_error(&format!("Analysis failed: {}", e));
+                    app.analysis.error = Some(e);
+                }
+            }
+        }
+        KafkaResponse::Timeline { topic, timeline } => {
+            if topic != app.timeline.topic {
+                return;
+            }
+            app.timeline.loading = false;
+            match timeline {
+                Ok(timeline) => {
+                    let total: u64 = (0..timeline.counts.len()).map(|i| timeline.count(i, None)).sum();
+                    app.log_info(&format!("Timeline of {}: {} message(s) in {} bucket(s)", topic, total, timeline.counts.len()));
+                    app.timeline.selected = timeline.counts.len().saturating_sub(1);
+                    app.timeline.timeline = Some(timeline);
+                }
+                Err(e) => {
+                    app.log_error(&format!("Timeline failed: {}", e));
+                    app.timeline.error = Some(e);
+                }
+            }
+        }
+        KafkaResponse::ConsumerPositions(positions) => {
+            app.messages.stats.update_positions(positions);
+        }
+        KafkaResponse::Page { topic, page } => {
+            if app.route != Route::Messages(topic) || !app.messages.paged {
+                return;
+            }
+            let messages = &mut app.messages;
+            messages.messages = page.records.clone();
+            messages.selected = 0;
+            messages.auto_scroll = false;
+            messages.target = None;
+            messages.page = Some(page);
+        }
+        KafkaResponse::Record { topic, partition, offset, records } => {
+            if app.route != Route::Messages(topic) {
+                return;
+            }
+            let messages = &mut app.messages;
+            messages.messages = records;
+            messages.paged = false;
+            messages.page = None;
+            messages.search_query.clear();
+            messages.update_filter();
+            messages.auto_scroll = false;
+            messages.target = Some((partition, offset));
+            match messages.messages.iter().position(|m| m.offset >= offset) {
+                Some(index) if messages.messages[index].offset == offset => {
+                    messages.selected = index;
+                    messages.show_detail = true;
+                    app.log_info(&format!("Showing partition {} offset {}", partition, offset));
+                }
+                found => {
+                    // Compaction can leave gaps, so show where it would have been
+                    messages.selected = found.unwrap_or(messages.messages.len().saturating_sub(1));
+                    messages.show_detail = false;
+                    app.log(LogLevel::Warn, &format!("Partition {} has no record at offset {}", partition, offset));
+                }
+            }
+        }
+        KafkaResponse::SearchProgress { id, scans } if id == app.search.id => {
+            app.search.scans = scans;
+        }
+        KafkaResponse::SearchMatches { id, matches } if id == app.search.id => {
+            app.search.matches.extend(matches);
+        }
+        KafkaResponse::SearchFinished { id, scanned, matched, stopped } if id == app.search.id => {
+            app.search.running = false;
+            let summary = match stopped {
+                Some(reason) => format!("Search {}: {} match(es) in {} message(s)", reason, matched, scanned),
+                None => format!("Search finished: {} match(es) in {} message(s)", matched, scanned),
+            };
+            app.log_info(&summary);
+            app.search.summary = Some(summary);
+        }
+        // Late results of a search that was replaced by a newer one
+        KafkaResponse::SearchProgress { .. } | KafkaResponse::SearchMatches { .. } | KafkaResponse::SearchFinished { .. } => {}
+        KafkaResponse::TableProgress { id, scans, keys } if id == app.topic_table.id => {
+            app.topic_table.scans = scans;
+            app.topic_table.keys = keys;
+        }
+        KafkaResponse::TableFinished { id, rows, summary } if id == app.topic_table.id => {
+            let table = &mut app.topic_table;
+            table.running = false;
+            table.keys = rows.len();
+            table.rows = rows;
+            table.selected = 0;
+            let message = match summary.stopped {
+                Some(ref reason) => format!("Table of {} {}: {} key(s)", table.topic, reason, table.keys),
+                None => format!(
+                    "Table of {}: {} key(s) from {} record(s), {} deleted",
+                    table.topic, table.keys, summary.scanned, summary.deleted
+                ),
+            };
+            table.summary = Some(summary);
+            app.log_info(&message);
+        }
+        KafkaResponse::TableCleanupPolicy { id, policy } if id == app.topic_table.id => {
+            app.topic_table.cleanup_policy = Some(policy.clone());
+            if !app.topic_table.is_compacted() {
+                let message = format!(
+                    "{} has cleanup.policy={}: the table reads every record, not just the latest per key",
+                    app.topic_table.topic, policy
+                );
+                app.status_message = message.clone();
+                app.log(LogLevel::Warn, &message);
+            }
+        }
+        KafkaResponse::TableProgress { .. } | KafkaResponse::TableFinished { .. } | KafkaResponse::TableCleanupPolicy { .. } => {}
         KafkaResponse::ConsumerGroupList(groups) => {
             app.consumer_groups.groups = groups;
             app.consumer_groups.loading = false;
-            app.log_info(&format!("Loaded {} consumer groups", app.consumer_groups.groups.len()));
+            let message = format!("Loaded {} consumer groups", app.consumer_groups.groups.len());
+            app.data_loaded(&message);
         }
         KafkaResponse::ConsumerGroupDetail(info) => {
             // Update the group in the list
+            let message = format!("Loaded detail for group: {}", info.name);
             if let Some(existing) = app.consumer_groups.groups.iter_mut().find(|g| g.name == info.name) {
                 *existing = info;
             }
+            app.data_loaded(&message);
         }
         KafkaResponse::MessageProduced {
             topic,
@@ -153,6 +518,19 @@ fn handle_kafka_response(app: &mut App, response: KafkaResponse) {
             app.log_info(&format!("Message produced to {}[{}]@{}", topic, partition, offset));
         }
         KafkaResponse::Error(msg) => {
+            if let Some(Dialog::ProduceMessage(ref mut d)) = app.dialog {
+                if d.result_message.as_deref() == Some("⏳ Sending...") {
+                    d.result_message = Some(format!("✗ {}", msg));
+                }
+            }
+            app.dashboard.loading = false;
+            app.brokers.loading = false;
+            app.broker_detail.loading = false;
+            app.topics.loading = false;
+            app.consumer_groups.loading = false;
+            app.reassignment.loading = false;
+            app.acls.loading = false;
+            app.auto_refreshing = false;
             app.log_error(&msg);
         }
         KafkaResponse::ConnectionTestResult {
@@ -205,11 +583,21 @@ fn handle_key_event(app: &mut App, key: KeyEvent, kafka_tx: &mpsc::UnboundedSend
     match &app.route {
         Route::ClusterSelect => handle_cluster_select_key(app, key, kafka_tx),
         Route::Dashboard => handle_main_key(app, key, kafka_tx),
+        Route::Brokers => handle_main_key(app, key, kafka_tx),
+        Route::BrokerDetail(_) => handle_main_key(app, key, kafka_tx),
         Route::Topics => handle_main_key(app, key, kafka_tx),
         Route::TopicDetail(_) => handle_main_key(app, key, kafka_tx),
         Route::Messages(_) => handle_main_key(app, key, kafka_tx),
+        Route::TopicSearch(_) => handle_main_key(app, key, kafka_tx),
+        Route::TopicTable(_) => handle_main_key(app, key, kafka_tx),
+        Route::Timeline(_) => handle_main_key(app, key, kafka_tx),
+        Route::TopicAnalysis(_) => handle_main_key(app, key, kafka_tx),
         Route::ConsumerGroups => handle_main_key(app, key, kafka_tx),
         Route::ConsumerGroupDetail(_) => handle_main_key(app, key, kafka_tx),
+        Route::Reassignment => handle_main_key(app, key, kafka_tx),
+        Route::Acls => handle_main_key(app, key, kafka_tx),
+        Route::ClientStats => handle_main_key(app, key, kafka_tx),
+        Route::AuditLog => handle_main_key(app, key, kafka_tx),
     }
 }
 
@@ -232,14 +620,24 @@ fn handle_cluster_select_key(
         }
         KeyCode::Enter => {
             if let Some(cluster) = app.config.clusters.get(app.cluster_select_index).cloned() {
-                app.active_cluster = Some(app.cluster_select_index);
-                app.log_info(&format!("Connecting to {}...", cluster.name));
-                let _ = kafka_tx.send(KafkaCommand::Connect(cluster));
+                if cluster.auth.needs_prompt() {
+                    app.dialog = Some(Dialog::PasswordPrompt(PasswordPromptDialog {
+                        cluster_index: app.cluster_select_index,
+                        cluster_name: cluster.name,
+                        password: String::new(),
+                        test_only: false,
+                    }));
+                } else {
+                    connect_cluster(app, app.cluster_select_index, cluster, None, kafka_tx);
+                }
             }
         }
         KeyCode::Char('a') => {
             app.dialog = Some(Dialog::EditCluster(EditClusterDialog::default()));
         }
+        KeyCode::Char('i') => {
+            app.dialog = Some(Dialog::ImportClusters(ImportClustersDialog::default()));
+        }
         KeyCode::Char('e') => {
             if let Some(cluster) = app.config.clusters.get(app.cluster_select_index) {
                 app.dialog = Some(Dialog::EditCluster(EditClusterDialog::from_config(cluster, app.cluster_select_index)));
@@ -251,18 +649,105 @@ fn handle_cluster_select_key(
             }
         }
         KeyCode::Char('t') => {
-            if let Some(cluster) = app.config.clusters.get(app.cluster_select_index) {
-                app.dialog = Some(Dialog::ConnectionTest(ConnectionTestDialog {
-                    cluster_name: cluster.name.clone(),
-                    status: ConnectionTestStatus::Testing,
-                }));
-                let _ = kafka_tx.send(KafkaCommand::TestConnection(cluster.clone()));
+            if let Some(cluster) = app.config.clusters.get(app.cluster_select_index).cloned() {
+                if cluster.auth.needs_prompt() {
+                    app.dialog = Some(Dialog::PasswordPrompt(PasswordPromptDialog {
+                        cluster_index: app.cluster_select_index,
+                        cluster_name: cluster.name,
+                        password: String::new(),
+                        test_only: true,
+                    }));
+                } else {
+                    test_cluster(app, cluster, None, kafka_tx);
+                }
             }
         }
         _ => {}
     }
 }
 
+fn connect_cluster(
+    app: &mut App,
+    index: usize,
+    cluster: ClusterConfig,
+    password: Option<String>,
+    kafka_tx: &mpsc::UnboundedSender<KafkaCommand>,
+) {
+    app.active_cluster = Some(index);
+    app.log_info(&format!("Connecting to {}...", cluster.name));
+    let _ = kafka_tx.send(KafkaCommand::Connect { cluster, password });
+}
+
+/// Sends a command that changes the cluster. Read-only clusters refuse it
+/// here as well as in the backend. On prod clusters the cluster name has to
+/// be typed first; `resume` is the dialog to show again once it is sent.
+/// Returns whether the command was sent right away.
+fn send_mutation(
+    app: &mut App,
+    command: KafkaCommand,
+    resume: Option<Dialog>,
+    kafka_tx: &mpsc::UnboundedSender<KafkaCommand>,
+) -> bool {
+    let mutation = command.mutation().unwrap_or_default();
+    let cluster = app.active_cluster_config().map(|c| (c.name.clone(), c.read_only, c.is_prod()));
+    match cluster {
+        Some((name, true, _)) => {
+            let message = format!("Refused to {}: cluster '{}' is read-only", mutation, name);
+            audit_rejected(app, &name, &command, &message);
+            app.log_error(&message);
+            false
+        }
+        Some((name, false, true)) => {
+            app.dialog = Some(Dialog::ProdConfirm(ProdConfirmDialog {
+                cluster_name: name,
+                mutation,
+                command,
+                confirm_input: String::new(),
+                resume: resume.map(Box::new),
+            }));
+            false
+        }
+        _ => {
+            let _ = kafka_tx.send(command);
+            true
+        }
+    }
+}
+
+/// Records a change refused before it reached the backend.
+fn audit_rejected(app: &mut App, cluster: &str, command: &KafkaCommand, message: &str) {
+    if let Some(entry) = audit::AuditEntry::begin(cluster, command) {
+        if let Err(e) = entry.finish("rejected", message) {
+            app.log_error(&format!("Audit log write failed: {:#}", e));
+        }
+    }
+}
+
+/// Appends a number when a cluster of that name already exists.
+fn unique_cluster_name(clusters: &[ClusterConfig], name: &str) -> String {
+    let taken = |candidate: &str| clusters.iter().any(|c| c.name == candidate);
+    if !taken(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}-{}", name, n))
+        .find(|candidate| !taken(candidate))
+        .unwrap_or_else(|| name.to_string())
+}
+
+fn test_cluster(
+    app: &mut App,
+    cluster: ClusterConfig,
+    password: Option<String>,
+    kafka_tx: &mpsc::UnboundedSender<KafkaCommand>,
+) {
+    app.dialog = Some(Dialog::ConnectionTest(ConnectionTestDialog {
+        cluster_name: cluster.name.clone(),
+        status: ConnectionTestStatus::Testing,
+    }));
+    let _ = kafka_tx.send(KafkaCommand::TestConnection { cluster, password });
+}
+
 fn handle_main_key(app: &mut App, key: KeyEvent, kafka_tx: &mpsc::UnboundedSender<KafkaCommand>) {
     match key.code {
         KeyCode::Char('q') => {
@@ -306,12 +791,26 @@ fn handle_sidebar_key(
                     Route::Dashboard => {
                         let _ = kafka_tx.send(KafkaCommand::FetchMetadata);
                     }
+                    Route::Brokers => {
+                        app.brokers.loading = true;
+                        let _ = kafka_tx.send(KafkaCommand::FetchBrokers);
+                    }
                     Route::Topics => {
                         let _ = kafka_tx.send(KafkaCommand::FetchTopics);
                     }
                     Route::ConsumerGroups => {
                         let _ = kafka_tx.send(KafkaCommand::FetchConsumerGroups);
                     }
+                    Route::Acls => {
+                        app.acls.loading = true;
+                        let _ = kafka_tx.send(KafkaCommand::FetchAcls);
+                    }
+                    Route::AuditLog => load_audit_log(app),
+                    Route::Reassignment => {
+                        if let (true, Some(plan)) = (app.reassignment.submitted, &app.reassignment.plan) {
+                            let _ = kafka_tx.send(KafkaCommand::TrackReassignment(plan.clone()));
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -340,6 +839,48 @@ fn handle_content_key(
             }
             _ => {}
         },
+        Route::Brokers => match key.code {
+            KeyCode::Char('j') | KeyCode::Down => app.brokers.next(),
+            KeyCode::Char('k') | KeyCode::Up => app.brokers.previous(),
+            KeyCode::Char('r') => {
+                app.brokers.loading = true;
+                let _ = kafka_tx.send(KafkaCommand::FetchBrokers);
+                app.log_info("Refreshing brokers...");
+            }
+            KeyCode::Enter => {
+                if let Some(broker) = app.brokers.brokers.get(app.brokers.selected) {
+                    let id = broker.id;
+                    app.broker_detail = BrokerDetailState {
+                        broker_id: id,
+                        loading: true,
+                        ..Default::default()
+                    };
+                    app.navigate(Route::BrokerDetail(id));
+                    let _ = kafka_tx.send(KafkaCommand::FetchBrokerConfig(id));
+                }
+            }
+            KeyCode::Char('x') => {
+                if let Some(broker) = app.brokers.brokers.get(app.brokers.selected) {
+                    plan_reassignment(app, ReassignmentGoal::DrainBroker(broker.id), kafka_tx);
+                }
+            }
+            KeyCode::Char('b') => plan_reassignment(app, ReassignmentGoal::Rebalance, kafka_tx),
+            _ => {}
+        },
+        Route::BrokerDetail(id) => match key.code {
+            KeyCode::Esc => {
+                app.navigate(Route::Brokers);
+                let _ = kafka_tx.send(KafkaCommand::FetchBrokers);
+            }
+            KeyCode::Char('j') | KeyCode::Down => app.broker_detail.next(),
+            KeyCode::Char('k') | KeyCode::Up => app.broker_detail.previous(),
+            KeyCode::Char('r') => {
+                app.broker_detail.loading = true;
+                let _ = kafka_tx.send(KafkaCommand::FetchBrokerConfig(*id));
+                app.log_info("Refreshing broker config...");
+            }
+            _ => {}
+        },
         Route::Topics => match key.code {
             KeyCode::Char('j') | KeyCode::Down => app.topics.next(),
             KeyCode::Char('k') | KeyCode::Up => app.topics.previous(),
@@ -372,6 +913,20 @@ fn handle_content_key(
                     app.navigate(Route::Messages(name));
                 }
             }
+            KeyCode::Char('v') => {
+                let filtered = app.topics.filtered_topics();
+                if let Some(topic) = filtered.get(app.topics.selected) {
+                    let name = topic.name.clone();
+                    start_topic_table(app, name, kafka_tx);
+                }
+            }
+            KeyCode::Char('a') => {
+                let filtered = app.topics.filtered_topics();
+                if let Some(topic) = filtered.get(app.topics.selected) {
+                    let name = topic.name.clone();
+                    start_analysis(app, name, analysis::DEFAULT_SAMPLE, kafka_tx);
+                }
+            }
             _ => {}
         },
         Route::TopicDetail(_) => match key.code {
@@ -397,6 +952,39 @@ fn handle_content_key(
                 app.messages = MessageState::new(&name);
                 app.navigate(Route::Messages(name));
             }
+            KeyCode::Char('v') => {
+                let name = app.topic_detail.topic_name.clone();
+                start_topic_table(app, name, kafka_tx);
+            }
+            KeyCode::Char('a') => {
+                let name = app.topic_detail.topic_name.clone();
+                start_analysis(app, name, analysis::DEFAULT_SAMPLE, kafka_tx);
+            }
+            KeyCode::Char('f') => {
+                let current = app.topic_detail.partitions.first().map(|p| p.replicas.len()).unwrap_or(1);
+                app.dialog = Some(Dialog::ReplicationFactor(ReplicationFactorDialog::new(&app.topic_detail.topic_name, current)));
+            }
+            KeyCode::Char(' ') => app.topic_detail.toggle_mark(),
+            KeyCode::Char('e') => {
+                let partitions = if app.topic_detail.marked_partitions.is_empty() {
+                    app.topic_detail.partitions.get(app.topic_detail.selected_partition)
+                        .map(|p| vec![p.id])
+                        .unwrap_or_default()
+                } else {
+                    app.topic_detail.marked_partitions.clone()
+                };
+                if !partitions.is_empty() {
+                    app.dialog = Some(Dialog::ElectLeaders(ElectLeadersDialog::new(&app.topic_detail.topic_name, partitions)));
+                }
+            }
+            KeyCode::Char('E') => {
+                let partitions = app.topic_detail.unpreferred_partitions();
+                if partitions.is_empty() {
+                    app.log_info("All partitions are already on their preferred leader");
+                } else {
+                    app.dialog = Some(Dialog::ElectLeaders(ElectLeadersDialog::new(&app.topic_detail.topic_name, partitions)));
+                }
+            }
             KeyCode::Char('r') => {
                 let name = app.topic_detail.topic_name.clone();
                 let _ = kafka_tx.send(KafkaCommand::FetchTopicDetail(name));
@@ -431,6 +1019,11 @@ fn handle_content_key(
                         app.messages.consuming = false;
                         app.log_info("Stopped consuming");
                     } else {
+                        if app.messages.paged {
+                            app.messages.paged = false;
+                            app.messages.page = None;
+                            app.messages.messages.clear();
+                        }
                         app.messages.consuming = true;
                         app.messages.auto_scroll = true;
                         let _ = kafka_tx.send(KafkaCommand::StartConsuming {
@@ -446,9 +1039,68 @@ fn handle_content_key(
                 KeyCode::Char('p') => {
                     app.dialog = Some(Dialog::ProduceMessage(ProduceMessageDialog::new(&topic)));
                 }
+                KeyCode::Char('f') => {
+                    app.dialog = Some(Dialog::Search(SearchDialog::new(&topic, &app.messages.search_query)));
+                }
+                KeyCode::Char('g') => {
+                    app.dialog = Some(Dialog::GoToOffset(GoToOffsetDialog::new(&topic)));
+                }
+                KeyCode::Char('z') => toggle_timezone(app),
+                KeyCode::Char('t') => app.messages.show_stats = !app.messages.show_stats,
+                KeyCode::Char('T') => {
+                    app.dialog = Some(Dialog::Timeline(TimelineDialog::new(&topic)));
+                }
+                KeyCode::Char('b') => {
+                    let messages = &mut app.messages;
+                    messages.messages.clear();
+                    messages.target = None;
+                    messages.page = None;
+                    if messages.paged {
+                        messages.paged = false;
+                        app.log_info("Left page mode");
+                    } else {
+                        let _ = kafka_tx.send(KafkaCommand::StopConsuming);
+                        messages.consuming = false;
+                        messages.paged = true;
+                        let anchor = messages.first_page();
+                        let _ = kafka_tx.send(KafkaCommand::FetchPage { topic: topic.clone(), anchor });
+                        app.log_info("Browsing page by page");
+                    }
+                }
+                KeyCode::Char('n') | KeyCode::PageDown if app.messages.paged => {
+                    match app.messages.page {
+                        Some(ref page) if page.at_end() => app.log_info("Already on the last page"),
+                        Some(ref page) => {
+                            let _ = kafka_tx.send(KafkaCommand::FetchPage { topic: topic.clone(), anchor: page.after() });
+                        }
+                        None => {}
+                    }
+                }
+                KeyCode::Char('N') | KeyCode::PageUp if app.messages.paged => {
+                    match app.messages.page {
+                        Some(ref page) if page.at_start() => app.log_info("Already on the first page"),
+                        Some(ref page) => {
+                            let _ = kafka_tx.send(KafkaCommand::FetchPage { topic: topic.clone(), anchor: page.before() });
+                        }
+                        None => {}
+                    }
+                }
+                KeyCode::Char('1') if app.messages.paged => {
+                    app.messages.offset_mode = OffsetMode::Earliest;
+                    let _ = kafka_tx.send(KafkaCommand::FetchPage {
+                        topic: topic.clone(),
+                        anchor: PageAnchor::From(SearchBound::Edge),
+                    });
+                }
+                KeyCode::Char('2') if app.messages.paged => {
+                    app.messages.offset_mode = OffsetMode::Latest;
+                    let _ = kafka_tx.send(KafkaCommand::FetchPage { topic: topic.clone(), anchor: PageAnchor::Last });
+                }
                 KeyCode::Char('1') => {
                     app.messages.offset_mode = OffsetMode::Earliest;
                     app.messages.messages.clear();
+                    app.messages.stats = ConsumeStats::default();
+                    app.messages.target = None;
                     app.messages.consuming = true;
                     app.messages.auto_scroll = true;
                     let _ = kafka_tx.send(KafkaCommand::StopConsuming);
@@ -461,6 +1113,8 @@ fn handle_content_key(
                 KeyCode::Char('2') => {
                     app.messages.offset_mode = OffsetMode::Latest;
                     app.messages.messages.clear();
+                    app.messages.stats = ConsumeStats::default();
+                    app.messages.target = None;
                     app.messages.consuming = true;
                     app.messages.auto_scroll = true;
                     let _ = kafka_tx.send(KafkaCommand::StopConsuming);
@@ -473,6 +1127,120 @@ fn handle_content_key(
                 _ => {}
             }
         }
+        Route::TopicSearch(topic) => match key.code {
+            KeyCode::Esc => {
+                if app.search.show_detail {
+                    app.search.show_detail = false;
+                } else {
+                    if app.search.running {
+                        let _ = kafka_tx.send(KafkaCommand::CancelSearch);
+                    }
+                    app.navigate(Route::Messages(topic.clone()));
+                }
+            }
+            KeyCode::Char('j') | KeyCode::Down => app.search.next(),
+            KeyCode::Char('k') | KeyCode::Up => app.search.previous(),
+            KeyCode::Enter => app.search.show_detail = !app.search.show_detail,
+            KeyCode::Char('c') if app.search.running => {
+                let _ = kafka_tx.send(KafkaCommand::CancelSearch);
+                app.log_info("Cancelling search...");
+            }
+            KeyCode::Char('n') => {
+                app.dialog = Some(Dialog::Search(SearchDialog::new(topic, &app.search.query)));
+            }
+            KeyCode::Char('z') => toggle_timezone(app),
+            _ => {}
+        },
+        Route::TopicTable(topic) => match key.code {
+            KeyCode::Esc => {
+                if app.topic_table.show_detail {
+                    app.topic_table.show_detail = false;
+                } else {
+                    if app.topic_table.running {
+                        let _ = kafka_tx.send(KafkaCommand::CancelTable);
+                    }
+                    app.navigate(Route::Topics);
+                    let _ = kafka_tx.send(KafkaCommand::FetchTopics);
+                }
+            }
+            KeyCode::Char('j') | KeyCode::Down => app.topic_table.next(),
+            KeyCode::Char('k') | KeyCode::Up => app.topic_table.previous(),
+            KeyCode::Enter => app.topic_table.show_detail = !app.topic_table.show_detail,
+            KeyCode::Char('c') if app.topic_table.running => {
+                let _ = kafka_tx.send(KafkaCommand::CancelTable);
+                app.log_info("Cancelling table read...");
+            }
+            KeyCode::Char('r') => start_topic_table(app, topic.clone(), kafka_tx),
+            KeyCode::Char('z') => toggle_timezone(app),
+            _ => {}
+        },
+        Route::TopicAnalysis(topic) => match key.code {
+            KeyCode::Esc => {
+                app.navigate(Route::Topics);
+                let _ = kafka_tx.send(KafkaCommand::FetchTopics);
+            }
+            KeyCode::Char('j') | KeyCode::Down => app.analysis.next(),
+            KeyCode::Char('k') | KeyCode::Up => app.analysis.previous(),
+            KeyCode::Char('r') => start_analysis(app, topic.clone(), app.analysis.sample, kafka_tx),
+            KeyCode::Char('+') => {
+                let sample = (app.analysis.sample * 2).min(analysis::MAX_SAMPLE);
+                start_analysis(app, topic.clone(), sample, kafka_tx);
+            }
+            KeyCode::Char('-') => {
+                let sample = (app.analysis.sample / 2).max(100);
+                start_analysis(app, topic.clone(), sample, kafka_tx);
+            }
+            KeyCode::Enter => {
+                // Browse the selected key's records
+                let Some(key) = app.analysis.analysis.as_ref().and_then(|a| a.top_keys.get(app.analysis.selected)) else {
+                    return;
+                };
+                let query = format!("key == \"{}\"", key.key.replace('\\', "\\\\").replace('"', "\\\""));
+                app.messages = MessageState::new(topic);
+                app.messages.search_query = query;
+                app.messages.update_filter();
+                app.navigate(Route::Messages(topic.clone()));
+            }
+            _ => {}
+        },
+        Route::Timeline(topic) => match key.code {
+            KeyCode::Esc => app.navigate(Route::Messages(topic.clone())),
+            KeyCode::Char('l') | KeyCode::Right => app.timeline.next(),
+            KeyCode::Char('h') | KeyCode::Left => app.timeline.previous(),
+            KeyCode::Home => app.timeline.selected = 0,
+            KeyCode::End => {
+                let len = app.timeline.timeline.as_ref().map_or(0, |t| t.counts.len());
+                app.timeline.selected = len.saturating_sub(1);
+            }
+            KeyCode::Char('p') => app.timeline.cycle_partition(),
+            KeyCode::Char('n') => {
+                app.dialog = Some(Dialog::Timeline(TimelineDialog::new(topic)));
+            }
+            KeyCode::Char('z') => toggle_timezone(app),
+            KeyCode::Enter => {
+                let Some(start) = app.timeline.timeline.as_ref().map(|t| t.bucket_start(app.timeline.selected)) else {
+                    return;
+                };
+                // Open the browser on the page that starts at the bucket
+                let _ = kafka_tx.send(KafkaCommand::StopConsuming);
+                if app.messages.topic != *topic {
+                    app.messages = MessageState::new(topic);
+                }
+                let messages = &mut app.messages;
+                messages.consuming = false;
+                messages.messages.clear();
+                messages.target = None;
+                messages.show_detail = false;
+                messages.offset_mode = OffsetMode::Timestamp(start);
+                messages.paged = true;
+                messages.page = None;
+                let anchor = messages.first_page();
+                let _ = kafka_tx.send(KafkaCommand::FetchPage { topic: topic.clone(), anchor });
+                app.log_info(&format!("Browsing {} from {}", topic, app.config.display.timezone.format(start, "%Y-%m-%d %H:%M")));
+                app.navigate(Route::Messages(topic.clone()));
+            }
+            _ => {}
+        },
         Route::ConsumerGroups => match key.code {
             KeyCode::Char('j') | KeyCode::Down => app.consumer_groups.next(),
             KeyCode::Char('k') | KeyCode::Up => app.consumer_groups.previous(),
@@ -489,6 +1257,73 @@ fn handle_content_key(
             }
             _ => {}
         },
+        Route::Reassignment => match key.code {
+            KeyCode::Char('j') | KeyCode::Down => app.reassignment.next(),
+            KeyCode::Char('k') | KeyCode::Up => app.reassignment.previous(),
+            KeyCode::Char('x') => {
+                if let Some(ref plan) = app.reassignment.plan {
+                    match reassign::export(plan) {
+                        Ok(path) => {
+                            app.reassignment.exported_to = Some(path.display().to_string());
+                            app.log_info(&format!("Reassignment JSON written to {}", path.display()));
+                        }
+                        Err(e) => app.log_error(&format!("Export failed: {}", e)),
+                    }
+                }
+            }
+            KeyCode::Char('s') if app.reassignment.move_count() > 0 && !app.reassignment.submitted => {
+                app.dialog = Some(Dialog::Confirm(ConfirmDialog::new(
+                    "Submit Reassignment",
+                    &format!("Move {} partition(s) with kafka-reassign-partitions?", app.reassignment.move_count()),
+                    ConfirmAction::SubmitReassignment,
+                )));
+            }
+            KeyCode::Char('r') => {
+                if let Some(ref plan) = app.reassignment.plan {
+                    let _ = kafka_tx.send(KafkaCommand::TrackReassignment(plan.clone()));
+                    app.log_info("Checking reassignment progress...");
+                }
+            }
+            _ => {}
+        },
+        Route::Acls => match key.code {
+            KeyCode::Char('j') | KeyCode::Down => app.acls.next(),
+            KeyCode::Char('k') | KeyCode::Up => app.acls.previous(),
+            KeyCode::Char('r') => {
+                app.acls.loading = true;
+                let _ = kafka_tx.send(KafkaCommand::FetchAcls);
+                app.log_info("Refreshing ACLs...");
+            }
+            KeyCode::Char('c') => {
+                app.dialog = Some(Dialog::CreateAcl(CreateAclDialog::default()));
+            }
+            KeyCode::Char('d') => {
+                let selected = app.acls.filtered_bindings().get(app.acls.selected).map(|b| (*b).clone());
+                if let Some(binding) = selected {
+                    app.dialog = Some(Dialog::Confirm(ConfirmDialog::new(
+                        "Delete ACL",
+                        &format!("Delete binding: {}?", binding.describe()),
+                        ConfirmAction::DeleteAcl(binding),
+                    )));
+                }
+            }
+            _ => {}
+        },
+        Route::ClientStats => match key.code {
+            KeyCode::Left | KeyCode::Char('h') => app.client_stats.previous_client(),
+            KeyCode::Right | KeyCode::Char('l') => app.client_stats.next_client(),
+            _ => {}
+        },
+        Route::AuditLog => match key.code {
+            KeyCode::Char('j') | KeyCode::Down => app.audit.next(),
+            KeyCode::Char('k') | KeyCode::Up => app.audit.previous(),
+            KeyCode::Enter => app.audit.show_detail = !app.audit.show_detail,
+            KeyCode::Char('r') => {
+                load_audit_log(app);
+                app.log_info("Audit log reloaded");
+            }
+            _ => {}
+        },
         Route::ConsumerGroupDetail(_) => match key.code {
             KeyCode::Esc => {
                 app.navigate(Route::ConsumerGroups);
@@ -507,6 +1342,66 @@ fn handle_content_key(
     }
 }
 
+fn load_audit_log(app: &mut App) {
+    match audit::read_entries() {
+        Ok(entries) => {
+            app.audit.entries = entries;
+            app.audit.error = None;
+        }
+        Err(e) => app.audit.error = Some(format!("{:#}", e)),
+    }
+    app.audit.selected = 0;
+}
+
+/// Samples `sample` records per partition of `topic` and opens the analysis.
+fn start_analysis(app: &mut App, topic: String, sample: usize, kafka_tx: &mpsc::UnboundedSender<KafkaCommand>) {
+    app.analysis = AnalysisState {
+        topic: topic.clone(),
+        sample,
+        loading: true,
+        ..Default::default()
+    };
+    let _ = kafka_tx.send(KafkaCommand::AnalyzeTopic { topic: topic.clone(), sample });
+    app.log_info(&format!("Sampling {} record(s) per partition of {}...", sample, topic));
+    app.navigate(Route::TopicAnalysis(topic));
+}
+
+/// Starts reading the latest record per key of `topic` and opens the table.
+fn start_topic_table(app: &mut App, topic: String, kafka_tx: &mpsc::UnboundedSender<KafkaCommand>) {
+    app.topic_table = TopicTableState {
+        id: app.topic_table.id + 1,
+        topic: topic.clone(),
+        running: true,
+        ..Default::default()
+    };
+    let _ = kafka_tx.send(KafkaCommand::StartTable {
+        id: app.topic_table.id,
+        topic: topic.clone(),
+    });
+    app.log_info(&format!("Reading the latest value per key of {}...", topic));
+    app.navigate(Route::TopicTable(topic));
+}
+
+/// Switches record times between local time and UTC, and saves the choice.
+fn toggle_timezone(app: &mut App) {
+    let timezone = app.config.display.timezone.toggle();
+    app.config.display.timezone = timezone;
+    match app.config.save() {
+        Ok(()) => app.log_info(&format!("Showing times in {}", timezone.label())),
+        Err(e) => app.log_error(&format!("Showing times in {}, but saving the config failed: {}", timezone.label(), e)),
+    }
+}
+
+fn plan_reassignment(app: &mut App, goal: ReassignmentGoal, kafka_tx: &mpsc::UnboundedSender<KafkaCommand>) {
+    app.log_info(&format!("Planning: {}...", goal.describe()));
+    app.reassignment = ReassignmentState {
+        loading: true,
+        ..Default::default()
+    };
+    app.navigate(Route::Reassignment);
+    let _ = kafka_tx.send(KafkaCommand::PlanReassignment(goal));
+}
+
 fn handle_search_key(app: &mut App, key: KeyEvent) {
     match key.code {
         KeyCode::Esc | KeyCode::Enter => {
@@ -515,14 +1410,44 @@ fn handle_search_key(app: &mut App, key: KeyEvent) {
         KeyCode::Char(c) => {
             match &app.route {
                 Route::Topics => app.topics.search_query.push(c),
-                Route::Messages(_) => app.messages.search_query.push(c),
+                Route::Acls => {
+                    app.acls.search_query.push(c);
+                    app.acls.selected = 0;
+                }
+                Route::AuditLog => {
+                    app.audit.search_query.push(c);
+                    app.audit.selected = 0;
+                }
+                Route::TopicTable(_) => {
+                    app.topic_table.search_query.push(c);
+                    app.topic_table.selected = 0;
+                }
+                Route::Messages(_) => {
+                    app.messages.search_query.push(c);
+                    app.messages.update_filter();
+                }
                 _ => {}
             }
         }
         KeyCode::Backspace => {
             match &app.route {
                 Route::Topics => { app.topics.search_query.pop(); }
-                Route::Messages(_) => { app.messages.search_query.pop(); }
+                Route::Acls => {
+                    app.acls.search_query.pop();
+                    app.acls.selected = 0;
+                }
+                Route::AuditLog => {
+                    app.audit.search_query.pop();
+                    app.audit.selected = 0;
+                }
+                Route::TopicTable(_) => {
+                    app.topic_table.search_query.pop();
+                    app.topic_table.selected = 0;
+                }
+                Route::Messages(_) => {
+                    app.messages.search_query.pop();
+                    app.messages.update_filter();
+                }
                 _ => {}
             }
         }
@@ -536,7 +1461,9 @@ fn handle_dialog_key(
     kafka_tx: &mpsc::UnboundedSender<KafkaCommand>,
 ) {
     if key.code == KeyCode::Esc {
-        app.dialog = None;
+        if let Some(Dialog::ProdConfirm(d)) = app.dialog.take() {
+            audit_rejected(app, &d.cluster_name, &d.command, "typed confirmation cancelled");
+        }
         app.focus = Focus::Content;
         return;
     }
@@ -559,13 +1486,15 @@ fn handle_dialog_key(
                         if !d.retention_ms.is_empty() {
                             config.insert("retention.ms".to_string(), d.retention_ms.clone());
                         }
-                        let _ = kafka_tx.send(KafkaCommand::CreateTopic {
+                        let command = KafkaCommand::CreateTopic {
                             name: d.name.clone(),
                             partitions,
                             replication_factor,
                             config,
-                        });
-                        app.log_info(&format!("Creating topic '{}'...", d.name));
+                        };
+                        if send_mutation(app, command, None, kafka_tx) {
+                            app.log_info(&format!("Creating topic '{}'...", d.name));
+                        }
                         return; // Dialog will be closed by response
                     }
                 }
@@ -597,10 +1526,12 @@ fn handle_dialog_key(
                     if d.confirm_input == d.item_name {
                         match d.item_type.as_str() {
                             "topic" => {
-                                let _ = kafka_tx.send(KafkaCommand::DeleteTopic(d.item_name.clone()));
-                                app.log_info(&format!("Deleting topic '{}'...", d.item_name));
-                                // Refresh topics after deletion
-                                let _ = kafka_tx.send(KafkaCommand::FetchTopics);
+                                let sent = send_mutation(app, KafkaCommand::DeleteTopic(d.item_name.clone()), None, kafka_tx);
+                                if sent {
+                                    app.log_info(&format!("Deleting topic '{}'...", d.item_name));
+                                    // Refresh topics after deletion
+                                    let _ = kafka_tx.send(KafkaCommand::FetchTopics);
+                                }
                             }
                             "cluster" => {
                                 app.config.clusters.retain(|c| c.name != d.item_name);
@@ -637,30 +1568,39 @@ fn handle_dialog_key(
                 }
                 KeyCode::F(5) => {
                     // Send message
-                    let headers: Vec<(String, String)> = if d.headers.is_empty() {
-                        Vec::new()
-                    } else {
-                        d.headers.split(',').filter_map(|pair| {
-                            let parts: Vec<&str> = pair.splitn(2, ':').collect();
-                            if parts.len() == 2 {
-                                Some((parts[0].trim().to_string(), parts[1].trim().to_string()))
-                            } else {
-                                None
-                            }
-                        }).collect()
-                    };
-                    let _ = kafka_tx.send(KafkaCommand::ProduceMessage {
+                    let command = KafkaCommand::ProduceMessage {
                         topic: d.topic.clone(),
-                        key: if d.key.is_empty() { None } else { Some(d.key.clone()) },
-                        value: d.value.clone(),
-                        headers,
-                    });
+                        key: (!d.null_key).then(|| d.key.clone()),
+                        value: (!d.null_value).then(|| d.value.clone()),
+                        headers: d.parse_headers(),
+                    };
                     d.result_message = Some("⏳ Sending...".to_string());
+                    if !send_mutation(app, command, Some(Dialog::ProduceMessage(d.clone())), kafka_tx) {
+                        if app.dialog.is_some() {
+                            return; // waiting for prod confirmation
+                        }
+                        d.result_message = Some("✗ Cluster is read-only".to_string());
+                    }
+                }
+                KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    match d.focused_field {
+                        0 => d.null_key = !d.null_key,
+                        1 => d.null_value = !d.null_value,
+                        _ => {}
+                    }
+                    d.result_message = None;
                 }
                 KeyCode::Char(c) => {
+                    // Typing into a null field makes it a string again
                     match d.focused_field {
-                        0 => d.key.push(c),
-                        1 => d.value.push(c),
+                        0 => {
+                            d.null_key = false;
+                            d.key.push(c);
+                        }
+                        1 => {
+                            d.null_value = false;
+                            d.value.push(c);
+                        }
                         2 => d.headers.push(c),
                         _ => {}
                     }
@@ -697,12 +1637,7 @@ fn handle_dialog_key(
             app.dialog = Some(Dialog::ResetOffset(d));
         }
         Some(Dialog::EditCluster(mut d)) => {
-            let max_fields = match d.auth_type {
-                1 | 2 | 3 => 5, // name, brokers, auth, user, pass
-                4 => 6,         // name, brokers, auth, ca, cert, key
-                5 | 6 | 7 => 6, // name, brokers, auth, user, pass, ca_cert(opt)
-                _ => 3,         // name, brokers, auth
-            };
+            let max_fields = d.field_count();
             match key.code {
                 KeyCode::Tab => {
                     d.focused_field = (d.focused_field + 1) % max_fields;
@@ -712,17 +1647,33 @@ fn handle_dialog_key(
                 }
                 KeyCode::Left => {
                     if d.focused_field == 2 {
-                        d.auth_type = if d.auth_type == 0 { 7 } else { d.auth_type - 1 };
+                        d.auth_type = (d.auth_type + EditClusterDialog::AUTH_TYPE_COUNT - 1) % EditClusterDialog::AUTH_TYPE_COUNT;
+                    } else if d.focused_field == d.environment_field() {
+                        d.cycle_environment(-1);
+                    } else if d.focused_field == d.read_only_field() {
+                        d.read_only = !d.read_only;
                     }
                 }
                 KeyCode::Right => {
                     if d.focused_field == 2 {
-                        d.auth_type = (d.auth_type + 1) % 8;
+                        d.auth_type = (d.auth_type + 1) % EditClusterDialog::AUTH_TYPE_COUNT;
+                    } else if d.focused_field == d.environment_field() {
+                        d.cycle_environment(1);
+                    } else if d.focused_field == d.read_only_field() {
+                        d.read_only = !d.read_only;
                     }
                 }
+                KeyCode::Char(' ') if d.focused_field == d.read_only_field() => d.read_only = !d.read_only,
                 KeyCode::Enter => {
                     if !d.name.is_empty() && !d.brokers.is_empty() {
-                        let config = d.to_config();
+                        let config = match d.to_config() {
+                            Ok(config) => config,
+                            Err(e) => {
+                                d.error = Some(e);
+                                app.dialog = Some(Dialog::EditCluster(d));
+                                return;
+                            }
+                        };
                         if let Some(idx) = d.editing_index {
                             if idx < app.config.clusters.len() {
                                 app.config.clusters[idx] = config;
@@ -737,55 +1688,307 @@ fn handle_dialog_key(
                     }
                 }
                 KeyCode::Char(c) => {
-                    match d.focused_field {
-                        0 => d.name.push(c),
-                        1 => d.brokers.push(c),
-                        2 => {} // auth type uses arrows
-                        3 => match d.auth_type {
-                            1 | 2 | 3 | 5 | 6 | 7 => d.username.push(c),
-                            4 => d.ca_cert.push(c),
-                            _ => {}
-                        },
-                        4 => match d.auth_type {
-                            1 | 2 | 3 | 5 | 6 | 7 => d.password.push(c),
-                            4 => d.client_cert.push(c),
-                            _ => {}
-                        },
-                        5 => match d.auth_type {
-                            4 => d.client_key.push(c),
-                            5 | 6 | 7 => d.ca_cert.push(c),
-                            _ => {}
-                        },
-                        _ => {}
+                    if let Some(field) = d.field_mut(d.focused_field) {
+                        field.push(c);
+                        d.error = None;
                     }
                 }
                 KeyCode::Backspace => {
-                    match d.focused_field {
-                        0 => { d.name.pop(); }
-                        1 => { d.brokers.pop(); }
-                        2 => {}
-                        3 => match d.auth_type {
-                            1 | 2 | 3 | 5 | 6 | 7 => { d.username.pop(); }
-                            4 => { d.ca_cert.pop(); }
-                            _ => {}
-                        },
-                        4 => match d.auth_type {
-                            1 | 2 | 3 | 5 | 6 | 7 => { d.password.pop(); }
-                            4 => { d.client_cert.pop(); }
-                            _ => {}
-                        },
-                        5 => match d.auth_type {
-                            4 => { d.client_key.pop(); }
-                            5 | 6 | 7 => { d.ca_cert.pop(); }
-                            _ => {}
-                        },
-                        _ => {}
+                    if let Some(field) = d.field_mut(d.focused_field) {
+                        field.pop();
+                        d.error = None;
                     }
                 }
                 _ => {}
             }
             app.dialog = Some(Dialog::EditCluster(d));
         }
+        Some(Dialog::ElectLeaders(mut d)) => {
+            if key.code == KeyCode::Enter && !d.running && d.results.is_none() {
+                let command = KafkaCommand::ElectPreferredLeaders {
+                    topic: d.topic.clone(),
+                    partitions: d.partitions.clone(),
+                };
+                d.running = true;
+                if send_mutation(app, command, Some(Dialog::ElectLeaders(d.clone())), kafka_tx) {
+                    app.log_info(&format!("Electing preferred leaders for '{}'...", d.topic));
+                } else if app.dialog.is_some() {
+                    return; // waiting for prod confirmation
+                } else {
+                    d.running = false;
+                }
+            }
+            app.dialog = Some(Dialog::ElectLeaders(d));
+        }
+        Some(Dialog::ProdConfirm(mut d)) => {
+            match key.code {
+                KeyCode::Enter if d.confirm_input == d.cluster_name => {
+                    app.log_info(&format!("Confirmed on prod cluster '{}': {}", d.cluster_name, d.mutation));
+                    let _ = kafka_tx.send(d.command);
+                    app.dialog = d.resume.map(|dialog| *dialog);
+                    return;
+                }
+                KeyCode::Char(c) => d.confirm_input.push(c),
+                KeyCode::Backspace => {
+                    d.confirm_input.pop();
+                }
+                _ => {}
+            }
+            app.dialog = Some(Dialog::ProdConfirm(d));
+        }
+        Some(Dialog::PasswordPrompt(mut d)) => {
+            match key.code {
+                KeyCode::Enter => {
+                    if let Some(cluster) = app.config.clusters.get(d.cluster_index).cloned() {
+                        // Sent beside the cluster, so it is never taken for a secret reference
+                        let password = Some(std::mem::take(&mut d.password));
+                        app.dialog = None;
+                        if d.test_only {
+                            test_cluster(app, cluster, password, kafka_tx);
+                        } else {
+                            connect_cluster(app, d.cluster_index, cluster, password, kafka_tx);
+                        }
+                        return;
+                    }
+                }
+                KeyCode::Char(c) => d.password.push(c),
+                KeyCode::Backspace => {
+                    d.password.pop();
+                }
+                _ => {}
+            }
+            app.dialog = Some(Dialog::PasswordPrompt(d));
+        }
+        Some(Dialog::ImportClusters(mut d)) => {
+            match (key.code, d.preview.take()) {
+                (KeyCode::Enter, Some(preview)) => {
+                    let count = preview.len();
+                    for imported in preview {
+                        let mut cluster = imported.cluster;
+                        cluster.name = unique_cluster_name(&app.config.clusters, &cluster.name);
+                        app.config.clusters.push(cluster);
+                    }
+                    let _ = app.config.save();
+                    app.log_info(&format!("Imported {} cluster(s)", count));
+                    app.dialog = None;
+                    return;
+                }
+                (KeyCode::Enter, None) => {
+                    let mut preview = Vec::new();
+                    d.errors.clear();
+                    for path in d.paths.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+                        match import::import_file(std::path::Path::new(path)) {
+                            Ok(imported) => preview.push(imported),
+                            Err(e) => d.errors.push(format!("{:#}", e)),
+                        }
+                    }
+                    if !preview.is_empty() {
+                        d.preview = Some(preview);
+                    }
+                }
+                // Any edit goes back from the preview to the paths
+                (KeyCode::Char(c), _) => d.paths.push(c),
+                (KeyCode::Backspace, Some(_)) => {}
+                (KeyCode::Backspace, None) => {
+                    d.paths.pop();
+                }
+                (_, preview) => d.preview = preview,
+            }
+            app.dialog = Some(Dialog::ImportClusters(d));
+        }
+        Some(Dialog::Search(mut d)) => {
+            match key.code {
+                KeyCode::Tab => d.focused_field = (d.focused_field + 1) % 3,
+                KeyCode::BackTab => d.focused_field = (d.focused_field + 2) % 3,
+                KeyCode::Enter => match d.parse() {
+                    Ok((filter, range)) => {
+                        app.search = SearchState {
+                            id: app.search.id + 1,
+                            topic: d.topic.clone(),
+                            query: d.query.clone(),
+                            range: range.describe(),
+                            running: true,
+                            ..Default::default()
+                        };
+                        let _ = kafka_tx.send(KafkaCommand::StartSearch {
+                            id: app.search.id,
+                            topic: d.topic.clone(),
+                            filter,
+                            range,
+                        });
+                        app.log_info(&format!("Searching {}: {}", d.topic, d.query));
+                        app.dialog = None;
+                        app.navigate(Route::TopicSearch(d.topic));
+                        return;
+                    }
+                    Err(e) => d.error = Some(e),
+                },
+                KeyCode::Char(c) => {
+                    d.field_mut().push(c);
+                    d.error = None;
+                }
+                KeyCode::Backspace => {
+                    d.field_mut().pop();
+                    d.error = None;
+                }
+                _ => {}
+            }
+            app.dialog = Some(Dialog::Search(d));
+        }
+        Some(Dialog::Timeline(mut d)) => {
+            match key.code {
+                KeyCode::Tab | KeyCode::BackTab => d.focused_field = (d.focused_field + 1) % 2,
+                KeyCode::Left | KeyCode::Right => d.bucket = d.bucket.toggle(),
+                KeyCode::Enter => match d.parse() {
+                    Ok((from, to)) => {
+                        app.timeline = TimelineState {
+                            topic: d.topic.clone(),
+                            loading: true,
+                            ..Default::default()
+                        };
+                        let _ = kafka_tx.send(KafkaCommand::FetchTimeline {
+                            topic: d.topic.clone(),
+                            from,
+                            to,
+                            bucket: d.bucket,
+                        });
+                        app.log_info(&format!("Counting messages of {} per {}...", d.topic, d.bucket.label()));
+                        app.dialog = None;
+                        app.navigate(Route::Timeline(d.topic));
+                        return;
+                    }
+                    Err(e) => d.error = Some(e),
+                },
+                KeyCode::Char(c) => {
+                    d.field_mut().push(c);
+                    d.error = None;
+                }
+                KeyCode::Backspace => {
+                    d.field_mut().pop();
+                    d.error = None;
+                }
+                _ => {}
+            }
+            app.dialog = Some(Dialog::Timeline(d));
+        }
+        Some(Dialog::GoToOffset(mut d)) => {
+            match key.code {
+                KeyCode::Tab => d.focused_field = (d.focused_field + 1) % 3,
+                KeyCode::BackTab => d.focused_field = (d.focused_field + 2) % 3,
+                KeyCode::Enter => match d.parse() {
+                    Ok((partition, offset, context)) => {
+                        // The fetched records replace the list, so stop appending to it
+                        let _ = kafka_tx.send(KafkaCommand::StopConsuming);
+                        app.messages.consuming = false;
+                        let _ = kafka_tx.send(KafkaCommand::FetchRecord {
+                            topic: d.topic.clone(),
+                            partition,
+                            offset,
+                            context,
+                        });
+                        app.log_info(&format!("Fetching partition {} offset {}...", partition, offset));
+                        app.dialog = None;
+                        return;
+                    }
+                    Err(e) => d.error = Some(e),
+                },
+                KeyCode::Char(c) if c.is_ascii_digit() => {
+                    d.field_mut().push(c);
+                    d.error = None;
+                }
+                KeyCode::Backspace => {
+                    d.field_mut().pop();
+                    d.error = None;
+                }
+                _ => {}
+            }
+            app.dialog = Some(Dialog::GoToOffset(d));
+        }
+        Some(Dialog::ReplicationFactor(mut d)) => {
+            match key.code {
+                KeyCode::Enter => {
+                    if let Ok(replication_factor) = d.replication_factor.parse::<usize>() {
+                        app.dialog = None;
+                        let goal = ReassignmentGoal::ChangeReplicationFactor {
+                            topic: d.topic.clone(),
+                            replication_factor,
+                        };
+                        plan_reassignment(app, goal, kafka_tx);
+                        return;
+                    }
+                }
+                KeyCode::Char(c) if c.is_ascii_digit() => {
+                    d.replication_factor.push(c);
+                }
+                KeyCode::Backspace => {
+                    d.replication_factor.pop();
+                }
+                _ => {}
+            }
+            app.dialog = Some(Dialog::ReplicationFactor(d));
+        }
+        Some(Dialog::Confirm(d)) => {
+            if key.code == KeyCode::Enter {
+                match d.action {
+                    ConfirmAction::SubmitReassignment => {
+                        if let Some(plan) = app.reassignment.plan.clone() {
+                            if send_mutation(app, KafkaCommand::SubmitReassignment(plan), None, kafka_tx) {
+                                app.log_info("Submitting reassignment...");
+                            }
+                        }
+                    }
+                    ConfirmAction::CreateAcl(binding) => {
+                        let description = binding.describe();
+                        if send_mutation(app, KafkaCommand::CreateAcl(binding), None, kafka_tx) {
+                            app.log_info(&format!("Creating ACL: {}...", description));
+                        }
+                    }
+                    ConfirmAction::DeleteAcl(binding) => {
+                        let description = binding.describe();
+                        if send_mutation(app, KafkaCommand::DeleteAcl(binding), None, kafka_tx) {
+                            app.log_info(&format!("Deleting ACL: {}...", description));
+                        }
+                    }
+                }
+                return;
+            }
+            app.dialog = Some(Dialog::Confirm(d));
+        }
+        Some(Dialog::CreateAcl(mut d)) => {
+            match key.code {
+                KeyCode::Tab => {
+                    d.focused_field = (d.focused_field + 1) % CreateAclDialog::FIELD_COUNT;
+                }
+                KeyCode::BackTab => {
+                    d.focused_field = (d.focused_field + CreateAclDialog::FIELD_COUNT - 1) % CreateAclDialog::FIELD_COUNT;
+                }
+                KeyCode::Left => d.cycle(-1),
+                KeyCode::Right => d.cycle(1),
+                KeyCode::Enter => {
+                    let binding = d.to_binding();
+                    if !binding.resource_name.is_empty() && binding.principal.contains(':') && !binding.host.is_empty() {
+                        app.dialog = Some(Dialog::Confirm(ConfirmDialog::new(
+                            "Create ACL",
+                            &format!("Create binding: {}?", binding.describe()),
+                            ConfirmAction::CreateAcl(binding),
+                        )));
+                        return;
+                    }
+                }
+                KeyCode::Char(c) => {
+                    if let Some(field) = d.text_field() {
+                        field.push(c);
+                    }
+                }
+                KeyCode::Backspace => {
+                    if let Some(field) = d.text_field() {
+                        field.pop();
+                    }
+                }
+                _ => {}
+            }
+            app.dialog = Some(Dialog::CreateAcl(d));
+        }
         Some(Dialog::ConnectionTest(_d)) => {
             // Only Esc closes this, already handled above
             app.dialog = Some(Dialog::ConnectionTest(_d));
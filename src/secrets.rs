@@ -0,0 +1,59 @@
+//! Secret references for values that should not live in config.toml.
+//!
+//! A secret is either a literal or one of
+//! - `env:VAR` — the value of an environment variable
+//! - `file:/path` — the contents of a file, trailing newline trimmed
+//! - `cmd:command` — the first line printed by a shell command, e.g. `cmd:pass show kafka/prod`
+//! - `prompt:` — asked for in a dialog every time the cluster is connected
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::path::PathBuf;
+use std::process::Command;
+
+pub const PROMPT: &str = "prompt:";
+
+const SCHEMES: [&str; 4] = ["env:", "file:", "cmd:", PROMPT];
+
+pub fn is_reference(value: &str) -> bool {
+    SCHEMES.iter().any(|scheme| value.starts_with(scheme))
+}
+
+/// `path` with a leading `~/` replaced by the home directory.
+pub fn expand_home(path: &str) -> Result<PathBuf> {
+    match path.strip_prefix("~/") {
+        Some(rest) => Ok(dirs::home_dir().ok_or_else(|| anyhow!("no home directory"))?.join(rest)),
+        None => Ok(path.into()),
+    }
+}
+
+/// Resolves a secret reference to its value. Literals are returned as they are.
+pub fn resolve(value: &str) -> Result<String> {
+    if let Some(var) = value.strip_prefix("env:") {
+        std::env::var(var).with_context(|| format!("environment variable {} is not set", var))
+    } else if let Some(path) = value.strip_prefix("file:") {
+        let path = expand_home(path)?;
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        Ok(contents.trim_end_matches(['\r', '\n']).to_string())
+    } else if let Some(command) = value.strip_prefix("cmd:") {
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .output()
+            .with_context(|| format!("failed to run `{}`", command))?;
+        if !output.status.success() {
+            bail!(
+                "`{}` exited with {}: {}",
+                command,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(stdout.lines().next().unwrap_or_default().to_string())
+    } else if value == PROMPT {
+        bail!("password was not entered")
+    } else {
+        Ok(value.to_string())
+    }
+}
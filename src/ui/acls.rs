@@ -0,0 +1,91 @@
+use crate::app::*;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+pub fn render_acls(app: &App, frame: &mut Frame, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(2),
+        ])
+        .split(area);
+
+    let title = Paragraph::new(" ACLs")
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(title, chunks[0]);
+
+    let search_style = if app.focus == Focus::Search {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let search = Paragraph::new(format!(" 🔍 {}", if app.acls.search_query.is_empty() { "Filter by principal or resource..." } else { &app.acls.search_query }))
+        .style(search_style)
+        .block(Block::default().borders(Borders::ALL).title(" Search ").border_style(search_style));
+    frame.render_widget(search, chunks[1]);
+
+    let filtered = app.acls.filtered_bindings();
+    let header = Row::new(vec![
+        Cell::from("Resource Type").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Resource").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Pattern").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Principal").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Host").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Operation").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Permission").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+    ]).height(1);
+
+    let rows: Vec<Row> = filtered.iter().enumerate().map(|(i, acl)| {
+        let style = if i == app.acls.selected {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let permission = match acl.permission {
+            AclPermission::Allow => Span::styled("Allow", Style::default().fg(Color::Green)),
+            AclPermission::Deny => Span::styled("Deny", Style::default().fg(Color::Red)),
+            AclPermission::Unknown => Span::raw("Unknown"),
+        };
+        Row::new(vec![
+            Cell::from(acl.resource_type.label()),
+            Cell::from(acl.resource_name.clone()),
+            Cell::from(acl.pattern_type.label()),
+            Cell::from(acl.principal.clone()),
+            Cell::from(acl.host.clone()),
+            Cell::from(acl.operation.label()),
+            Cell::from(permission),
+        ]).style(style)
+    }).collect();
+
+    let mut table_state = TableState::default().with_selected(Some(app.acls.selected));
+    let table = Table::new(rows, [
+        Constraint::Length(16),
+        Constraint::Min(20),
+        Constraint::Length(10),
+        Constraint::Min(16),
+        Constraint::Length(15),
+        Constraint::Length(16),
+        Constraint::Length(11),
+    ])
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(if app.acls.loading {
+                " Bindings (loading...) ".to_string()
+            } else {
+                format!(" Bindings ({}) ", filtered.len())
+            })
+            .title_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(if app.focus == Focus::Content { Color::Cyan } else { Color::DarkGray })),
+    );
+    frame.render_stateful_widget(table, chunks[2], &mut table_state);
+
+    let help = Paragraph::new(" c: Create | d: Delete | /: Search | r: Refresh ")
+        .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, chunks[3]);
+}
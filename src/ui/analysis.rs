@@ -0,0 +1,182 @@
+use super::client_stats::format_bytes;
+use crate::app::*;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+/// Skew above this is worth a look, above twice this it's a problem
+const SKEW_WARN: f64 = 1.5;
+
+pub fn render_analysis(app: &App, frame: &mut Frame, area: Rect, topic: &str) {
+    let state = &app.analysis;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Percentage(45),
+            Constraint::Min(6),
+            Constraint::Length(2),
+        ])
+        .split(area);
+
+    let mut title = vec![
+        Span::styled(format!(" Analysis: {} ", topic), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("[newest {} per partition] ", state.sample), Style::default().fg(Color::Yellow)),
+    ];
+    if let Some(ref analysis) = state.analysis {
+        title.push(Span::styled(
+            format!(
+                "{} sampled, {} distinct key(s), {:.1}% null keys, skew ",
+                analysis.sampled,
+                analysis.distinct_keys,
+                analysis.null_key_ratio() * 100.0
+            ),
+            Style::default().fg(Color::DarkGray),
+        ));
+        title.push(Span::styled(
+            format!("{:.2}", analysis.skew),
+            Style::default().fg(skew_color(analysis.skew)).add_modifier(Modifier::BOLD),
+        ));
+        if analysis.truncated() > 0 {
+            title.push(Span::styled(
+                format!("  {} partition(s) only partly sampled before the deadline", analysis.truncated()),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+    }
+    let title_widget = Paragraph::new(Line::from(title))
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(title_widget, chunks[0]);
+
+    let Some(ref analysis) = state.analysis else {
+        let (text, color) = match (&state.error, state.loading) {
+            (Some(error), _) => (format!(" ✗ {}", error), Color::Red),
+            (None, true) => (" Sampling the newest records of every partition...".to_string(), Color::DarkGray),
+            (None, false) => (" Press r to analyse the topic".to_string(), Color::DarkGray),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Partitions ")
+            .title_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(Paragraph::new(text).style(Style::default().fg(color)).block(block), chunks[1]);
+        render_help(frame, chunks[3]);
+        return;
+    };
+
+    let header_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let header = Row::new(vec![
+        Cell::from("Part").style(header_style),
+        Cell::from("Records").style(header_style),
+        Cell::from("Share").style(header_style),
+        Cell::from("Sampled").style(header_style),
+        Cell::from("Avg size").style(header_style),
+        Cell::from("Est. size").style(header_style),
+        Cell::from("Null keys").style(header_style),
+        Cell::from("Keys").style(header_style),
+    ]).height(1);
+
+    let total = analysis.total_records().max(1) as f64;
+    let mean = total / analysis.partitions.len().max(1) as f64;
+    let rows: Vec<Row> = analysis.partitions.iter().map(|p| {
+        let share = p.records as f64 / total;
+        let color = if mean > 0.0 { skew_color(p.records as f64 / mean) } else { Color::White };
+        let null_keys = if p.sampled > 0 {
+            format!("{:.1}%", p.null_keys as f64 / p.sampled as f64 * 100.0)
+        } else {
+            "-".to_string()
+        };
+        Row::new(vec![
+            Cell::from(p.partition.to_string()),
+            Cell::from(p.records.to_string()).style(Style::default().fg(color)),
+            Cell::from(format!("{:>5.1}%", share * 100.0)),
+            if p.truncated {
+                Cell::from(format!("{}/{}", p.sampled, p.requested)).style(Style::default().fg(Color::Yellow))
+            } else {
+                Cell::from(p.sampled.to_string())
+            },
+            Cell::from(p.average_size().map(|b| format_bytes(b as i64)).unwrap_or_else(|| "-".to_string())),
+            Cell::from(p.estimated_bytes().map(|b| format_bytes(b as i64)).unwrap_or_else(|| "-".to_string())),
+            Cell::from(null_keys),
+            Cell::from(p.distinct_keys.to_string()),
+        ]).style(Style::default().fg(Color::White))
+    }).collect();
+
+    let partitions = Table::new(rows, [
+        Constraint::Length(6),
+        Constraint::Length(12),
+        Constraint::Length(7),
+        Constraint::Length(13),
+        Constraint::Length(10),
+        Constraint::Length(11),
+        Constraint::Length(10),
+        Constraint::Length(8),
+    ])
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Partitions ({}), {} record(s) by watermarks ", analysis.partitions.len(), analysis.total_records()))
+            .title_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_widget(partitions, chunks[1]);
+
+    let header = Row::new(vec![
+        Cell::from("Key").style(header_style),
+        Cell::from("Count").style(header_style),
+        Cell::from("Share").style(header_style),
+        Cell::from("Partitions").style(header_style),
+    ]).height(1);
+
+    let sampled = analysis.sampled.max(1) as f64;
+    let rows: Vec<Row> = analysis.top_keys.iter().enumerate().map(|(i, key)| {
+        let style = if i == state.selected {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let partitions: Vec<String> = key.partitions.iter().map(|p| p.to_string()).collect();
+        Row::new(vec![
+            Cell::from(key.key.clone()),
+            Cell::from(key.count.to_string()),
+            Cell::from(format!("{:>5.1}%", key.count as f64 / sampled * 100.0)),
+            Cell::from(partitions.join(", ")),
+        ]).style(style)
+    }).collect();
+
+    let mut table_state = TableState::default().with_selected(Some(state.selected));
+    let keys = Table::new(rows, [
+        Constraint::Min(30),
+        Constraint::Length(9),
+        Constraint::Length(7),
+        Constraint::Length(16),
+    ])
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Top keys ({} of {}) ", analysis.top_keys.len(), analysis.distinct_keys))
+            .title_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(if app.focus == Focus::Content { Color::Cyan } else { Color::DarkGray })),
+    );
+    frame.render_stateful_widget(keys, chunks[2], &mut table_state);
+
+    render_help(frame, chunks[3]);
+}
+
+/// Color of a partition size relative to the mean, or of the skew score
+fn skew_color(ratio: f64) -> Color {
+    if ratio >= SKEW_WARN * 2.0 {
+        Color::Red
+    } else if ratio >= SKEW_WARN {
+        Color::Yellow
+    } else {
+        Color::Green
+    }
+}
+
+fn render_help(frame: &mut Frame, area: Rect) {
+    let help = Paragraph::new(" Enter: Browse key | +/-: Sample size | r: Run again | Esc: Back ")
+        .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, area);
+}
@@ -0,0 +1,112 @@
+use crate::app::*;
+use crate::audit;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+pub fn render_audit_log(app: &App, frame: &mut Frame, area: Rect) {
+    let state = &app.audit;
+    let mut constraints = vec![Constraint::Length(3), Constraint::Length(3), Constraint::Min(5)];
+    if state.show_detail {
+        constraints.push(Constraint::Length(14));
+    }
+    constraints.push(Constraint::Length(2));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(area);
+
+    let title = Paragraph::new(Line::from(vec![
+        Span::styled(" Audit Log ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(audit::audit_path().display().to_string(), Style::default().fg(Color::DarkGray)),
+    ]))
+    .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(title, chunks[0]);
+
+    let search_style = if app.focus == Focus::Search {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let search = Paragraph::new(format!(" 🔍 {}", if state.search_query.is_empty() { "Filter by cluster, user, operation or arguments..." } else { &state.search_query }))
+        .style(search_style)
+        .block(Block::default().borders(Borders::ALL).title(" Search ").border_style(search_style));
+    frame.render_widget(search, chunks[1]);
+
+    let filtered = state.filtered_entries();
+    let header = Row::new(vec![
+        Cell::from("Time").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("User").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Cluster").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Operation").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Arguments").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Result").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+    ]).height(1);
+
+    let rows: Vec<Row> = filtered.iter().enumerate().map(|(i, entry)| {
+        let style = if i == state.selected {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let time = chrono::DateTime::parse_from_rfc3339(&entry.timestamp)
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|_| entry.timestamp.clone());
+        let result_color = match entry.result.as_str() {
+            "ok" => Color::Green,
+            "rejected" => Color::Yellow,
+            _ => Color::Red,
+        };
+        Row::new(vec![
+            Cell::from(time),
+            Cell::from(entry.user.clone()),
+            Cell::from(entry.cluster.clone()),
+            Cell::from(entry.operation.clone()),
+            Cell::from(entry.arguments.to_string()),
+            Cell::from(Span::styled(entry.result.clone(), Style::default().fg(result_color))),
+        ]).style(style)
+    }).collect();
+
+    let mut table_state = TableState::default().with_selected(Some(state.selected));
+    let table = Table::new(rows, [
+        Constraint::Length(19),
+        Constraint::Length(12),
+        Constraint::Length(16),
+        Constraint::Length(24),
+        Constraint::Min(20),
+        Constraint::Length(9),
+    ])
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(match &state.error {
+                Some(error) => format!(" Entries (error: {}) ", error),
+                None => format!(" Entries ({}) ", filtered.len()),
+            })
+            .title_style(Style::default().fg(if state.error.is_some() { Color::Red } else { Color::Cyan }))
+            .border_style(Style::default().fg(if app.focus == Focus::Content { Color::Cyan } else { Color::DarkGray })),
+    );
+    frame.render_stateful_widget(table, chunks[2], &mut table_state);
+
+    if state.show_detail {
+        let detail = filtered
+            .get(state.selected)
+            .and_then(|entry| serde_json::to_string_pretty(entry).ok())
+            .unwrap_or_default();
+        let detail = Paragraph::new(detail)
+            .style(Style::default().fg(Color::White))
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Entry ")
+                    .title_style(Style::default().fg(Color::Cyan))
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            );
+        frame.render_widget(detail, chunks[3]);
+    }
+
+    let help = Paragraph::new(" Enter: Details | /: Search | r: Reload ")
+        .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, chunks[chunks.len() - 1]);
+}
@@ -0,0 +1,150 @@
+use crate::app::*;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+pub fn render_brokers(app: &App, frame: &mut Frame, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(2),
+        ])
+        .split(area);
+
+    let title_text = match &app.dashboard.cluster_id {
+        Some(id) => format!(" Brokers (cluster {})", id),
+        None => " Brokers".to_string(),
+    };
+    let title = Paragraph::new(title_text)
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(title, chunks[0]);
+
+    let header = Row::new(vec![
+        Cell::from("ID").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Host").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Port").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Role").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Leaders").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Replicas").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+    ]).height(1);
+
+    let rows: Vec<Row> = app.brokers.brokers.iter().enumerate().map(|(i, broker)| {
+        let style = if i == app.brokers.selected {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let role = if broker.is_controller {
+            Span::styled("Controller", Style::default().fg(Color::Yellow))
+        } else {
+            Span::raw("")
+        };
+        Row::new(vec![
+            Cell::from(broker.id.to_string()),
+            Cell::from(broker.host.clone()),
+            Cell::from(broker.port.to_string()),
+            Cell::from(role),
+            Cell::from(broker.leader_count.to_string()),
+            Cell::from(broker.replica_count.to_string()),
+        ]).style(style)
+    }).collect();
+
+    let table = Table::new(rows, [
+        Constraint::Length(6),
+        Constraint::Min(20),
+        Constraint::Length(7),
+        Constraint::Length(12),
+        Constraint::Length(9),
+        Constraint::Length(10),
+    ])
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(if app.brokers.loading {
+                " Brokers (loading...) ".to_string()
+            } else {
+                format!(" Brokers ({}) ", app.brokers.brokers.len())
+            })
+            .title_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(if app.focus == Focus::Content { Color::Cyan } else { Color::DarkGray })),
+    );
+    frame.render_widget(table, chunks[1]);
+
+    let help = Paragraph::new(" Enter: Config | x: Drain broker | b: Rebalance | r: Refresh ")
+        .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, chunks[2]);
+}
+
+pub fn render_broker_detail(app: &App, frame: &mut Frame, area: Rect, broker_id: i32) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(2),
+        ])
+        .split(area);
+
+    let endpoint = app.brokers.brokers.iter()
+        .find(|b| b.id == broker_id)
+        .map(|b| format!(" ({}:{})", b.host, b.port))
+        .unwrap_or_default();
+    let title = Paragraph::new(format!(" Broker {}{}", broker_id, endpoint))
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(title, chunks[0]);
+
+    let header = Row::new(vec![
+        Cell::from("Name").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Value").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Source").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+    ]).height(1);
+
+    let ds = &app.broker_detail;
+    let rows: Vec<Row> = ds.config.iter().enumerate().map(|(i, entry)| {
+        let value = if entry.is_sensitive {
+            "******".to_string()
+        } else {
+            entry.value.clone().unwrap_or_else(|| "<null>".to_string())
+        };
+        let style = if i == ds.selected {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else if entry.is_default {
+            Style::default().fg(Color::DarkGray)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        Row::new(vec![
+            Cell::from(entry.name.clone()),
+            Cell::from(value),
+            Cell::from(entry.source.clone()),
+        ]).style(style)
+    }).collect();
+
+    let mut table_state = TableState::default().with_selected(Some(ds.selected));
+    let table = Table::new(rows, [
+        Constraint::Min(30),
+        Constraint::Min(20),
+        Constraint::Length(22),
+    ])
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(if ds.loading {
+                " Configuration (loading...) ".to_string()
+            } else {
+                format!(" Configuration ({}) ", ds.config.len())
+            })
+            .title_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_stateful_widget(table, chunks[1], &mut table_state);
+
+    let help = Paragraph::new(" Esc: Back | r: Refresh ")
+        .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, chunks[2]);
+}
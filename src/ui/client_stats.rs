@@ -0,0 +1,200 @@
+use crate::app::*;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+pub fn render_client_stats(app: &App, frame: &mut Frame, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Percentage(45),
+            Constraint::Min(5),
+            Constraint::Length(2),
+        ])
+        .split(area);
+
+    let state = &app.client_stats;
+    let tabs: Vec<Span> = state.clients.iter().enumerate().flat_map(|(i, c)| {
+        let style = if i == state.selected_client {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        vec![Span::raw(" "), Span::styled(format!(" {} ", c.role.label()), style)]
+    }).collect();
+    let mut title_spans = vec![Span::styled(" Client Stats ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))];
+    title_spans.extend(tabs);
+    let title = Paragraph::new(Line::from(title_spans))
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(title, chunks[0]);
+
+    let stats = match state.current() {
+        Some(stats) => stats,
+        None => {
+            let empty = Paragraph::new("Waiting for the first statistics report from librdkafka...")
+                .style(Style::default().fg(Color::DarkGray))
+                .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+            frame.render_widget(empty, chunks[1]);
+            return;
+        }
+    };
+
+    let summary = Paragraph::new(Line::from(vec![
+        Span::styled(" Producer queue: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(format!("{} msgs / {}", stats.queued_msgs, format_bytes(stats.queued_bytes as i64)), Style::default().fg(Color::White)),
+        Span::styled("  Sent: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(format_bytes(stats.tx_bytes), Style::default().fg(Color::White)),
+        Span::styled("  Received: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(format_bytes(stats.rx_bytes), Style::default().fg(Color::White)),
+        Span::styled("  Reported: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(stats.received_at.format("%H:%M:%S").to_string(), Style::default().fg(Color::White)),
+    ]))
+    .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(summary, chunks[1]);
+
+    render_broker_stats(stats, frame, chunks[2]);
+    render_partition_stats(stats, frame, chunks[3]);
+
+    let help = Paragraph::new(" ←/→: Switch client | RTT is network round trip; Outbuf/Wait are requests queued locally / awaiting a reply ")
+        .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, chunks[4]);
+}
+
+fn render_broker_stats(stats: &ClientStats, frame: &mut Frame, area: Rect) {
+    let header = Row::new(vec![
+        Cell::from("ID").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Broker").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("State").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("RTT avg").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("RTT p99").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Outbuf").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Wait").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Tx").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Rx").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Timeouts").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+    ]).height(1);
+
+    let rows: Vec<Row> = stats.brokers.iter().map(|b| {
+        let state_color = if b.state == "UP" { Color::Green } else { Color::Yellow };
+        Row::new(vec![
+            Cell::from(b.node_id.to_string()),
+            Cell::from(b.name.clone()),
+            Cell::from(Span::styled(b.state.clone(), Style::default().fg(state_color))),
+            Cell::from(format_micros(b.rtt_avg)),
+            Cell::from(format_micros(b.rtt_p99)),
+            Cell::from(b.outbuf_cnt.to_string()),
+            Cell::from(b.waitresp_cnt.to_string()),
+            Cell::from(format_bytes(b.tx_bytes as i64)),
+            Cell::from(format_bytes(b.rx_bytes as i64)),
+            Cell::from(b.req_timeouts.to_string()),
+        ]).style(Style::default().fg(Color::White))
+    }).collect();
+
+    let table = Table::new(rows, [
+        Constraint::Length(5),
+        Constraint::Min(20),
+        Constraint::Length(8),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(7),
+        Constraint::Length(6),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(9),
+    ])
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Brokers ({}) ", stats.brokers.len()))
+            .title_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_widget(table, area);
+}
+
+fn render_partition_stats(stats: &ClientStats, frame: &mut Frame, area: Rect) {
+    let header = Row::new(vec![
+        Cell::from("Topic").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Partition").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Leader").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Fetch State").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Fetch Queue").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Next Offset").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Lag").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Msg Queue").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("In Flight").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+    ]).height(1);
+
+    let rows: Vec<Row> = stats.partitions.iter().map(|p| {
+        let fetch_color = match p.fetch_state.as_str() {
+            "active" => Color::Green,
+            "none" | "stopped" => Color::DarkGray,
+            _ => Color::Yellow,
+        };
+        Row::new(vec![
+            Cell::from(p.topic.clone()),
+            Cell::from(p.partition.to_string()),
+            Cell::from(p.leader.to_string()),
+            Cell::from(Span::styled(p.fetch_state.clone(), Style::default().fg(fetch_color))),
+            Cell::from(p.fetchq_cnt.to_string()),
+            Cell::from(format_offset(p.next_offset)),
+            Cell::from(format_offset(p.consumer_lag)),
+            Cell::from(p.msgq_cnt.to_string()),
+            Cell::from(p.xmit_msgq_cnt.to_string()),
+        ]).style(Style::default().fg(Color::White))
+    }).collect();
+
+    let table = Table::new(rows, [
+        Constraint::Min(20),
+        Constraint::Length(10),
+        Constraint::Length(7),
+        Constraint::Length(14),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
+    ])
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Partitions ({}) ", stats.partitions.len()))
+            .title_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_widget(table, area);
+}
+
+fn format_micros(value: Option<i64>) -> String {
+    match value {
+        Some(us) if us > 0 => format!("{:.1} ms", us as f64 / 1000.0),
+        _ => "-".to_string(),
+    }
+}
+
+/// librdkafka reports -1 for offsets and lag it doesn't know yet.
+fn format_offset(value: i64) -> String {
+    if value < 0 {
+        "-".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn format_bytes(bytes: i64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
@@ -0,0 +1,67 @@
+use crate::config::ConfigError;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+pub fn render_config_error(error: &ConfigError, note: Option<&str>, frame: &mut Frame) {
+    let area = frame.area();
+    frame.render_widget(Clear, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(3),
+        ])
+        .margin(2)
+        .split(area);
+
+    let title = Paragraph::new(format!("⚠ Problems in {}", error.path.display()))
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Yellow)));
+    frame.render_widget(title, chunks[0]);
+
+    let mut lines = Vec::new();
+    for issue in &error.issues {
+        match issue.line {
+            Some(line) => {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("line {}: ", line), Style::default().fg(Color::Cyan)),
+                    Span::styled(issue.message.clone(), Style::default().fg(Color::Red)),
+                ]));
+                if let Some(text) = error.lines.get(line - 1) {
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("{:>6} │ ", line), Style::default().fg(Color::DarkGray)),
+                        Span::styled(text.clone(), Style::default().fg(Color::White)),
+                    ]));
+                }
+            }
+            None => lines.push(Line::from(Span::styled(issue.message.clone(), Style::default().fg(Color::Red)))),
+        }
+        lines.push(Line::from(""));
+    }
+    if let Some(note) = note {
+        lines.push(Line::from(Span::styled(note.to_string(), Style::default().fg(Color::Yellow))));
+    }
+    let issues = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} issue(s) ", error.issues.len()))
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+    frame.render_widget(issues, chunks[1]);
+
+    let mut help = String::from(" Fix the file, then r: Reload");
+    if error.config.is_some() {
+        help.push_str(" | c: Continue anyway");
+    }
+    help.push_str(" | q: Quit ");
+    let help_text = Paragraph::new(help)
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(help_text, chunks[2]);
+}
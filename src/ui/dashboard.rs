@@ -13,7 +13,11 @@ pub fn render_dashboard(app: &App, frame: &mut Frame, area: Rect) {
         .split(area);
 
     // Title
-    let title = Paragraph::new(" Cluster Dashboard")
+    let title_text = match &app.dashboard.cluster_id {
+        Some(id) => format!(" Cluster Dashboard ({})", id),
+        None => " Cluster Dashboard".to_string(),
+    };
+    let title = Paragraph::new(title_text)
         .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
         .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
     frame.render_widget(title, chunks[0]);
@@ -50,7 +54,13 @@ pub fn render_dashboard(app: &App, frame: &mut Frame, area: Rect) {
     render_stat_card(frame, stats_chunks[3], "Partitions", &ds.partition_count.to_string(), Color::Cyan);
 
     // Broker list
-    let broker_ids: Vec<String> = ds.brokers_online.iter().map(|b| format!("Broker {} ● Online", b)).collect();
+    let broker_ids: Vec<String> = ds.brokers_online.iter().map(|b| {
+        if ds.controller_id == Some(*b) {
+            format!("Broker {} ● Online (controller)", b)
+        } else {
+            format!("Broker {} ● Online", b)
+        }
+    }).collect();
     let broker_text = if broker_ids.is_empty() {
         "No broker data. Press 'r' to refresh.".to_string()
     } else {
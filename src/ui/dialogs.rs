@@ -10,6 +10,16 @@ pub fn render_dialog(dialog: &Dialog, frame: &mut Frame) {
         Dialog::ResetOffset(d) => render_reset_offset(d, frame),
         Dialog::EditCluster(d) => render_edit_cluster(d, frame),
         Dialog::ConnectionTest(d) => render_connection_test(d, frame),
+        Dialog::ElectLeaders(d) => render_elect_leaders(d, frame),
+        Dialog::ReplicationFactor(d) => render_replication_factor(d, frame),
+        Dialog::Confirm(d) => render_confirm(d, frame),
+        Dialog::CreateAcl(d) => render_create_acl(d, frame),
+        Dialog::PasswordPrompt(d) => render_password_prompt(d, frame),
+        Dialog::ImportClusters(d) => render_import_clusters(d, frame),
+        Dialog::ProdConfirm(d) => render_prod_confirm(d, frame),
+        Dialog::Search(d) => render_search(d, frame),
+        Dialog::GoToOffset(d) => render_go_to_offset(d, frame),
+        Dialog::Timeline(d) => render_timeline(d, frame),
     }
 }
 
@@ -132,6 +142,48 @@ fn render_delete_confirm(dialog: &DeleteConfirmDialog, frame: &mut Frame) {
     frame.render_widget(help, chunks[2]);
 }
 
+fn render_prod_confirm(dialog: &ProdConfirmDialog, frame: &mut Frame) {
+    let area = centered_rect(50, 30, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" ⚠ PRODUCTION: {} ", dialog.cluster_name))
+        .title_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+        .border_style(Style::default().fg(Color::Red));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(3),
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ])
+        .margin(1)
+        .split(inner);
+
+    let warning = Paragraph::new(format!(
+        " You are about to {} on production cluster '{}'.",
+        dialog.mutation, dialog.cluster_name
+    ))
+    .style(Style::default().fg(Color::Yellow))
+    .wrap(Wrap { trim: false });
+    frame.render_widget(warning, chunks[0]);
+
+    let input = Paragraph::new(format!(" Type the cluster name to confirm: {}", dialog.confirm_input))
+        .style(Style::default().fg(Color::White))
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Red)));
+    frame.render_widget(input, chunks[1]);
+
+    let help = Paragraph::new(" Enter: Confirm | Esc: Cancel ")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[2]);
+}
+
 fn render_produce_message(dialog: &ProduceMessageDialog, frame: &mut Frame) {
     let area = centered_rect(60, 60, frame.area());
     frame.render_widget(Clear, area);
@@ -156,7 +208,8 @@ fn render_produce_message(dialog: &ProduceMessageDialog, frame: &mut Frame) {
         .margin(1)
         .split(inner);
 
-    render_input_field(frame, chunks[0], "Key (optional)", &dialog.key, dialog.focused_field == 0);
+    let key = if dialog.null_key { "<null>" } else { dialog.key.as_str() };
+    render_input_field(frame, chunks[0], "Key", key, dialog.focused_field == 0);
 
     // Value field (multi-line)
     let value_style = if dialog.focused_field == 1 {
@@ -169,13 +222,20 @@ fn render_produce_message(dialog: &ProduceMessageDialog, frame: &mut Frame) {
     } else {
         Style::default().fg(Color::DarkGray)
     };
-    let value_widget = Paragraph::new(format!(" {}", if dialog.value.is_empty() { "(enter message value)" } else { &dialog.value }))
+    let value = if dialog.null_value {
+        "<null> (tombstone)"
+    } else if dialog.value.is_empty() {
+        "(enter message value)"
+    } else {
+        &dialog.value
+    };
+    let value_widget = Paragraph::new(format!(" {}", value))
         .style(value_style)
         .wrap(Wrap { trim: false })
         .block(Block::default().borders(Borders::ALL).title(" Value ").border_style(value_border));
     frame.render_widget(value_widget, chunks[1]);
 
-    render_input_field(frame, chunks[2], "Headers (k1:v1,k2:v2)", &dialog.headers, dialog.focused_field == 2);
+    render_input_field(frame, chunks[2], "Headers (k1:v1,k2:v2; a name alone is null)", &dialog.headers, dialog.focused_field == 2);
 
     if let Some(ref result) = dialog.result_message {
         let result_color = if result.starts_with("✓") { Color::Green } else { Color::Red };
@@ -183,7 +243,7 @@ fn render_produce_message(dialog: &ProduceMessageDialog, frame: &mut Frame) {
             .style(Style::default().fg(result_color));
         frame.render_widget(result_widget, chunks[3]);
     } else {
-        let help = Paragraph::new(" Tab: Next Field | Ctrl+Enter/F5: Send | Esc: Cancel ")
+        let help = Paragraph::new(" Tab: Next Field | Ctrl+N: Null | Ctrl+Enter/F5: Send | Esc: Cancel ")
             .style(Style::default().fg(Color::DarkGray))
             .alignment(Alignment::Center);
         frame.render_widget(help, chunks[3]);
@@ -232,7 +292,7 @@ fn render_reset_offset(dialog: &ResetOffsetDialog, frame: &mut Frame) {
 }
 
 fn render_edit_cluster(dialog: &EditClusterDialog, frame: &mut Frame) {
-    let area = centered_rect(60, 70, frame.area());
+    let area = centered_rect(60, 90, frame.area());
     frame.render_widget(Clear, area);
 
     let title = if dialog.editing_index.is_some() { " Edit Cluster " } else { " Add Cluster " };
@@ -245,7 +305,8 @@ fn render_edit_cluster(dialog: &EditClusterDialog, frame: &mut Frame) {
     frame.render_widget(block, area);
 
     let auth_types = ["None", "SASL/PLAIN", "SCRAM-SHA-256", "SCRAM-SHA-512", "SSL/TLS",
-                      "SASL_SSL/PLAIN", "SASL_SSL/SCRAM-256", "SASL_SSL/SCRAM-512"];
+                      "SASL_SSL/PLAIN", "SASL_SSL/SCRAM-256", "SASL_SSL/SCRAM-512",
+                      "SASL/OAUTHBEARER", "SASL_SSL/OAUTHBEARER", "OAUTHBEARER (unsecured JWT)"];
     let auth_display = format!("{} (←/→ to change)", auth_types.get(dialog.auth_type).unwrap_or(&"Unknown"));
 
     let mut constraints = vec![
@@ -257,6 +318,8 @@ fn render_edit_cluster(dialog: &EditClusterDialog, frame: &mut Frame) {
     let needs_credentials = matches!(dialog.auth_type, 1 | 2 | 3 | 5 | 6 | 7);
     let needs_ca = matches!(dialog.auth_type, 5 | 6 | 7);
     let needs_ssl_certs = dialog.auth_type == 4;
+    let needs_oauth = matches!(dialog.auth_type, 8 | 9);
+    let needs_principal = dialog.auth_type == 10;
 
     if needs_credentials {
         constraints.push(Constraint::Length(3)); // username
@@ -270,6 +333,21 @@ fn render_edit_cluster(dialog: &EditClusterDialog, frame: &mut Frame) {
         constraints.push(Constraint::Length(3)); // client_cert
         constraints.push(Constraint::Length(3)); // client_key
     }
+    if needs_oauth {
+        constraints.push(Constraint::Length(3)); // token endpoint
+        constraints.push(Constraint::Length(3)); // client id
+        constraints.push(Constraint::Length(3)); // client secret
+        constraints.push(Constraint::Length(3)); // scope (optional)
+        if dialog.auth_type == 9 {
+            constraints.push(Constraint::Length(3)); // ca_cert path (optional)
+        }
+    }
+    if needs_principal {
+        constraints.push(Constraint::Length(3)); // principal
+    }
+    constraints.push(Constraint::Length(3)); // properties
+    constraints.push(Constraint::Length(3)); // environment
+    constraints.push(Constraint::Length(3)); // read-only
     constraints.push(Constraint::Length(2)); // help
     constraints.push(Constraint::Min(0));
 
@@ -287,7 +365,7 @@ fn render_edit_cluster(dialog: &EditClusterDialog, frame: &mut Frame) {
     if needs_credentials {
         render_input_field(frame, chunks[field_idx], "Username", &dialog.username, dialog.focused_field == 3);
         field_idx += 1;
-        render_input_field(frame, chunks[field_idx], "Password", &"*".repeat(dialog.password.len()), dialog.focused_field == 4);
+        render_input_field(frame, chunks[field_idx], "Password (or env:/file:/cmd:/prompt:)", &masked_secret(&dialog.password), dialog.focused_field == 4);
         field_idx += 1;
     }
     if needs_ca {
@@ -302,13 +380,57 @@ fn render_edit_cluster(dialog: &EditClusterDialog, frame: &mut Frame) {
         render_input_field(frame, chunks[field_idx], "Client Key Path", &dialog.client_key, dialog.focused_field == 5);
         field_idx += 1;
     }
-
-    let help = Paragraph::new(" Tab: Next | Enter: Save | Esc: Cancel ")
-        .style(Style::default().fg(Color::DarkGray))
-        .alignment(Alignment::Center);
+    if needs_oauth {
+        render_input_field(frame, chunks[field_idx], "Token Endpoint URL", &dialog.token_endpoint, dialog.focused_field == 3);
+        field_idx += 1;
+        render_input_field(frame, chunks[field_idx], "Client ID", &dialog.username, dialog.focused_field == 4);
+        field_idx += 1;
+        render_input_field(frame, chunks[field_idx], "Client Secret (or env:/file:/cmd:/prompt:)", &masked_secret(&dialog.password), dialog.focused_field == 5);
+        field_idx += 1;
+        render_input_field(frame, chunks[field_idx], "Scope (optional)", &dialog.scope, dialog.focused_field == 6);
+        field_idx += 1;
+        if dialog.auth_type == 9 {
+            render_input_field(frame, chunks[field_idx], "CA Cert Path (optional)", &dialog.ca_cert, dialog.focused_field == 7);
+            field_idx += 1;
+        }
+    }
+    if needs_principal {
+        render_input_field(frame, chunks[field_idx], "Principal", &dialog.username, dialog.focused_field == 3);
+        field_idx += 1;
+    }
+    render_input_field(
+        frame,
+        chunks[field_idx],
+        "Properties (key=value; consumer.key=\"a;b\"; ...)",
+        &dialog.properties,
+        dialog.focused_field == dialog.properties_field(),
+    );
+    field_idx += 1;
+    let environment = format!("{} (←/→ to change)", dialog.environment.map_or("none", |e| e.label()));
+    render_input_field(frame, chunks[field_idx], "Environment", &environment, dialog.focused_field == dialog.environment_field());
+    field_idx += 1;
+    let read_only = format!("[{}] reject topic, ACL, produce and other changes (Space to toggle)", if dialog.read_only { "x" } else { " " });
+    render_input_field(frame, chunks[field_idx], "Read-only", &read_only, dialog.focused_field == dialog.read_only_field());
+    field_idx += 1;
+
+    let help = match dialog.error {
+        Some(ref error) => Paragraph::new(format!(" ✗ {}", error)).style(Style::default().fg(Color::Red)),
+        None => Paragraph::new(" Tab: Next | Enter: Save | Esc: Cancel ").style(Style::default().fg(Color::DarkGray)),
+    }
+    .alignment(Alignment::Center);
     frame.render_widget(help, chunks[field_idx]);
 }
 
+/// Literal secrets are masked; references like `env:KAFKA_PASSWORD` are shown
+/// so they can be checked.
+fn masked_secret(value: &str) -> String {
+    if crate::secrets::is_reference(value) {
+        value.to_string()
+    } else {
+        "*".repeat(value.chars().count())
+    }
+}
+
 fn render_connection_test(dialog: &ConnectionTestDialog, frame: &mut Frame) {
     let area = centered_rect(50, 20, frame.area());
     frame.render_widget(Clear, area);
@@ -347,3 +469,370 @@ fn render_connection_test(dialog: &ConnectionTestDialog, frame: &mut Frame) {
         .alignment(Alignment::Center);
     frame.render_widget(help, chunks[1]);
 }
+
+fn render_elect_leaders(dialog: &ElectLeadersDialog, frame: &mut Frame) {
+    let area = centered_rect(50, 50, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Preferred Leader Election: {} ", dialog.topic))
+        .title_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(3), Constraint::Length(2)])
+        .margin(1)
+        .split(inner);
+
+    let summary = match (&dialog.results, dialog.running) {
+        (Some(_), _) => " Election finished".to_string(),
+        (None, true) => " ⏳ Running election...".to_string(),
+        (None, false) => format!(" Elect the preferred leader for {} partition(s)?", dialog.partitions.len()),
+    };
+    frame.render_widget(Paragraph::new(summary).style(Style::default().fg(Color::White)), chunks[0]);
+
+    let lines: Vec<Line> = match &dialog.results {
+        Some(results) => results.iter().map(|r| match &r.outcome {
+            Ok(msg) => Line::from(Span::styled(
+                format!(" ✓ Partition {}: {}", r.partition, msg),
+                Style::default().fg(Color::Green),
+            )),
+            Err(msg) => Line::from(Span::styled(
+                format!(" ✗ Partition {}: {}", r.partition, msg),
+                Style::default().fg(Color::Red),
+            )),
+        }).collect(),
+        None => dialog.partitions.iter()
+            .map(|p| Line::from(format!(" Partition {}", p)))
+            .collect(),
+    };
+    let list = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(list, chunks[1]);
+
+    let help_text = if dialog.results.is_some() || dialog.running { " Esc: Close " } else { " Enter: Elect | Esc: Cancel " };
+    let help = Paragraph::new(help_text)
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[2]);
+}
+
+fn render_replication_factor(dialog: &ReplicationFactorDialog, frame: &mut Frame) {
+    let area = centered_rect(50, 25, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Replication Factor: {} ", dialog.topic))
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Length(2), Constraint::Min(0)])
+        .margin(1)
+        .split(inner);
+
+    render_input_field(frame, chunks[0], "New Replication Factor", &dialog.replication_factor, true);
+
+    let help = Paragraph::new(" Enter: Plan | Esc: Cancel ")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
+}
+
+fn render_password_prompt(dialog: &PasswordPromptDialog, frame: &mut Frame) {
+    let area = centered_rect(50, 25, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" 🔑 Password for {} ", dialog.cluster_name))
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Length(2), Constraint::Min(0)])
+        .margin(1)
+        .split(inner);
+
+    render_input_field(frame, chunks[0], "Password", &"*".repeat(dialog.password.chars().count()), true);
+
+    let action = if dialog.test_only { "Test" } else { "Connect" };
+    let help = Paragraph::new(format!(" Enter: {} | Esc: Cancel ", action))
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
+}
+
+fn render_import_clusters(dialog: &ImportClustersDialog, frame: &mut Frame) {
+    let area = centered_rect(70, 70, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Import Clusters ")
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3), Constraint::Length(2)])
+        .margin(1)
+        .split(inner);
+
+    render_input_field(
+        frame,
+        chunks[0],
+        "client.properties / kcat.conf paths (comma separated)",
+        &dialog.paths,
+        dialog.preview.is_none(),
+    );
+
+    let mut lines = Vec::new();
+    for imported in dialog.preview.iter().flatten() {
+        let cluster = &imported.cluster;
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", cluster.name), Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("({}) ", cluster.brokers), Style::default().fg(Color::DarkGray)),
+            Span::styled(cluster.auth.label(), Style::default().fg(Color::Yellow)),
+            Span::styled(
+                format!("  {} extra properties  from {}", cluster.properties.len(), imported.source.display()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        for note in &imported.notes {
+            lines.push(Line::from(Span::styled(format!("  ! {}", note), Style::default().fg(Color::Yellow))));
+        }
+    }
+    for error in &dialog.errors {
+        lines.push(Line::from(Span::styled(format!("✗ {}", error), Style::default().fg(Color::Red))));
+    }
+    let preview = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::ALL).title(" Preview ").border_style(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(preview, chunks[1]);
+
+    let help = match &dialog.preview {
+        Some(preview) => format!(" Enter: Add {} cluster(s) | type to edit paths | Esc: Cancel ", preview.len()),
+        None => " Enter: Preview | Esc: Cancel ".to_string(),
+    };
+    let help = Paragraph::new(help)
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[2]);
+}
+
+fn render_confirm(dialog: &ConfirmDialog, frame: &mut Frame) {
+    let area = centered_rect(50, 25, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" ⚠ {} ", dialog.title))
+        .title_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(2), Constraint::Length(2)])
+        .margin(1)
+        .split(inner);
+
+    let message = Paragraph::new(format!(" {}", dialog.message))
+        .style(Style::default().fg(Color::White))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(message, chunks[0]);
+
+    let help = Paragraph::new(" Enter: Confirm | Esc: Cancel ")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
+}
+
+fn render_create_acl(dialog: &CreateAclDialog, frame: &mut Frame) {
+    let area = centered_rect(60, 80, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Create ACL ")
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut constraints = vec![Constraint::Length(3); CreateAclDialog::FIELD_COUNT];
+    constraints.push(Constraint::Length(2)); // help
+    constraints.push(Constraint::Min(0));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .margin(1)
+        .split(inner);
+
+    let binding = dialog.to_binding();
+    let choice = |label: &str| format!("{} (←/→ to change)", label);
+    let resource_name = if binding.resource_type == AclResourceType::Cluster {
+        binding.resource_name.clone()
+    } else {
+        dialog.resource_name.clone()
+    };
+
+    render_input_field(frame, chunks[0], "Resource Type", &choice(binding.resource_type.label()), dialog.focused_field == 0);
+    render_input_field(frame, chunks[1], "Resource Name", &resource_name, dialog.focused_field == 1);
+    render_input_field(frame, chunks[2], "Pattern Type", &choice(binding.pattern_type.label()), dialog.focused_field == 2);
+    render_input_field(frame, chunks[3], "Principal (e.g. User:alice)", &dialog.principal, dialog.focused_field == 3);
+    render_input_field(frame, chunks[4], "Host", &dialog.host, dialog.focused_field == 4);
+    render_input_field(frame, chunks[5], "Operation", &choice(binding.operation.label()), dialog.focused_field == 5);
+    render_input_field(frame, chunks[6], "Permission", &choice(binding.permission.label()), dialog.focused_field == 6);
+
+    let help = Paragraph::new(" Tab: Next | Enter: Create | Esc: Cancel ")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[CreateAclDialog::FIELD_COUNT]);
+}
+
+fn render_search(dialog: &SearchDialog, frame: &mut Frame) {
+    let area = centered_rect(60, 50, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Search: {} ", dialog.topic))
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ])
+        .margin(1)
+        .split(inner);
+
+    render_input_field(frame, chunks[0], "Filter", &dialog.query, dialog.focused_field == 0);
+    render_input_field(frame, chunks[1], "From (offset, time or -1d; empty = earliest)", &dialog.from, dialog.focused_field == 1);
+    render_input_field(frame, chunks[2], "To (offset, time or -1h; empty = latest)", &dialog.to, dialog.focused_field == 2);
+
+    if let Some(ref error) = dialog.error {
+        let error = Paragraph::new(format!(" ✗ {}", error)).style(Style::default().fg(Color::Red));
+        frame.render_widget(error, chunks[3]);
+    } else {
+        let hint = Paragraph::new(" Times: 2024-05-01, 2024-05-01T12:00:00, now, -30m, -2h, -1d")
+            .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(hint, chunks[3]);
+    }
+
+    let help = Paragraph::new(" Tab: Next Field | Enter: Search | Esc: Cancel ")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[4]);
+}
+
+fn render_timeline(dialog: &TimelineDialog, frame: &mut Frame) {
+    let area = centered_rect(50, 40, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Timeline: {} ", dialog.topic))
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ])
+        .margin(1)
+        .split(inner);
+
+    render_input_field(frame, chunks[0], "From (time or -1h)", &dialog.from, dialog.focused_field == 0);
+    render_input_field(frame, chunks[1], "To (time or -10m; empty = now)", &dialog.to, dialog.focused_field == 1);
+
+    let bucket = Paragraph::new(Line::from(vec![
+        Span::styled(" Bucket: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(format!("◀ per {} ▶", dialog.bucket.label()), Style::default().fg(Color::Cyan)),
+    ]));
+    frame.render_widget(bucket, chunks[2]);
+
+    if let Some(ref error) = dialog.error {
+        let error = Paragraph::new(format!(" ✗ {}", error)).style(Style::default().fg(Color::Red));
+        frame.render_widget(error, chunks[3]);
+    } else {
+        let hint = Paragraph::new(" Times: 2024-05-01, 2024-05-01T12:00:00, now, -30m, -2h, -1d")
+            .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(hint, chunks[3]);
+    }
+
+    let help = Paragraph::new(" Tab: Next Field | ←/→: Bucket | Enter: Count | Esc: Cancel ")
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[4]);
+}
+
+fn render_go_to_offset(dialog: &GoToOffsetDialog, frame: &mut Frame) {
+    let area = centered_rect(40, 40, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Go to Offset: {} ", dialog.topic))
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(2),
+            Constraint::Min(0),
+        ])
+        .margin(1)
+        .split(inner);
+
+    render_input_field(frame, chunks[0], "Partition", &dialog.partition, dialog.focused_field == 0);
+    render_input_field(frame, chunks[1], "Offset", &dialog.offset, dialog.focused_field == 1);
+    render_input_field(frame, chunks[2], "Records around it", &dialog.context, dialog.focused_field == 2);
+
+    let footer = match dialog.error {
+        Some(ref error) => Paragraph::new(format!(" ✗ {}", error)).style(Style::default().fg(Color::Red)),
+        None => Paragraph::new(" Tab: Next Field | Enter: Go | Esc: Cancel ")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center),
+    };
+    frame.render_widget(footer, chunks[3]);
+}
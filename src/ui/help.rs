@@ -46,6 +46,17 @@ pub fn render_help(frame: &mut Frame) {
             Span::raw("Toggle this help"),
         ]),
         Line::from(""),
+        Line::from(Span::styled("Brokers", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Enter     ", Style::default().fg(Color::Cyan)),
+            Span::raw("Show broker configuration"),
+        ]),
+        Line::from(vec![
+            Span::styled("  x / b     ", Style::default().fg(Color::Cyan)),
+            Span::raw("Plan broker drain / cluster rebalance"),
+        ]),
+        Line::from(""),
         Line::from(Span::styled("Topics", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
         Line::from(""),
         Line::from(vec![
@@ -60,11 +71,49 @@ pub fn render_help(frame: &mut Frame) {
             Span::styled("  m         ", Style::default().fg(Color::Cyan)),
             Span::raw("Browse messages for topic"),
         ]),
+        Line::from(vec![
+            Span::styled("  v         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Latest value per key (compacted topics)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  a         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Key distribution and partition skew"),
+        ]),
+        Line::from(vec![
+            Span::styled("  e / E     ", Style::default().fg(Color::Cyan)),
+            Span::raw("Elect preferred leader (marked / all drifted)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  f         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Plan a replication factor change"),
+        ]),
         Line::from(vec![
             Span::styled("  r         ", Style::default().fg(Color::Cyan)),
             Span::raw("Refresh data"),
         ]),
         Line::from(""),
+        Line::from(Span::styled("ACLs", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  c / d     ", Style::default().fg(Color::Cyan)),
+            Span::raw("Create / delete binding (asks for confirmation)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  /         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Filter by principal or resource"),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled("Audit Log", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Enter     ", Style::default().fg(Color::Cyan)),
+            Span::raw("Show entry as JSON"),
+        ]),
+        Line::from(vec![
+            Span::styled("  /         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Filter by cluster, user, operation or arguments"),
+        ]),
+        Line::from(""),
         Line::from(Span::styled("Messages", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
         Line::from(""),
         Line::from(vec![
@@ -83,6 +132,38 @@ pub fn render_help(frame: &mut Frame) {
             Span::styled("  2         ", Style::default().fg(Color::Cyan)),
             Span::raw("Consume from latest"),
         ]),
+        Line::from(vec![
+            Span::styled("  /         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Filter, e.g. value.status == \"FAILED\" and key ~ /^user-/"),
+        ]),
+        Line::from(vec![
+            Span::styled("  f         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Search a range of the topic (c cancels)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  g         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Go to a partition and offset"),
+        ]),
+        Line::from(vec![
+            Span::styled("  b         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Switch between live tail and pages"),
+        ]),
+        Line::from(vec![
+            Span::styled("  n / N     ", Style::default().fg(Color::Cyan)),
+            Span::raw("Next / previous page"),
+        ]),
+        Line::from(vec![
+            Span::styled("  z         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Show times in UTC / local time"),
+        ]),
+        Line::from(vec![
+            Span::styled("  t         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Show / hide consumption stats"),
+        ]),
+        Line::from(vec![
+            Span::styled("  T         ", Style::default().fg(Color::Cyan)),
+            Span::raw("Message count per minute / hour"),
+        ]),
         Line::from(""),
         Line::from(Span::styled("General", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
         Line::from(""),
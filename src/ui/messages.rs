@@ -1,21 +1,37 @@
+use super::client_stats::format_bytes;
 use crate::app::*;
+use crate::config::TimeZone;
+use crate::kafka::page::Page;
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
 pub fn render_messages(app: &App, frame: &mut Frame, area: Rect, topic: &str) {
+    // Page position or live stats: one line per partition, at most a quarter of the view
+    let show_stats = app.messages.consuming && app.messages.show_stats && !app.messages.paged;
+    let position_height = match app.messages.page {
+        Some(ref page) if app.messages.paged => (page.partitions.len() as u16 + 2).min(area.height / 4).max(3),
+        _ if show_stats => (app.messages.stats.partitions.len() as u16 + 3).min(area.height / 4).max(6),
+        _ => 0,
+    };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),
             Constraint::Length(3),
+            Constraint::Length(position_height),
             Constraint::Min(5),
             Constraint::Length(2),
         ])
         .split(area);
 
     // Title with consuming status
-    let status_icon = if app.messages.consuming { "⏺ Consuming" } else { "⏸ Paused" };
-    let status_color = if app.messages.consuming { Color::Green } else { Color::Yellow };
+    let (status_icon, status_color) = if app.messages.paged {
+        ("📄 Page", Color::Cyan)
+    } else if app.messages.consuming {
+        ("⏺ Consuming", Color::Green)
+    } else {
+        ("⏸ Paused", Color::Yellow)
+    };
     let title = Line::from(vec![
         Span::styled(format!(" Messages: {} ", topic), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Span::styled(format!("[{}]", status_icon), Style::default().fg(status_color)),
@@ -31,63 +47,29 @@ pub fn render_messages(app: &App, frame: &mut Frame, area: Rect, topic: &str) {
     } else {
         Style::default().fg(Color::DarkGray)
     };
-    let search = Paragraph::new(format!(" 🔍 {}", if app.messages.search_query.is_empty() { "Filter messages..." } else { &app.messages.search_query }))
+    let mut search_line = vec![Span::raw(format!(
+        " 🔍 {}",
+        if app.messages.search_query.is_empty() { "Filter: text, value.status == \"FAILED\", key ~ /^user-/, header.trace-id exists..." } else { &app.messages.search_query }
+    ))];
+    if let Some(ref error) = app.messages.filter_error {
+        search_line.push(Span::styled(format!("  ✗ {}", error), Style::default().fg(Color::Red)));
+    }
+    let search = Paragraph::new(Line::from(search_line))
         .style(search_style)
         .block(Block::default().borders(Borders::ALL).title(" Filter ").border_style(search_style));
     frame.render_widget(search, chunks[1]);
 
+    if let Some(page) = app.messages.page.as_ref().filter(|_| app.messages.paged) {
+        render_page_position(page, frame, chunks[2]);
+    } else if show_stats {
+        render_consume_stats(&app.messages.stats, frame, chunks[2]);
+    }
+
     if app.messages.show_detail {
         // Detail view of selected message
         let filtered = app.messages.filtered_messages();
         if let Some(msg) = filtered.get(app.messages.selected) {
-            let mut text = Vec::new();
-            text.push(Line::from(vec![
-                Span::styled("Partition: ", Style::default().fg(Color::DarkGray)),
-                Span::styled(msg.partition.to_string(), Style::default().fg(Color::White)),
-                Span::styled("  Offset: ", Style::default().fg(Color::DarkGray)),
-                Span::styled(msg.offset.to_string(), Style::default().fg(Color::White)),
-            ]));
-            if let Some(ts) = msg.timestamp {
-                let dt = chrono::DateTime::from_timestamp_millis(ts)
-                    .map(|d| d.format("%Y-%m-%d %H:%M:%S%.3f").to_string())
-                    .unwrap_or_else(|| ts.to_string());
-                text.push(Line::from(vec![
-                    Span::styled("Timestamp: ", Style::default().fg(Color::DarkGray)),
-                    Span::styled(dt, Style::default().fg(Color::White)),
-                ]));
-            }
-            text.push(Line::from(vec![
-                Span::styled("Key: ", Style::default().fg(Color::DarkGray)),
-                Span::styled(msg.key.as_deref().unwrap_or("<null>"), Style::default().fg(Color::Yellow)),
-            ]));
-            if !msg.headers.is_empty() {
-                text.push(Line::from(Span::styled("Headers:", Style::default().fg(Color::DarkGray))));
-                for (k, v) in &msg.headers {
-                    text.push(Line::from(format!("  {}: {}", k, v)));
-                }
-            }
-            text.push(Line::from(Span::styled("Value:", Style::default().fg(Color::DarkGray))));
-
-            // Try to pretty-print JSON
-            let value_display = if let Ok(json) = serde_json::from_str::<serde_json::Value>(&msg.value) {
-                serde_json::to_string_pretty(&json).unwrap_or_else(|_| msg.value.clone())
-            } else {
-                msg.value.clone()
-            };
-            for line in value_display.lines() {
-                text.push(Line::from(Span::styled(format!("  {}", line), Style::default().fg(Color::Green))));
-            }
-
-            let detail = Paragraph::new(text)
-                .wrap(Wrap { trim: false })
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .title(" Message Detail ")
-                        .title_style(Style::default().fg(Color::Cyan))
-                        .border_style(Style::default().fg(Color::Cyan)),
-                );
-            frame.render_widget(detail, chunks[2]);
+            render_message_detail(msg, app.config.display.timezone, frame, chunks[3]);
         }
     } else {
         // Table view
@@ -102,20 +84,20 @@ pub fn render_messages(app: &App, frame: &mut Frame, area: Rect, topic: &str) {
 
         let rows: Vec<Row> = filtered.iter().enumerate().map(|(i, msg)| {
             let ts = msg.timestamp
-                .and_then(|t| chrono::DateTime::from_timestamp_millis(t))
-                .map(|d| d.format("%H:%M:%S").to_string())
+                .map(|t| app.config.display.timezone.format(t, "%H:%M:%S"))
                 .unwrap_or_default();
-            let value_preview: String = msg.value.chars().take(60).collect();
             let style = if i == app.messages.selected {
                 Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else if app.messages.target == Some((msg.partition, msg.offset)) {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
             } else {
                 Style::default().fg(Color::White)
             };
             Row::new(vec![
                 Cell::from(msg.partition.to_string()),
                 Cell::from(msg.offset.to_string()),
-                Cell::from(msg.key.as_deref().unwrap_or("").to_string()),
-                Cell::from(value_preview),
+                Cell::from(key_span(msg)),
+                Cell::from(value_span(msg, 60)),
                 Cell::from(ts),
             ]).style(style)
         }).collect();
@@ -135,11 +117,255 @@ pub fn render_messages(app: &App, frame: &mut Frame, area: Rect, topic: &str) {
                 .title_style(Style::default().fg(Color::Cyan))
                 .border_style(Style::default().fg(if app.focus == Focus::Content { Color::Cyan } else { Color::DarkGray })),
         );
-        frame.render_widget(table, chunks[2]);
+        frame.render_widget(table, chunks[3]);
     }
 
     // Help line
-    let help = Paragraph::new(" s: Start/Stop | Enter: Detail | Esc: Back/Close | p: Produce | /: Filter | 1: Earliest | 2: Latest ")
+    let help = if app.messages.paged {
+        " n/PgDn: Next page | N/PgUp: Previous page | 1: First | 2: Last | b: Live tail | Enter: Detail | /: Filter | g: Go to offset | z: UTC/local "
+    } else {
+        " s: Start/Stop | b: Pages | Enter: Detail | Esc: Back/Close | p: Produce | /: Filter | f: Search range | g: Go to offset | 1: Earliest | 2: Latest | t: Stats | T: Timeline | z: UTC/local "
+    };
+    let help = Paragraph::new(help)
         .style(Style::default().fg(Color::DarkGray));
-    frame.render_widget(help, chunks[3]);
+    frame.render_widget(help, chunks[4]);
+}
+
+/// "partition 2: 1200–1299 of 0–54000" for every partition of the page
+fn render_page_position(page: &Page, frame: &mut Frame, area: Rect) {
+    let lines: Vec<Line> = page.partitions.iter().map(|p| {
+        let shown = if p.count == 0 {
+            "—".to_string()
+        } else {
+            format!("{}–{}", p.start, p.end - 1)
+        };
+        let log = if p.high > p.low {
+            format!("{}–{}", p.low, p.high - 1)
+        } else {
+            "empty".to_string()
+        };
+        Line::from(vec![
+            Span::styled(format!(" partition {}: ", p.partition), Style::default().fg(Color::White)),
+            Span::styled(shown, Style::default().fg(Color::Cyan)),
+            Span::styled(format!(" of {}  ({} on page)", log, p.count), Style::default().fg(Color::DarkGray)),
+        ])
+    }).collect();
+    let mut title = format!(" Page of {} ", page.records.len());
+    if page.at_start() {
+        title.push_str("· first ");
+    }
+    if page.at_end() {
+        title.push_str("· last ");
+    }
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_widget(widget, area);
+}
+
+/// Rates per partition next to a sparkline of the overall rate
+fn render_consume_stats(stats: &ConsumeStats, frame: &mut Frame, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(area);
+
+    let header = Row::new(vec![
+        Cell::from("Part").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("msg/s").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("bytes/s").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Position").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("High").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Cell::from("Behind").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+    ]).height(1);
+    let offset = |o: Option<i64>| o.map(|o| o.to_string()).unwrap_or_else(|| "-".to_string());
+    let rows: Vec<Row> = stats.partitions.iter().map(|(partition, p)| {
+        let (messages, bytes) = p.rates.rate();
+        let behind_color = match p.behind() {
+            Some(0) => Color::Green,
+            Some(_) => Color::Yellow,
+            None => Color::DarkGray,
+        };
+        Row::new(vec![
+            Cell::from(partition.to_string()),
+            Cell::from(format!("{:.1}", messages)),
+            Cell::from(format_bytes(bytes as i64)),
+            Cell::from(offset(p.position)),
+            Cell::from(offset(p.high)),
+            Cell::from(Span::styled(offset(p.behind()), Style::default().fg(behind_color))),
+        ])
+    }).collect();
+    let table = Table::new(rows, [
+        Constraint::Length(6),
+        Constraint::Length(9),
+        Constraint::Length(11),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Min(8),
+    ])
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Partitions ")
+            .title_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_widget(table, chunks[0]);
+
+    let (messages, bytes) = stats.rates.rate();
+    // The most recent seconds that fit, one bar per second
+    let width = chunks[1].width.saturating_sub(2) as usize;
+    let history: Vec<u64> = stats.rates.buckets.iter().rev().take(width).rev().map(|b| b.0).collect();
+    let sparkline = Sparkline::default()
+        .data(&history)
+        .style(Style::default().fg(Color::Green))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    " {:.1} msg/s · {}/s · {} behind ",
+                    messages,
+                    format_bytes(bytes as i64),
+                    stats.behind()
+                ))
+                .title_style(Style::default().fg(Color::Cyan))
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+    frame.render_widget(sparkline, chunks[1]);
+}
+
+pub fn render_message_detail(msg: &KafkaMessage, timezone: TimeZone, frame: &mut Frame, area: Rect) {
+    let mut text = Vec::new();
+    let mut position = vec![
+        Span::styled("Partition: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(msg.partition.to_string(), Style::default().fg(Color::White)),
+        Span::styled("  Offset: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(msg.offset.to_string(), Style::default().fg(Color::White)),
+    ];
+    if let Some(epoch) = msg.leader_epoch {
+        position.push(Span::styled("  Leader epoch: ", Style::default().fg(Color::DarkGray)));
+        position.push(Span::styled(epoch.to_string(), Style::default().fg(Color::White)));
+    }
+    text.push(Line::from(position));
+    if let Some(ts) = msg.timestamp {
+        let kind = msg.timestamp_type.map(|t| t.label()).unwrap_or("unknown type");
+        text.push(Line::from(vec![
+            Span::styled("Timestamp: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(timezone.format(ts, "%Y-%m-%d %H:%M:%S%.3f"), Style::default().fg(Color::White)),
+            Span::styled(format!(" {}", timezone.label()), Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("  {}", age(ts)), Style::default().fg(Color::Cyan)),
+            Span::styled(format!("  ({})", kind), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    text.push(Line::from(vec![
+        Span::styled("Size: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(format_bytes(msg.total_size() as i64), Style::default().fg(Color::White)),
+        Span::styled(
+            format!(
+                "  (key {}, value {}, headers {})",
+                format_bytes(msg.key_size as i64),
+                format_bytes(msg.value_size as i64),
+                format_bytes(msg.headers_size as i64)
+            ),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]));
+    text.push(Line::from(vec![
+        Span::styled("Key: ", Style::default().fg(Color::DarkGray)),
+        match msg.key {
+            Some(ref key) => Span::styled(key.clone(), Style::default().fg(Color::Yellow)),
+            None => null_span(),
+        },
+    ]));
+    if !msg.headers.is_empty() {
+        text.push(Line::from(Span::styled("Headers:", Style::default().fg(Color::DarkGray))));
+        for (k, v) in &msg.headers {
+            text.push(Line::from(vec![
+                Span::raw(format!("  {}: ", k)),
+                match v {
+                    Some(v) => Span::raw(v.clone()),
+                    None => null_span(),
+                },
+            ]));
+        }
+    }
+
+    match msg.value {
+        None => text.push(Line::from(vec![
+            Span::styled("Value: ", Style::default().fg(Color::DarkGray)),
+            tombstone_span(),
+        ])),
+        Some(ref value) => {
+            text.push(Line::from(Span::styled("Value:", Style::default().fg(Color::DarkGray))));
+            // Try to pretty-print JSON
+            let value_display = if let Ok(json) = serde_json::from_str::<serde_json::Value>(value) {
+                serde_json::to_string_pretty(&json).unwrap_or_else(|_| value.clone())
+            } else {
+                value.clone()
+            };
+            if value_display.is_empty() {
+                text.push(Line::from(Span::styled("  (empty)", Style::default().fg(Color::DarkGray))));
+            }
+            for line in value_display.lines() {
+                text.push(Line::from(Span::styled(format!("  {}", line), Style::default().fg(Color::Green))));
+            }
+        }
+    }
+
+    let detail = Paragraph::new(text)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Message Detail ")
+                .title_style(Style::default().fg(Color::Cyan))
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+    frame.render_widget(detail, area);
+}
+
+fn null_span() -> Span<'static> {
+    Span::styled("<null>", Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC))
+}
+
+fn tombstone_span() -> Span<'static> {
+    Span::styled("⌫ tombstone", Style::default().fg(Color::Magenta).add_modifier(Modifier::ITALIC))
+}
+
+/// Key for a table cell; a null key reads differently from an empty one.
+pub fn key_span(msg: &KafkaMessage) -> Span<'static> {
+    match msg.key {
+        Some(ref key) => Span::raw(key.clone()),
+        None => null_span(),
+    }
+}
+
+/// The first `max` characters of the value, or the tombstone marker.
+pub fn value_span(msg: &KafkaMessage, max: usize) -> Span<'static> {
+    match msg.value {
+        Some(ref value) => Span::raw(value.chars().take(max).collect::<String>()),
+        None => tombstone_span(),
+    }
+}
+
+/// "3m ago", or "in 5s" for a timestamp ahead of the local clock
+fn age(millis: i64) -> String {
+    let delta = chrono::Utc::now().timestamp_millis() - millis;
+    let secs = delta.abs() / 1000;
+    let span = match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86_399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86_400),
+    };
+    if delta >= 0 {
+        format!("{} ago", span)
+    } else {
+        format!("in {}", span)
+    }
 }
@@ -1,8 +1,18 @@
 mod sidebar;
 mod dashboard;
+mod brokers;
 mod topics;
 mod messages;
 mod consumer_groups;
+mod reassignment;
+mod search;
+mod topic_table;
+mod timeline;
+mod analysis;
+mod acls;
+mod audit;
+mod client_stats;
+mod config_error;
 mod dialogs;
 mod help;
 
@@ -10,6 +20,8 @@ use crate::app::*;
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
+pub use config_error::render_config_error;
+
 pub fn render(app: &App, frame: &mut Frame) {
     if app.route == Route::ClusterSelect {
         render_cluster_select(app, frame);
@@ -22,13 +34,27 @@ pub fn render(app: &App, frame: &mut Frame) {
         return;
     }
 
+    let mut area = frame.area();
+    if let Some(cluster) = app.active_cluster_config().filter(|c| c.is_prod()) {
+        let banner_area = Rect { height: 1, ..area };
+        let read_only = if cluster.read_only { " (read-only)" } else { "" };
+        let banner = Paragraph::new(format!(
+            " ⚠ PRODUCTION — {}{} — changes require typing the cluster name",
+            cluster.name, read_only
+        ))
+        .style(Style::default().fg(Color::White).bg(Color::Red).add_modifier(Modifier::BOLD));
+        frame.render_widget(banner, banner_area);
+        area.y += 1;
+        area.height = area.height.saturating_sub(1);
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Min(3),
             Constraint::Length(3),
         ])
-        .split(frame.area());
+        .split(area);
 
     let main_chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -90,17 +116,32 @@ fn render_cluster_select(app: &App, frame: &mut Frame) {
                 crate::config::AuthConfig::SaslSslPlain { .. } => "🔒 SASL_SSL/PLAIN",
                 crate::config::AuthConfig::SaslSslScram256 { .. } => "🔒 SASL_SSL/256",
                 crate::config::AuthConfig::SaslSslScram512 { .. } => "🔒 SASL_SSL/512",
+                crate::config::AuthConfig::SaslOAuthBearer { .. } => "🔐 OAUTHBEARER",
+                crate::config::AuthConfig::SaslSslOAuthBearer { .. } => "🔒 SASL_SSL/OAUTHBEARER",
+                crate::config::AuthConfig::SaslOAuthBearerUnsecured { .. } => "🔓 OAUTHBEARER (unsecured)",
             };
             let style = if i == app.cluster_select_index {
                 Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD)
             } else {
                 Style::default().fg(Color::White)
             };
-            ListItem::new(Line::from(vec![
+            let mut spans = vec![
                 Span::styled(format!("  {} ", c.name), style),
                 Span::styled(format!("({}) ", c.brokers), Style::default().fg(Color::DarkGray)),
                 Span::styled(auth_badge, Style::default().fg(Color::Yellow)),
-            ]))
+            ];
+            if let Some(env) = c.environment {
+                let color = match env {
+                    crate::config::Environment::Dev => Color::Green,
+                    crate::config::Environment::Staging => Color::Yellow,
+                    crate::config::Environment::Prod => Color::Red,
+                };
+                spans.push(Span::styled(format!(" [{}]", env.label()), Style::default().fg(color).add_modifier(Modifier::BOLD)));
+            }
+            if c.read_only {
+                spans.push(Span::styled(" read-only", Style::default().fg(Color::Cyan)));
+            }
+            ListItem::new(Line::from(spans))
         }).collect();
 
         let list = List::new(items)
@@ -110,7 +151,7 @@ fn render_cluster_select(app: &App, frame: &mut Frame) {
     }
 
     // Help bar
-    let help_text = Paragraph::new(" Enter: Connect | a: Add | e: Edit | d: Delete | t: Test | ?: Help | q: Quit ")
+    let help_text = Paragraph::new(" Enter: Connect | a: Add | i: Import | e: Edit | d: Delete | t: Test | ?: Help | q: Quit ")
         .style(Style::default().fg(Color::DarkGray))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
@@ -120,18 +161,32 @@ fn render_cluster_select(app: &App, frame: &mut Frame) {
 fn render_content(app: &App, frame: &mut Frame, area: Rect) {
     match &app.route {
         Route::Dashboard => dashboard::render_dashboard(app, frame, area),
+        Route::Brokers => brokers::render_brokers(app, frame, area),
+        Route::BrokerDetail(id) => brokers::render_broker_detail(app, frame, area, *id),
         Route::Topics => topics::render_topics(app, frame, area),
         Route::TopicDetail(name) => topics::render_topic_detail(app, frame, area, name),
         Route::Messages(topic) => messages::render_messages(app, frame, area, topic),
+        Route::TopicSearch(topic) => search::render_search(app, frame, area, topic),
+        Route::TopicTable(topic) => topic_table::render_topic_table(app, frame, area, topic),
+        Route::Timeline(topic) => timeline::render_timeline(app, frame, area, topic),
+        Route::TopicAnalysis(topic) => analysis::render_analysis(app, frame, area, topic),
         Route::ConsumerGroups => consumer_groups::render_consumer_groups(app, frame, area),
         Route::ConsumerGroupDetail(name) => consumer_groups::render_consumer_group_detail(app, frame, area, name),
+        Route::Reassignment => reassignment::render_reassignment(app, frame, area),
+        Route::Acls => acls::render_acls(app, frame, area),
+        Route::ClientStats => client_stats::render_client_stats(app, frame, area),
+        Route::AuditLog => audit::render_audit_log(app, frame, area),
         _ => {}
     }
 }
 
 fn render_status_bar(app: &App, frame: &mut Frame, area: Rect) {
     let cluster_name = app.active_cluster_config()
-        .map(|c| c.name.clone())
+        .map(|c| {
+            let env = c.environment.map(|e| format!(" [{}]", e.label())).unwrap_or_default();
+            let read_only = if c.read_only { " RO" } else { "" };
+            format!("{}{}{}", c.name, env, read_only)
+        })
         .unwrap_or_else(|| "Not connected".to_string());
 
     let chunks = Layout::default()
@@ -139,13 +194,22 @@ fn render_status_bar(app: &App, frame: &mut Frame, area: Rect) {
         .constraints([
             Constraint::Length(30),
             Constraint::Min(20),
-            Constraint::Length(20),
+            Constraint::Length(34),
         ])
         .split(area);
 
+    let (state, color) = match &app.connection {
+        ConnectionStatus::Connected => (" connected ".to_string(), Color::Green),
+        ConnectionStatus::Degraded(_) => (" degraded ".to_string(), Color::Yellow),
+        ConnectionStatus::Reconnecting { attempt, .. } => (format!(" reconnecting #{} ", attempt), Color::Red),
+    };
+    let mut cluster_block = Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray));
+    if app.active_cluster.is_some() {
+        cluster_block = cluster_block.title(state).title_style(Style::default().fg(color));
+    }
     let cluster_info = Paragraph::new(format!(" 📡 {}", cluster_name))
-        .style(Style::default().fg(Color::Green))
-        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+        .style(Style::default().fg(color))
+        .block(cluster_block);
     frame.render_widget(cluster_info, chunks[0]);
 
     let status = Paragraph::new(format!(" {}", app.status_message))
@@ -154,7 +218,12 @@ fn render_status_bar(app: &App, frame: &mut Frame, area: Rect) {
     frame.render_widget(status, chunks[1]);
 
     let time = chrono::Local::now().format("%H:%M:%S").to_string();
-    let time_widget = Paragraph::new(format!(" {} ", time))
+    let updated = match (app.last_updated, app.refresh_interval()) {
+        (Some(_), Some(_)) if app.dialog.is_some() => "⏸ paused  ".to_string(),
+        (Some(at), _) => format!("updated {}s ago  ", at.elapsed().as_secs()),
+        (None, _) => String::new(),
+    };
+    let time_widget = Paragraph::new(format!(" {}{} ", updated, time))
         .style(Style::default().fg(Color::DarkGray))
         .alignment(Alignment::Right)
         .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
@@ -0,0 +1,129 @@
+use crate::app::*;
+use crate::kafka::reassign::MoveStatus;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+pub fn render_reassignment(app: &App, frame: &mut Frame, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(2),
+        ])
+        .split(area);
+
+    let state = &app.reassignment;
+    let goal = match &state.plan {
+        Some(plan) => plan.goal.describe(),
+        None if state.loading => "Planning...".to_string(),
+        None => "No plan".to_string(),
+    };
+    let title = Paragraph::new(format!(" Reassignment: {}", goal))
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(title, chunks[0]);
+
+    let done = state.progress.iter().filter(|p| p.status == MoveStatus::Done).count();
+    let mut summary = vec![
+        Span::styled(" Moves: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(state.move_count().to_string(), Style::default().fg(Color::White)),
+    ];
+    if state.submitted {
+        summary.push(Span::styled("  Done: ", Style::default().fg(Color::DarkGray)));
+        summary.push(Span::styled(
+            format!("{}/{}", done, state.move_count()),
+            Style::default().fg(if done == state.move_count() { Color::Green } else { Color::Yellow }),
+        ));
+    }
+    if let Some(ref path) = state.exported_to {
+        summary.push(Span::styled("  Exported: ", Style::default().fg(Color::DarkGray)));
+        summary.push(Span::styled(path.clone(), Style::default().fg(Color::White)));
+    }
+    let summary = Paragraph::new(Line::from(summary))
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(summary, chunks[1]);
+
+    if state.plan.is_none() {
+        let text = if state.loading {
+            "Building plan..."
+        } else {
+            "No plan yet. In Brokers press 'x' to drain a broker or 'b' to rebalance;\nin a topic's detail press 'f' to change its replication factor."
+        };
+        let empty = Paragraph::new(text)
+            .style(Style::default().fg(Color::DarkGray))
+            .block(Block::default().borders(Borders::ALL).title(" Plan ").border_style(Style::default().fg(Color::DarkGray)));
+        frame.render_widget(empty, chunks[2]);
+    } else {
+        let header = Row::new(vec![
+            Cell::from("Topic").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Cell::from("Partition").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Cell::from("Current").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Cell::from("Target").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Cell::from("Status").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        ]).height(1);
+
+        let moves = state.plan.as_ref().map(|p| p.moves.as_slice()).unwrap_or_default();
+        let rows: Vec<Row> = moves.iter().enumerate().map(|(i, m)| {
+            let status = match (state.submitted, state.status_of(&m.topic, m.partition)) {
+                (false, _) => Span::styled("Planned", Style::default().fg(Color::DarkGray)),
+                (true, Some(MoveStatus::Done)) => Span::styled("● Done", Style::default().fg(Color::Green)),
+                (true, Some(MoveStatus::InProgress)) => Span::styled("● Moving", Style::default().fg(Color::Yellow)),
+                (true, _) => Span::styled("● Pending", Style::default().fg(Color::DarkGray)),
+            };
+            let style = if i == state.selected {
+                Style::default().bg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            Row::new(vec![
+                Cell::from(m.topic.clone()),
+                Cell::from(m.partition.to_string()),
+                Cell::from(replica_diff(&m.current, &m.removed(), Color::Red)),
+                Cell::from(replica_diff(&m.target, &m.added(), Color::Green)),
+                Cell::from(status),
+            ]).style(style)
+        }).collect();
+
+        let mut table_state = TableState::default().with_selected(Some(state.selected));
+        let table = Table::new(rows, [
+            Constraint::Min(20),
+            Constraint::Length(10),
+            Constraint::Min(15),
+            Constraint::Min(15),
+            Constraint::Length(12),
+        ])
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Plan (red: leaving, green: joining) ")
+                .title_style(Style::default().fg(Color::Cyan))
+                .border_style(Style::default().fg(if app.focus == Focus::Content { Color::Cyan } else { Color::DarkGray })),
+        );
+        frame.render_stateful_widget(table, chunks[2], &mut table_state);
+    }
+
+    let help = Paragraph::new(" x: Export JSON | s: Submit | r: Refresh progress ")
+        .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, chunks[3]);
+}
+
+/// Renders a replica list, highlighting the brokers that change.
+fn replica_diff<'a>(replicas: &[i32], changed: &[i32], color: Color) -> Line<'a> {
+    let mut spans = vec![Span::raw("[")];
+    for (i, r) in replicas.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(", "));
+        }
+        if changed.contains(r) {
+            spans.push(Span::styled(r.to_string(), Style::default().fg(color).add_modifier(Modifier::BOLD)));
+        } else {
+            spans.push(Span::raw(r.to_string()));
+        }
+    }
+    spans.push(Span::raw("]"));
+    Line::from(spans)
+}
+
@@ -0,0 +1,115 @@
+use super::messages::{key_span, render_message_detail, value_span};
+use crate::app::*;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+pub fn render_search(app: &App, frame: &mut Frame, area: Rect, topic: &str) {
+    let state = &app.search;
+    // One line per partition, at most a third of the view
+    let progress_height = (state.scans.len() as u16 + 2).min(area.height / 3).max(3);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(progress_height),
+            Constraint::Min(5),
+            Constraint::Length(2),
+        ])
+        .split(area);
+
+    let (status, status_color) = if state.running { ("⏺ Scanning", Color::Green) } else { ("■ Done", Color::Yellow) };
+    let title = Line::from(vec![
+        Span::styled(format!(" Search: {} ", topic), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("[{}]", status), Style::default().fg(status_color)),
+        Span::styled(format!(" {}  ", state.query), Style::default().fg(Color::White)),
+        Span::styled(state.range.clone(), Style::default().fg(Color::DarkGray)),
+    ]);
+    let title_widget = Paragraph::new(title)
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(title_widget, chunks[0]);
+
+    let bar_width = 30;
+    let progress: Vec<Line> = state.scans.iter().map(|scan| {
+        let filled = (scan.progress() * bar_width as f64).round() as usize;
+        let color = if scan.done() { Color::Green } else { Color::Cyan };
+        Line::from(vec![
+            Span::styled(format!(" P{:<4}", scan.partition), Style::default().fg(Color::White)),
+            Span::styled("█".repeat(filled), Style::default().fg(color)),
+            Span::styled("░".repeat(bar_width - filled), Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!(" {:>5.1}%  {}/{}  {} match(es)", scan.progress() * 100.0, scan.scanned(), scan.total(), scan.matches),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ])
+    }).collect();
+    let progress_title = match &state.summary {
+        Some(summary) => format!(" {} ", summary),
+        None => format!(" Progress: {}/{} ", state.scanned(), state.total()),
+    };
+    let progress_widget = Paragraph::new(progress).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(progress_title)
+            .title_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_widget(progress_widget, chunks[1]);
+
+    if state.show_detail {
+        if let Some(msg) = state.matches.get(state.selected) {
+            render_message_detail(msg, app.config.display.timezone, frame, chunks[2]);
+        }
+    } else {
+        let header = Row::new(vec![
+            Cell::from("Part").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Cell::from("Offset").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Cell::from("Key").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Cell::from("Value").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Cell::from("Timestamp").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        ]).height(1);
+
+        let rows: Vec<Row> = state.matches.iter().enumerate().map(|(i, msg)| {
+            let ts = msg.timestamp
+                .map(|t| app.config.display.timezone.format(t, "%Y-%m-%d %H:%M:%S"))
+                .unwrap_or_default();
+            let style = if i == state.selected {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Row::new(vec![
+                Cell::from(msg.partition.to_string()),
+                Cell::from(msg.offset.to_string()),
+                Cell::from(key_span(msg)),
+                Cell::from(value_span(msg, 60)),
+                Cell::from(ts),
+            ]).style(style)
+        }).collect();
+
+        let mut table_state = TableState::default().with_selected(Some(state.selected));
+        let table = Table::new(rows, [
+            Constraint::Length(6),
+            Constraint::Length(10),
+            Constraint::Length(15),
+            Constraint::Min(30),
+            Constraint::Length(19),
+        ])
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Matches ({}) ", state.matches.len()))
+                .title_style(Style::default().fg(Color::Cyan))
+                .border_style(Style::default().fg(if app.focus == Focus::Content { Color::Cyan } else { Color::DarkGray })),
+        );
+        frame.render_stateful_widget(table, chunks[2], &mut table_state);
+    }
+
+    let help = if state.running {
+        " c: Cancel | Enter: Detail | n: New search | z: UTC/local | Esc: Back "
+    } else {
+        " Enter: Detail | n: New search | z: UTC/local | Esc: Back "
+    };
+    let help = Paragraph::new(help).style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, chunks[3]);
+}
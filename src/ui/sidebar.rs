@@ -11,8 +11,13 @@ pub fn render_sidebar(app: &App, frame: &mut Frame, area: Rect) {
         let indent = "  ".repeat(item.indent);
         let icon = match &item.route {
             Route::Dashboard => "📊",
+            Route::Brokers => "🖥",
             Route::Topics => "📋",
             Route::ConsumerGroups => "👥",
+            Route::Acls => "🛡",
+            Route::Reassignment => "🔀",
+            Route::ClientStats => "📈",
+            Route::AuditLog => "📜",
             _ => "  ",
         };
         let style = if i == app.sidebar.selected {
@@ -0,0 +1,121 @@
+use crate::app::*;
+use crate::kafka::timeline::BucketSize;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+pub fn render_timeline(app: &App, frame: &mut Frame, area: Rect, topic: &str) {
+    let state = &app.timeline;
+    let timezone = app.config.display.timezone;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(8),
+            Constraint::Length(3),
+            Constraint::Length(2),
+        ])
+        .split(area);
+
+    let scope = match state.partition {
+        Some(p) => format!("partition {}", p),
+        None => "all partitions".to_string(),
+    };
+    let mut title = vec![
+        Span::styled(format!(" Timeline: {} ", topic), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("[{}] ", scope), Style::default().fg(Color::Yellow)),
+    ];
+    if let Some(ref timeline) = state.timeline {
+        let last = timeline.counts.len().saturating_sub(1);
+        title.push(Span::styled(
+            format!(
+                "{} → {}, per {}",
+                timezone.format(timeline.bucket_start(0), "%Y-%m-%d %H:%M"),
+                timezone.format(timeline.bucket_start(last + 1), "%Y-%m-%d %H:%M"),
+                timeline.bucket.label()
+            ),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    let title_widget = Paragraph::new(Line::from(title))
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(title_widget, chunks[0]);
+
+    let chart_block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Messages per bucket ")
+        .title_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(if app.focus == Focus::Content { Color::Cyan } else { Color::DarkGray }));
+    let Some(ref timeline) = state.timeline else {
+        let (text, color) = match (&state.error, state.loading) {
+            (Some(error), _) => (format!(" ✗ {}", error), Color::Red),
+            (None, true) => (" Looking up offsets at each bucket boundary...".to_string(), Color::DarkGray),
+            (None, false) => (" Press n to choose a window".to_string(), Color::DarkGray),
+        };
+        frame.render_widget(Paragraph::new(text).style(Style::default().fg(color)).block(chart_block), chunks[1]);
+        render_help(frame, chunks[3]);
+        return;
+    };
+
+    // Bars get narrower as the window grows; past one column each, the
+    // chart scrolls to keep the selected bucket in view
+    let buckets = timeline.counts.len();
+    let width = chunks[1].width.saturating_sub(2) as usize;
+    let (bar_width, gap) = if buckets * 2 <= width { ((width / buckets).saturating_sub(1).clamp(1, 5), 1) } else { (1, 0) };
+    let visible = (width / (bar_width + gap)).max(1);
+    let first = state.selected.saturating_sub(visible / 2).min(buckets.saturating_sub(visible));
+    let bars: Vec<Bar> = (first..buckets.min(first + visible))
+        .map(|i| {
+            let color = if i == state.selected { Color::Yellow } else { Color::Cyan };
+            Bar::default()
+                .value(timeline.count(i, state.partition))
+                .text_value(String::new())
+                .style(Style::default().fg(color))
+        })
+        .collect();
+    let chart = BarChart::default()
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(bar_width as u16)
+        .bar_gap(gap as u16)
+        .block(chart_block);
+    frame.render_widget(chart, chunks[1]);
+
+    let start = timeline.bucket_start(state.selected);
+    let format = match timeline.bucket {
+        BucketSize::Minute => "%Y-%m-%d %H:%M",
+        BucketSize::Hour => "%Y-%m-%d %H:00",
+    };
+    let mut info = vec![
+        Span::styled(format!(" {} {} ", timezone.format(start, format), timezone.label()), Style::default().fg(Color::White)),
+        Span::styled(
+            format!("{} message(s)", timeline.count(state.selected, state.partition)),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if state.partition.is_none() && timeline.partitions.len() > 1 {
+        let busiest = timeline
+            .partitions
+            .iter()
+            .map(|&p| (p, timeline.count(state.selected, Some(p))))
+            .max_by_key(|&(_, count)| count)
+            .filter(|&(_, count)| count > 0);
+        if let Some((p, count)) = busiest {
+            info.push(Span::styled(format!("  busiest: partition {} ({})", p, count), Style::default().fg(Color::DarkGray)));
+        }
+    }
+    let info_widget = Paragraph::new(Line::from(info)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Selected ")
+            .title_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_widget(info_widget, chunks[2]);
+
+    render_help(frame, chunks[3]);
+}
+
+fn render_help(frame: &mut Frame, area: Rect) {
+    let help = Paragraph::new(" ←/→: Select | Enter: Browse from here | p: Partition / total | n: New window | z: UTC/local | Esc: Back ")
+        .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, area);
+}
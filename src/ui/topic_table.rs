@@ -0,0 +1,142 @@
+use super::messages::{render_message_detail, value_span};
+use crate::app::*;
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+pub fn render_topic_table(app: &App, frame: &mut Frame, area: Rect, topic: &str) {
+    let state = &app.topic_table;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(2),
+        ])
+        .split(area);
+
+    let (status, status_color) = if state.running { ("⏺ Reading", Color::Green) } else { ("■ Done", Color::Yellow) };
+    let mut title = vec![
+        Span::styled(format!(" Latest per key: {} ", topic), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("[{}] ", status), Style::default().fg(status_color)),
+    ];
+    match &state.summary {
+        Some(summary) => {
+            title.push(Span::styled(
+                format!(
+                    "{} key(s) from {} record(s), {} deleted by tombstones, {} without a key",
+                    state.rows.len(),
+                    summary.scanned,
+                    summary.deleted,
+                    summary.null_keys
+                ),
+                Style::default().fg(Color::DarkGray),
+            ));
+            if let Some(ref reason) = summary.stopped {
+                title.push(Span::styled(format!("  {}", reason), Style::default().fg(Color::Red)));
+            }
+        }
+        None => title.push(Span::styled(
+            format!("{}/{} record(s), {} key(s) so far", state.scanned(), state.total(), state.keys),
+            Style::default().fg(Color::DarkGray),
+        )),
+    }
+    if !state.is_compacted() {
+        title.push(Span::styled("  not a compacted topic", Style::default().fg(Color::Yellow)));
+    }
+    let title_widget = Paragraph::new(Line::from(title))
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+    frame.render_widget(title_widget, chunks[0]);
+
+    let search_style = if app.focus == Focus::Search {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let search = Paragraph::new(format!(" 🔍 {}", if state.search_query.is_empty() { "Filter by key or value..." } else { &state.search_query }))
+        .style(search_style)
+        .block(Block::default().borders(Borders::ALL).title(" Search ").border_style(search_style));
+    frame.render_widget(search, chunks[1]);
+
+    let filtered = state.filtered_rows();
+    if state.running {
+        let bar_width = 30;
+        let progress: Vec<Line> = state.scans.iter().map(|scan| {
+            let filled = (scan.progress() * bar_width as f64).round() as usize;
+            let color = if scan.done() { Color::Green } else { Color::Cyan };
+            Line::from(vec![
+                Span::styled(format!(" P{:<4}", scan.partition), Style::default().fg(Color::White)),
+                Span::styled("█".repeat(filled), Style::default().fg(color)),
+                Span::styled("░".repeat(bar_width - filled), Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format!(" {:>5.1}%  {}/{}", scan.progress() * 100.0, scan.scanned(), scan.total()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])
+        }).collect();
+        let progress_widget = Paragraph::new(progress).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Reading to the high watermark ")
+                .title_style(Style::default().fg(Color::Cyan))
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        frame.render_widget(progress_widget, chunks[2]);
+    } else if state.show_detail {
+        if let Some(msg) = filtered.get(state.selected) {
+            render_message_detail(msg, app.config.display.timezone, frame, chunks[2]);
+        }
+    } else {
+        let header = Row::new(vec![
+            Cell::from("Key").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Cell::from("Value").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Cell::from("Part").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Cell::from("Offset").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Cell::from("Updated").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        ]).height(1);
+
+        let rows: Vec<Row> = filtered.iter().enumerate().map(|(i, msg)| {
+            let ts = msg.timestamp
+                .map(|t| app.config.display.timezone.format(t, "%Y-%m-%d %H:%M:%S"))
+                .unwrap_or_default();
+            let style = if i == state.selected {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Row::new(vec![
+                Cell::from(msg.key.clone().unwrap_or_default()),
+                Cell::from(value_span(msg, 80)),
+                Cell::from(msg.partition.to_string()),
+                Cell::from(msg.offset.to_string()),
+                Cell::from(ts),
+            ]).style(style)
+        }).collect();
+
+        let mut table_state = TableState::default().with_selected(Some(state.selected));
+        let table = Table::new(rows, [
+            Constraint::Length(24),
+            Constraint::Min(30),
+            Constraint::Length(6),
+            Constraint::Length(10),
+            Constraint::Length(19),
+        ])
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Keys ({}) ", filtered.len()))
+                .title_style(Style::default().fg(Color::Cyan))
+                .border_style(Style::default().fg(if app.focus == Focus::Content { Color::Cyan } else { Color::DarkGray })),
+        );
+        frame.render_stateful_widget(table, chunks[2], &mut table_state);
+    }
+
+    let help = if state.running {
+        " c: Cancel | Esc: Back "
+    } else {
+        " Enter: Detail | /: Search | r: Reload | z: UTC/local | Esc: Back "
+    };
+    let help = Paragraph::new(help).style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(help, chunks[3]);
+}
@@ -67,7 +67,7 @@ pub fn render_topics(app: &App, frame: &mut Frame, area: Rect) {
     frame.render_widget(table, chunks[2]);
 
     // Help line
-    let help = Paragraph::new(" c: Create | d: Delete | Enter: Detail | m: Messages | /: Search | r: Refresh ")
+    let help = Paragraph::new(" c: Create | d: Delete | Enter: Detail | m: Messages | v: Latest per key | a: Analyse | /: Search | r: Refresh ")
         .style(Style::default().fg(Color::DarkGray));
     frame.render_widget(help, chunks[3]);
 }
@@ -89,6 +89,7 @@ pub fn render_topic_detail(app: &App, frame: &mut Frame, area: Rect, topic_name:
 
     // Partition details
     let header = Row::new(vec![
+        Cell::from("").style(Style::default()),
         Cell::from("Partition").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Cell::from("Leader").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
         Cell::from("Replicas").style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
@@ -97,17 +98,21 @@ pub fn render_topic_detail(app: &App, frame: &mut Frame, area: Rect, topic_name:
     ]).height(1);
 
     let rows: Vec<Row> = app.topic_detail.partitions.iter().enumerate().map(|(i, p)| {
-        let status = if p.isr.len() == p.replicas.len() {
-            Span::styled("● Synced", Style::default().fg(Color::Green))
-        } else {
+        let status = if p.isr.len() != p.replicas.len() {
             Span::styled("● Under-replicated", Style::default().fg(Color::Yellow))
+        } else if !p.has_preferred_leader() {
+            Span::styled("● Not preferred leader", Style::default().fg(Color::Magenta))
+        } else {
+            Span::styled("● Synced", Style::default().fg(Color::Green))
         };
+        let mark = if app.topic_detail.marked_partitions.contains(&p.id) { "*" } else { "" };
         let style = if i == app.topic_detail.selected_partition {
             Style::default().fg(Color::Black).bg(Color::Cyan)
         } else {
             Style::default().fg(Color::White)
         };
         Row::new(vec![
+            Cell::from(mark),
             Cell::from(p.id.to_string()),
             Cell::from(format!("Broker {}", p.leader)),
             Cell::from(format!("{:?}", p.replicas)),
@@ -117,11 +122,12 @@ pub fn render_topic_detail(app: &App, frame: &mut Frame, area: Rect, topic_name:
     }).collect();
 
     let table = Table::new(rows, [
+        Constraint::Length(1),
         Constraint::Length(10),
         Constraint::Length(12),
         Constraint::Min(15),
         Constraint::Min(15),
-        Constraint::Length(20),
+        Constraint::Length(24),
     ])
     .header(header)
     .block(
@@ -133,7 +139,7 @@ pub fn render_topic_detail(app: &App, frame: &mut Frame, area: Rect, topic_name:
     );
     frame.render_widget(table, chunks[1]);
 
-    let help = Paragraph::new(" Esc: Back | m: Messages | r: Refresh ")
+    let help = Paragraph::new(" Esc: Back | m: Messages | v: Latest per key | a: Analyse | Space: Mark | e: Elect leader | E: Elect all drifted | f: Replication factor | r: Refresh ")
         .style(Style::default().fg(Color::DarkGray));
     frame.render_widget(help, chunks[2]);
 }